@@ -1,11 +1,18 @@
-use crate::utils::error::{Result, FinkError, PromptError, ExternalError};
-use crate::utils::frontmatter::FrontmatterUpdater;
+use crate::utils::error::{Result, FinkError, PromptError, ExternalError, StorageError, ValidationError};
+use crate::utils::frontmatter::{FrontmatterDocument, FrontmatterUpdater};
+use crate::utils::includes;
 use crate::utils::templates::TemplateGenerator;
 use crate::utils::config::Config;
-use crate::utils::constants::PROMPTS_DIR;
+use crate::utils::constants::{PROMPTS_DIR, TEMPLATES_DIR};
+use crate::utils::diff::unified_diff;
+use crate::utils::output_format::OutputFormat;
+use crate::utils::query::SearchQuery;
+use crate::utils::tags::{self, tag_matches};
+use crate::utils::variables::{self, VariableSpec};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::cell::RefCell;
-use crate::application::models::{PromptMetadata, PromptFilter, SearchType, PromptType};
+use crate::application::models::{PromptMetadata, PromptFilter, PromptType, PromptTypes, Recipe, SearchResult, Revision, TagChange, TagOperation, TrashEntry};
 use crate::application::repository::{PromptRepository, FileSystemRepository};
 use crate::application::traits::PromptApplication;
 use crate::storage::FileSystem;
@@ -15,6 +22,9 @@ pub struct DefaultPromptApplication {
     repository: Box<dyn PromptRepository>,
     clipboard: RefCell<ClipboardManager>,
     editor_launcher: RefCell<EditorLauncher>,
+    prompt_types: PromptTypes,
+    /// Problems met while reading prompt bodies, kept until `take_warnings`
+    warnings: RefCell<Vec<String>>,
 }
 
 impl DefaultPromptApplication {
@@ -27,12 +37,19 @@ impl DefaultPromptApplication {
             repository,
             clipboard,
             editor_launcher: RefCell::new(EditorLauncher::new()),
+            prompt_types: PromptTypes::default(),
+            warnings: RefCell::new(Vec::new()),
         })
     }
     
     pub fn with_config(config: &Config) -> Result<Self> {
         let storage = FileSystem::new(config.storage_path().to_path_buf());
         let repository = Box::new(FileSystemRepository::new(storage));
+        
+        // Purging is housekeeping; a failure here must not block normal use
+        if config.trash_retention_days() > 0 {
+            let _ = repository.purge_trash(config.trash_retention_days());
+        }
         let clipboard = RefCell::new(ClipboardManager::new());
         let editor_launcher = EditorLauncher::with_editor(config.editor());
 
@@ -40,6 +57,8 @@ impl DefaultPromptApplication {
             repository,
             clipboard,
             editor_launcher: RefCell::new(editor_launcher),
+            prompt_types: config.prompt_types().clone(),
+            warnings: RefCell::new(Vec::new()),
         })
     }
     
@@ -54,12 +73,181 @@ impl DefaultPromptApplication {
             .ok_or_else(|| FinkError::Prompt(PromptError::NotFound(name.to_string())))
     }
     
+    /// Rejects a type that is not declared in the config
+    fn check_prompt_type(&self, prompt_type: &PromptType) -> Result<()> {
+        if self.prompt_types.get(prompt_type).is_none() {
+            let declared: Vec<&str> = self.prompt_types.definitions().iter().map(|d| d.id.as_str()).collect();
+            return Err(FinkError::Validation(ValidationError::InvalidInput(
+                "type",
+                format!("unknown prompt type '{}' (expected one of {})", prompt_type.as_str(), declared.join(", ")),
+            )));
+        }
+        Ok(())
+    }
+    
     fn get_prompt_file_path(&self, metadata: &PromptMetadata) -> PathBuf {
         self.repository
             .get_base_path()
             .join(PROMPTS_DIR)
             .join(&metadata.file_path)
     }
+    
+    /// Opens the prompt in the editor, optionally at a line, and records a revision if it changed
+    fn edit_prompt_in_editor(&self, name: &str, line: Option<usize>) -> Result<()> {
+        let metadata = self.find_prompt_metadata(name)?;
+        let file_path = self.get_prompt_file_path(&metadata);
+        let before = self.repository.read_prompt(&metadata)?;
+        
+        match line {
+            Some(line) => self.editor_launcher.borrow().launch_at_line(&file_path, line)?,
+            None => self.editor_launcher.borrow().launch(&file_path)?,
+        }
+        
+        // Record the pre-edit text if the editor changed the file
+        let after = self.repository.read_prompt(&metadata)?;
+        if after != before {
+            self.repository.record_revision(&metadata, &before)?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Works out the new file content of every prompt a tag operation changes, without writing
+    fn plan_tag_operation(&self, operation: &TagOperation) -> Result<Vec<(TagChange, String, String)>> {
+        let prompts = self.repository.list_all()?;
+        let in_use = |tag: &str| prompts.iter().any(|p| p.tags.iter().any(|t| tag_matches(t, tag)));
+        let invalid = |message: &str| FinkError::Validation(ValidationError::InvalidInput("tag", message.to_string()));
+        
+        let (sources, target) = match operation {
+            TagOperation::Rename { from, to } => (vec![from], Some(to)),
+            TagOperation::Merge { sources, into } => (sources.iter().collect(), Some(into)),
+            TagOperation::Delete(tag) => (vec![tag], None),
+        };
+        if sources.iter().chain(target.iter()).any(|tag| tag.trim_matches('/').trim().is_empty()) {
+            return Err(invalid("tag names cannot be empty"));
+        }
+        if let Some(missing) = sources.iter().find(|tag| !in_use(tag)) {
+            return Err(FinkError::Prompt(PromptError::TagNotFound(missing.to_string())));
+        }
+        match operation {
+            TagOperation::Rename { from, to } if tag_matches(to, from) || tag_matches(from, to) => {
+                return Err(invalid("a tag cannot be renamed into itself"));
+            }
+            TagOperation::Rename { to, .. } if in_use(to) => {
+                return Err(FinkError::Prompt(PromptError::TagExists(to.clone())));
+            }
+            TagOperation::Merge { sources, into } if sources.iter().any(|source| tag_matches(into, source)) => {
+                return Err(invalid("cannot merge a tag into itself"));
+            }
+            _ => {}
+        }
+        
+        let mut planned = Vec::new();
+        for prompt in prompts {
+            let after = tags::apply_operation(operation, &prompt.tags);
+            if after == prompt.tags {
+                continue;
+            }
+            let original = self.repository.read_prompt(&prompt)?;
+            let updated = FrontmatterUpdater::update_tags(&original, &prompt.name, &after)?;
+            let before = prompt.tags.clone();
+            planned.push((TagChange { prompt, before, after }, original, updated));
+        }
+        Ok(planned)
+    }
+    
+    /// Renders a new prompt from a user template in the templates directory, falling back to the
+    /// built-in templates
+    fn generate_content(&self, name: &str, template: Option<&str>, content: Option<&str>, prompt_type: PromptType) -> Result<String> {
+        if let Some(template_name) = template {
+            let template_name = Self::normalize_template_name(template_name)?;
+            if self.repository.template_exists(&template_name) {
+                let template_content = self.repository.get_template_content(&template_name)?;
+                return TemplateGenerator::generate_from_user_template(&template_content, name, prompt_type);
+            }
+        }
+        
+        TemplateGenerator::generate_with_content_and_type(name, template, content, prompt_type)
+    }
+    
+    /// Validates a template or recipe name; both are stored as flat files without folders
+    fn normalize_template_name(name: &str) -> Result<String> {
+        let normalized = Self::normalize_prompt_name(name)?;
+        if normalized.contains('/') {
+            return Err(FinkError::Validation(ValidationError::InvalidInput(
+                "name",
+                format!("'{}' cannot contain folders", name),
+            )));
+        }
+        Ok(normalized)
+    }
+    
+    /// Body of a prompt with its frontmatter variable declarations
+    fn read_body(&self, metadata: &PromptMetadata) -> Result<(String, Vec<VariableSpec>)> {
+        let content = self.repository.read_prompt(metadata)?;
+        let (body, declared) = FrontmatterUpdater::body_and_declarations(&content, |e| {
+            let warning = format!("Failed to parse frontmatter in {}: {}", metadata.file_path, e);
+            let mut warnings = self.warnings.borrow_mut();
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        });
+        Ok((body.trim().to_string(), declared))
+    }
+    
+    /// Body of a prompt with includes inlined, plus the variable declarations of every prompt
+    /// involved. The including prompt's declarations win over those of the prompts it includes.
+    fn expand_body(&self, metadata: &PromptMetadata) -> Result<(String, Vec<VariableSpec>)> {
+        let (body, mut declared) = self.read_body(metadata)?;
+        
+        let expanded = includes::expand(metadata.id(), &body, &mut |name| {
+            let Some(included) = self.repository.find_by_name(name)? else {
+                return Ok(None);
+            };
+            let (included_body, included_declared) = self.read_body(&included)?;
+            for spec in included_declared {
+                if !declared.iter().any(|d| d.name == spec.name) {
+                    declared.push(spec);
+                }
+            }
+            Ok(Some((included.id().to_string(), included_body)))
+        })?;
+        
+        Ok((expanded, declared))
+    }
+    
+    fn get_template_file_path(&self, name: &str) -> PathBuf {
+        self.repository
+            .get_base_path()
+            .join(TEMPLATES_DIR)
+            .join(format!("{}.md", name))
+    }
+    
+    /// Normalizes a prompt name into its storage path, e.g. `Backend/Code Review` -> `backend/code-review`
+    fn normalize_prompt_name(name: &str) -> Result<String> {
+        let segments: Vec<String> = name
+            .trim()
+            .trim_matches('/')
+            .split('/')
+            .map(|segment| segment.trim().to_lowercase().replace(' ', "-"))
+            .collect();
+        
+        // Reject empty, hidden and parent (`..`) segments so prompts stay inside the prompts directory
+        if segments.iter().any(|segment| segment.is_empty() || segment.starts_with('.')) {
+            return Err(FinkError::Validation(ValidationError::InvalidInput(
+                "name",
+                format!("'{}' is not a valid prompt path", name),
+            )));
+        }
+        
+        Ok(segments.join("/"))
+    }
+    
+    /// Name without its folder, used for the frontmatter of nested prompts
+    fn leaf_name(name: &str) -> &str {
+        let trimmed = name.trim().trim_matches('/');
+        trimmed.rsplit('/').next().unwrap_or(trimmed).trim()
+    }
 }
 
 impl PromptApplication for DefaultPromptApplication {
@@ -68,9 +256,10 @@ impl PromptApplication for DefaultPromptApplication {
             .map_err(FinkError::from)?;
         
         if let Some(filter) = filter {
-            if let Some(tags) = filter.tags {
-                prompts.retain(|p| p.tags.iter().any(|t| tags.contains(t)));
+            for prompt_type in &filter.types {
+                self.check_prompt_type(prompt_type)?;
             }
+            prompts.retain(|p| filter.matches(p));
         }
         
         Ok(prompts)
@@ -85,25 +274,46 @@ impl PromptApplication for DefaultPromptApplication {
         Ok((metadata, content))
     }
 
+    fn get_expanded_prompt(&self, identifier: &str) -> Result<(PromptMetadata, String)> {
+        let metadata = self.find_prompt_metadata(identifier)?;
+        let (body, _) = self.expand_body(&metadata)?;
+        Ok((metadata, body))
+    }
+
+    fn get_frontmatter(&self, identifier: &str) -> Result<serde_json::Value> {
+        let metadata = self.find_prompt_metadata(identifier)?;
+        let content = self.repository.read_prompt(&metadata)?;
+        FrontmatterDocument::parse(&content)?.to_json()
+    }
+
     fn copy_to_clipboard(&self, content: &str) -> Result<()> {
         self.clipboard.borrow_mut().copy(content)
             .map_err(|e| FinkError::External(ExternalError::ClipboardError(e.to_string())))
     }
 
-    fn search_prompts(&self, query: &str, search_type: SearchType) -> Result<Vec<PromptMetadata>> {
-        self.repository.search(query, search_type)
+    fn search_prompts(&self, query: &str, content: bool) -> Result<Vec<SearchResult>> {
+        let mut query = SearchQuery::parse(query)
+            .map_err(|e| FinkError::Validation(ValidationError::InvalidInput("query", e.to_string())))?;
+        if content {
+            query = query.include_content();
+        }
+        self.repository.search(&query)
             .map_err(FinkError::from)
     }
 
+    fn match_prompt(&self, query: &SearchQuery, metadata: &PromptMetadata) -> Option<SearchResult> {
+        self.repository.match_prompt(query, metadata)
+    }
+
     fn create_prompt(&self, name: &str, template: Option<&str>) -> Result<()> {
-        let normalized_name = name.to_lowercase().replace(' ', "-");
+        let normalized_name = Self::normalize_prompt_name(name)?;
         
         // Check if prompt already exists
         if self.repository.prompt_exists(&normalized_name) {
             return Err(FinkError::Prompt(PromptError::AlreadyExists(name.to_string())));
         }
         
-        let content = TemplateGenerator::generate(name, template)?;
+        let content = self.generate_content(Self::leaf_name(name), template, None, PromptType::default())?;
         
         // Create the prompt using repository
         self.repository.create_prompt(&normalized_name, &content)
@@ -112,14 +322,14 @@ impl PromptApplication for DefaultPromptApplication {
     }
 
     fn create_prompt_with_content(&self, name: &str, template: Option<&str>, content: Option<String>) -> Result<()> {
-        let normalized_name = name.to_lowercase().replace(' ', "-");
+        let normalized_name = Self::normalize_prompt_name(name)?;
         
         // Check if prompt already exists
         if self.repository.prompt_exists(&normalized_name) {
             return Err(FinkError::Prompt(PromptError::AlreadyExists(name.to_string())));
         }
         
-        let prompt_content = TemplateGenerator::generate_with_content(name, template, content.as_deref())?;
+        let prompt_content = self.generate_content(Self::leaf_name(name), template, content.as_deref(), PromptType::default())?;
         
         // Create the prompt using repository
         self.repository.create_prompt(&normalized_name, &prompt_content)
@@ -128,14 +338,14 @@ impl PromptApplication for DefaultPromptApplication {
     }
 
     fn create_prompt_with_type(&self, name: &str, template: Option<&str>, prompt_type: PromptType) -> Result<()> {
-        let normalized_name = name.to_lowercase().replace(' ', "-");
+        let normalized_name = Self::normalize_prompt_name(name)?;
         
         // Check if prompt already exists
         if self.repository.prompt_exists(&normalized_name) {
             return Err(FinkError::Prompt(PromptError::AlreadyExists(name.to_string())));
         }
         
-        let content = TemplateGenerator::generate_with_type(name, template, prompt_type)?;
+        let content = self.generate_content(Self::leaf_name(name), template, None, prompt_type)?;
         
         // Create the prompt using repository
         self.repository.create_prompt(&normalized_name, &content)
@@ -144,14 +354,14 @@ impl PromptApplication for DefaultPromptApplication {
     }
 
     fn create_prompt_with_content_and_type(&self, name: &str, template: Option<&str>, content: Option<String>, prompt_type: PromptType) -> Result<()> {
-        let normalized_name = name.to_lowercase().replace(' ', "-");
+        let normalized_name = Self::normalize_prompt_name(name)?;
         
         // Check if prompt already exists
         if self.repository.prompt_exists(&normalized_name) {
             return Err(FinkError::Prompt(PromptError::AlreadyExists(name.to_string())));
         }
         
-        let prompt_content = TemplateGenerator::generate_with_content_and_type(name, template, content.as_deref(), prompt_type)?;
+        let prompt_content = self.generate_content(Self::leaf_name(name), template, content.as_deref(), prompt_type)?;
         
         // Create the prompt using repository
         self.repository.create_prompt(&normalized_name, &prompt_content)
@@ -160,12 +370,11 @@ impl PromptApplication for DefaultPromptApplication {
     }
 
     fn edit_prompt(&self, name: &str) -> Result<()> {
-        let metadata = self.find_prompt_metadata(name)?;
-        let file_path = self.get_prompt_file_path(&metadata);
-        
-        self.editor_launcher.borrow().launch(&file_path)?;
-        
-        Ok(())
+        self.edit_prompt_in_editor(name, None)
+    }
+
+    fn edit_prompt_at_line(&self, name: &str, line: usize) -> Result<()> {
+        self.edit_prompt_in_editor(name, Some(line))
     }
 
     fn delete_prompt(&self, name: &str, force: bool) -> Result<()> {
@@ -183,32 +392,272 @@ impl PromptApplication for DefaultPromptApplication {
     }
 
     fn copy_prompt(&self, name: &str) -> Result<()> {
-        // Get the prompt content
-        let (_, content) = self.get_prompt(name)?;
-        
-        // Copy to clipboard
-        self.copy_to_clipboard(&content)?;
-        
-        Ok(())
+        self.copy_prompt_with_variables(name, &HashMap::new())
+    }
+    
+    fn prompt_variables(&self, name: &str) -> Result<Vec<VariableSpec>> {
+        let metadata = self.find_prompt_metadata(name)?;
+        let (body, declared) = self.expand_body(&metadata)?;
+        Ok(variables::resolve_specs(&body, &declared))
+    }
+    
+    fn render_prompt(&self, name: &str, values: &HashMap<String, String>) -> Result<String> {
+        let metadata = self.find_prompt_metadata(name)?;
+        let (body, declared) = self.expand_body(&metadata)?;
+        let specs = variables::resolve_specs(&body, &declared);
+        variables::render_with_specs(&body, &specs, values)
+    }
+    
+    fn copy_prompt_with_variables(&self, name: &str, values: &HashMap<String, String>) -> Result<()> {
+        let content = self.render_prompt(name, values)?;
+        self.copy_to_clipboard(&content)
     }
 
     fn get_base_path(&self) -> &std::path::Path {
         self.repository.get_base_path()
     }
 
+    fn tag_counts(&self) -> Result<BTreeMap<String, usize>> {
+        let prompts = self.repository.list_all()?;
+        Ok(tags::count_tags(prompts.iter().map(|p| p.tags.as_slice())))
+    }
+
+    fn preview_tag_operation(&self, operation: &TagOperation) -> Result<Vec<TagChange>> {
+        Ok(self.plan_tag_operation(operation)?
+            .into_iter()
+            .map(|(change, _, _)| change)
+            .collect())
+    }
+
+    fn apply_tag_operation(&self, operation: &TagOperation) -> Result<Vec<TagChange>> {
+        // Every file is rewritten in memory first, so a prompt that fails to parse stops the
+        // operation before anything is written
+        let planned = self.plan_tag_operation(operation)?;
+        
+        for (index, (change, _, updated)) in planned.iter().enumerate() {
+            if let Err(e) = self.repository.write_prompt(&change.prompt, updated) {
+                // Put back the files already written, without keeping the new tags as revisions
+                let unrestored: Vec<&str> = planned[..index]
+                    .iter()
+                    .filter(|(written, original, _)| {
+                        self.repository.restore_prompt_content(&written.prompt, original).is_err()
+                    })
+                    .map(|(written, _, _)| written.prompt.file_path.as_str())
+                    .collect();
+                if unrestored.is_empty() {
+                    return Err(e.into());
+                }
+                return Err(FinkError::Storage(StorageError::Io(std::io::Error::other(format!(
+                    "{}; these prompts keep the new tags as they could not be restored: {}",
+                    e,
+                    unrestored.join(", ")
+                )))));
+            }
+        }
+        
+        Ok(planned.into_iter().map(|(change, _, _)| change).collect())
+    }
+
     fn update_prompt_tags(&self, name: &str, tags: Vec<String>) -> Result<()> {
         let metadata = self.find_prompt_metadata(name)?;
         
         let content = self.repository.read_prompt(&metadata)?;
-        let updated_content = FrontmatterUpdater::update_tags(&content, name, &tags)?;
+        let updated_content = FrontmatterUpdater::update_tags(&content, &metadata.name, &tags)?;
         
         self.repository.write_prompt(&metadata, &updated_content)?;
         
         Ok(())
     }
     
+    fn update_prompt_type(&self, name: &str, prompt_type: PromptType) -> Result<()> {
+        let metadata = self.find_prompt_metadata(name)?;
+        
+        let content = self.repository.read_prompt(&metadata)?;
+        let updated_content = FrontmatterUpdater::update_type(&content, &metadata.name, &prompt_type)?;
+        
+        self.repository.write_prompt(&metadata, &updated_content)?;
+        
+        Ok(())
+    }
+    
+    fn export_prompts(&self, identifiers: &[String], directory: &std::path::Path) -> Result<Vec<PathBuf>> {
+        let prompts = identifiers
+            .iter()
+            .map(|identifier| self.find_prompt_metadata(identifier))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.repository.export_prompts(&prompts, directory)?)
+    }
+    
     fn get_clipboard_content(&self) -> Result<String> {
         self.clipboard.borrow_mut().get_content()
             .map_err(|e| FinkError::External(ExternalError::ClipboardError(e.to_string())))
     }
-}
\ No newline at end of file
+
+    fn list_revisions(&self, name: &str) -> Result<Vec<Revision>> {
+        let metadata = self.find_prompt_metadata(name)?;
+        Ok(self.repository.list_revisions(&metadata)?)
+    }
+
+    fn diff_prompt(&self, name: &str, revision: Option<u32>) -> Result<String> {
+        let metadata = self.find_prompt_metadata(name)?;
+        
+        // Default to the most recent snapshot
+        let number = match revision {
+            Some(number) => number,
+            None => self.repository.list_revisions(&metadata)?
+                .last()
+                .map(|r| r.number)
+                .ok_or_else(|| FinkError::Validation(ValidationError::InvalidInput(
+                    "revision",
+                    format!("'{}' has no saved revisions", name),
+                )))?,
+        };
+        
+        let old = self.repository.read_revision(&metadata, number)?
+            .ok_or_else(|| FinkError::Prompt(PromptError::RevisionNotFound(name.to_string(), number)))?;
+        let current = self.repository.read_prompt(&metadata)?;
+        
+        Ok(unified_diff(
+            &old,
+            &current,
+            &format!("{} (revision {})", metadata.id(), number),
+            &format!("{} (current)", metadata.id()),
+        ))
+    }
+
+    fn restore_prompt(&self, name: &str, revision: u32) -> Result<()> {
+        let metadata = self.find_prompt_metadata(name)?;
+        
+        let content = self.repository.read_revision(&metadata, revision)?
+            .ok_or_else(|| FinkError::Prompt(PromptError::RevisionNotFound(name.to_string(), revision)))?;
+        
+        // write_prompt snapshots the current text first, so a restore can itself be undone
+        self.repository.write_prompt(&metadata, &content)?;
+        Ok(())
+    }
+
+    fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        Ok(self.repository.list_trash()?)
+    }
+
+    fn restore_from_trash(&self, identifier: &str) -> Result<TrashEntry> {
+        // Accept a trash id, or the prompt's old path/name for its most recent deletion
+        let entry = self.repository.list_trash()?
+            .into_iter()
+            .find(|entry| entry.id == identifier || entry.prompt_id() == identifier)
+            .ok_or_else(|| FinkError::Prompt(PromptError::NotInTrash(identifier.to_string())))?;
+        
+        if self.repository.prompt_exists(entry.prompt_id()) {
+            return Err(FinkError::Prompt(PromptError::AlreadyExists(entry.prompt_id().to_string())));
+        }
+        
+        Ok(self.repository.restore_from_trash(&entry.id)?)
+    }
+
+    fn empty_trash(&self) -> Result<usize> {
+        Ok(self.repository.empty_trash()?)
+    }
+
+    fn list_templates(&self) -> Result<Vec<String>> {
+        self.repository.list_templates().map_err(FinkError::from)
+    }
+
+    fn create_template(&self, name: &str) -> Result<std::path::PathBuf> {
+        let normalized_name = Self::normalize_template_name(name)?;
+        if self.repository.template_exists(&normalized_name) {
+            return Err(FinkError::Validation(ValidationError::InvalidInput(
+                "name",
+                format!("template '{}' already exists; edit it with 'fink template edit {}'", normalized_name, normalized_name),
+            )));
+        }
+        
+        self.repository.save_template(&normalized_name, TemplateGenerator::starter_user_template())?;
+        Ok(self.get_template_file_path(&normalized_name))
+    }
+
+    fn edit_template(&self, name: &str) -> Result<()> {
+        let normalized_name = Self::normalize_template_name(name)?;
+        if !self.repository.template_exists(&normalized_name) {
+            return Err(FinkError::Prompt(PromptError::TemplateNotFound(name.to_string())));
+        }
+        
+        self.editor_launcher.borrow().launch(&self.get_template_file_path(&normalized_name))
+    }
+
+    fn combine_prompts(&self, identifiers: &[String], comment: &str, format: OutputFormat) -> Result<String> {
+        let sections = identifiers
+            .iter()
+            .map(|identifier| {
+                let (metadata, content) = self.get_expanded_prompt(identifier)?;
+                Ok((metadata.prompt_type, content))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        Ok(format.combine(&sections, &self.prompt_types, comment))
+    }
+
+    fn combine_typed_prompts(&self, prompts: &[(PromptType, String)], comment: &str, format: OutputFormat) -> Result<String> {
+        for (prompt_type, identifier) in prompts {
+            self.check_prompt_type(prompt_type)?;
+            
+            let metadata = self.find_prompt_metadata(identifier)?;
+            if &metadata.prompt_type != prompt_type {
+                return Err(FinkError::Validation(ValidationError::InvalidInput(
+                    "prompt",
+                    format!("'{}' is a {} prompt, not {}", identifier, metadata.prompt_type.as_str(), prompt_type.as_str()),
+                )));
+            }
+        }
+        
+        // Combined in the configured type order, as in build mode
+        let mut ordered: Vec<&(PromptType, String)> = prompts.iter().collect();
+        ordered.sort_by_key(|(prompt_type, _)| {
+            self.prompt_types.definitions().iter().position(|d| &d.id == prompt_type)
+        });
+        let identifiers: Vec<String> = ordered.into_iter().map(|(_, identifier)| identifier.clone()).collect();
+        self.combine_prompts(&identifiers, comment, format)
+    }
+
+    fn list_recipes(&self) -> Result<Vec<Recipe>> {
+        Ok(self.repository.list_recipes()?)
+    }
+
+    fn save_recipe(&self, name: &str, prompts: Vec<String>, comment: &str) -> Result<Recipe> {
+        let normalized_name = Self::normalize_template_name(name)?;
+        if prompts.is_empty() {
+            return Err(FinkError::Validation(ValidationError::InvalidInput(
+                "prompts",
+                "a recipe needs at least one prompt".to_string(),
+            )));
+        }
+        
+        let recipe = Recipe {
+            name: normalized_name,
+            prompts,
+            comment: comment.to_string(),
+            created_at: chrono::Local::now(),
+        };
+        self.repository.save_recipe(&recipe)?;
+        Ok(recipe)
+    }
+
+    fn delete_recipe(&self, name: &str) -> Result<()> {
+        if self.repository.delete_recipe(&Self::normalize_template_name(name)?)? {
+            Ok(())
+        } else {
+            Err(FinkError::Prompt(PromptError::RecipeNotFound(name.to_string())))
+        }
+    }
+
+    fn build_recipe(&self, name: &str, format: OutputFormat) -> Result<String> {
+        let recipe = self.repository.get_recipe(&Self::normalize_template_name(name)?)?
+            .ok_or_else(|| FinkError::Prompt(PromptError::RecipeNotFound(name.to_string())))?;
+        self.combine_prompts(&recipe.prompts, &recipe.comment, format)
+    }
+
+    fn take_warnings(&self) -> Vec<String> {
+        let mut warnings = self.repository.take_warnings();
+        warnings.append(&mut self.warnings.take());
+        warnings
+    }
+}
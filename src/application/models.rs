@@ -1,47 +1,312 @@
+use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
+use crate::utils::tags::tag_matches;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
-pub enum PromptType {
-    Instruction,
-    Context,
-    InputIndicator,
-    OutputIndicator,
-    Etc,
-    #[default]
-    Whole,
+/// Type id from a prompt's frontmatter. The built-in types are available as constants;
+/// any other id is kept in lowercase so types declared in the config keep working.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PromptType(Cow<'static, str>);
+
+impl PromptType {
+    pub const INSTRUCTION: PromptType = PromptType(Cow::Borrowed("instruction"));
+    pub const CONTEXT: PromptType = PromptType(Cow::Borrowed("context"));
+    pub const INPUT_INDICATOR: PromptType = PromptType(Cow::Borrowed("input_indicator"));
+    pub const OUTPUT_INDICATOR: PromptType = PromptType(Cow::Borrowed("output_indicator"));
+    pub const ETC: PromptType = PromptType(Cow::Borrowed("etc"));
+    pub const WHOLE: PromptType = PromptType(Cow::Borrowed("whole"));
+
+    /// Parses a type id case-insensitively, so `Persona` in frontmatter matches `persona` in the config
+    pub fn new(id: &str) -> Self {
+        let id = id.trim().to_lowercase();
+        match id.replace('_', "").as_str() {
+            "instruction" => PromptType::INSTRUCTION,
+            "context" => PromptType::CONTEXT,
+            "inputindicator" => PromptType::INPUT_INDICATOR,
+            "outputindicator" => PromptType::OUTPUT_INDICATOR,
+            "etc" => PromptType::ETC,
+            "whole" => PromptType::WHOLE,
+            _ => PromptType(Cow::Owned(id)),
+        }
+    }
+
+    /// The id as written in frontmatter, e.g. `input_indicator`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for PromptType {
+    fn default() -> Self {
+        PromptType::WHOLE
+    }
 }
 
 impl fmt::Display for PromptType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            PromptType::Instruction => write!(f, "Instruction"),
-            PromptType::Context => write!(f, "Context"),
-            PromptType::InputIndicator => write!(f, "Input Indicator"),
-            PromptType::OutputIndicator => write!(f, "Output Indicator"),
-            PromptType::Etc => write!(f, "Etc"),
-            PromptType::Whole => write!(f, "Whole"),
+        match self.as_str() {
+            "instruction" => write!(f, "Instruction"),
+            "context" => write!(f, "Context"),
+            "input_indicator" => write!(f, "Input Indicator"),
+            "output_indicator" => write!(f, "Output Indicator"),
+            "etc" => write!(f, "Etc"),
+            "whole" => write!(f, "Whole"),
+            other => write!(f, "{}", other),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl Serialize for PromptType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for PromptType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // Also accepts the variant names written by older versions, e.g. `InputIndicator`
+        let id = String::deserialize(deserializer)?;
+        Ok(PromptType::new(&id))
+    }
+}
+
+/// A prompt type declared in the config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TypeDefinition {
+    pub id: PromptType,
+    /// Name shown in the create dialog, build mode and markdown output
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Whether build mode offers a step for this type
+    #[serde(default = "default_build")]
+    pub build: bool,
+}
+
+fn default_build() -> bool {
+    true
+}
+
+impl TypeDefinition {
+    pub fn new(id: PromptType, name: &str, description: &str, build: bool) -> Self {
+        Self {
+            id,
+            name: name.to_string(),
+            description: description.to_string(),
+            build,
+        }
+    }
+}
+
+/// The prompt types available to the library, in build order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PromptTypes(Vec<TypeDefinition>);
+
+impl PromptTypes {
+    pub fn new(definitions: Vec<TypeDefinition>) -> Self {
+        Self(definitions)
+    }
+
+    pub fn definitions(&self) -> &[TypeDefinition] {
+        &self.0
+    }
+
+    pub fn get(&self, prompt_type: &PromptType) -> Option<&TypeDefinition> {
+        self.0.iter().find(|definition| &definition.id == prompt_type)
+    }
+
+    /// Types offered as build steps, in the order they are combined
+    pub fn build_order(&self) -> Vec<PromptType> {
+        self.0
+            .iter()
+            .filter(|definition| definition.build)
+            .map(|definition| definition.id.clone())
+            .collect()
+    }
+
+    /// Configured display name, falling back to the type id for undeclared types
+    pub fn display_name(&self, prompt_type: &PromptType) -> String {
+        self.get(prompt_type)
+            .map(|definition| definition.name.clone())
+            .unwrap_or_else(|| prompt_type.to_string())
+    }
+
+    /// The declared type after `prompt_type`, wrapping around
+    pub fn next(&self, prompt_type: &PromptType) -> PromptType {
+        self.step(prompt_type, 1)
+    }
+
+    /// The declared type before `prompt_type`, wrapping around
+    pub fn previous(&self, prompt_type: &PromptType) -> PromptType {
+        self.step(prompt_type, self.0.len().saturating_sub(1))
+    }
+
+    fn step(&self, prompt_type: &PromptType, offset: usize) -> PromptType {
+        if self.0.is_empty() {
+            return prompt_type.clone();
+        }
+        let index = self.0.iter().position(|definition| &definition.id == prompt_type).unwrap_or(0);
+        self.0[(index + offset) % self.0.len()].id.clone()
+    }
+}
+
+impl Default for PromptTypes {
+    fn default() -> Self {
+        Self(vec![
+            TypeDefinition::new(PromptType::WHOLE, "Whole", "Complete prompt (default)", false),
+            TypeDefinition::new(PromptType::INSTRUCTION, "Instruction", "Task or instruction for the model", true),
+            TypeDefinition::new(PromptType::CONTEXT, "Context", "External information or context", true),
+            TypeDefinition::new(PromptType::INPUT_INDICATOR, "Input Indicator", "Input or question marker", true),
+            TypeDefinition::new(PromptType::OUTPUT_INDICATOR, "Output Indicator", "Output format indicator", true),
+            TypeDefinition::new(PromptType::ETC, "Etc", "Other component type", true),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PromptMetadata {
     pub name: String,
     pub file_path: String,
     pub tags: Vec<String>,
     pub prompt_type: PromptType,
+    /// Description from the frontmatter, searched along with the name and tags
+    #[serde(default)]
+    pub description: String,
+    /// `created_at` from the frontmatter, if it has one
+    #[serde(default)]
+    pub created_at: Option<DateTime<Local>>,
+    /// When the file was last written
+    #[serde(default)]
+    pub modified_at: Option<DateTime<Local>>,
+}
+
+impl PromptMetadata {
+    /// Path-based identifier relative to the prompts directory, e.g. `backend/review`
+    pub fn id(&self) -> &str {
+        self.file_path.strip_suffix(".md").unwrap_or(&self.file_path)
+    }
+
+    /// Folder the prompt lives in, if it is not at the top level
+    pub fn folder(&self) -> Option<&str> {
+        self.id().rsplit_once('/').map(|(folder, _)| folder)
+    }
+
+    /// Name prefixed with its folder, e.g. `backend/Code Review`
+    pub fn display_name(&self) -> String {
+        match self.folder() {
+            Some(folder) => format!("{}/{}", folder, self.name),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// A saved snapshot of a prompt file, numbered from 1 in the order it was taken
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Revision {
+    pub number: u32,
+    pub created_at: DateTime<Local>,
+    pub size: u64,
+}
+
+/// A deleted prompt waiting in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    /// Path relative to the prompts directory the file was deleted from
+    pub original_path: String,
+    pub deleted_at: DateTime<Local>,
+}
+
+impl TrashEntry {
+    /// Path-based identifier the prompt had before it was deleted
+    pub fn prompt_id(&self) -> &str {
+        self.original_path.strip_suffix(".md").unwrap_or(&self.original_path)
+    }
+}
+
+/// A saved build: prompts to combine in order, plus the closing comment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recipe {
+    pub name: String,
+    /// Prompt ids in the order they are combined
+    pub prompts: Vec<String>,
+    #[serde(default)]
+    pub comment: String,
+    pub created_at: DateTime<Local>,
 }
 
+/// Where a content search term was found in a prompt file
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ContentMatch {
+    /// 1-based line in the file, counting the frontmatter, so editors can jump to it
+    pub line: usize,
+    /// 1-based character column of the match within the line
+    pub column: usize,
+    /// The matching line, trimmed and shortened around the match
+    pub snippet: String,
+}
+
+/// A prompt found by a search, with the places its content matched
 #[derive(Debug, Clone)]
-pub struct PromptFilter {
-    pub tags: Option<Vec<String>>,
+pub struct SearchResult {
+    pub prompt: PromptMetadata,
+    /// Ranking score, higher is better
+    pub score: i64,
+    pub matches: Vec<ContentMatch>,
+}
+
+/// A tag edit applied across the whole library. Parent tags carry their descendants along,
+/// so renaming `lang` also renames `lang/rust`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagOperation {
+    Rename { from: String, to: String },
+    Merge { sources: Vec<String>, into: String },
+    Delete(String),
 }
 
+/// A prompt whose tags a `TagOperation` changes
 #[derive(Debug, Clone)]
-pub enum SearchType {
-    Name,
-    Content,
-    Tags,
+pub struct TagChange {
+    pub prompt: PromptMetadata,
+    pub before: Vec<String>,
+    pub after: Vec<String>,
+}
+
+/// How the tags of a `PromptFilter` combine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TagMatch {
+    /// A prompt needs at least one of the tags
+    #[default]
+    Any,
+    /// A prompt needs every tag
     All,
+}
+
+/// Narrows a prompt list by tags and types. Parent tags match their descendants.
+#[derive(Debug, Clone, Default)]
+pub struct PromptFilter {
+    pub tags: Option<Vec<String>>,
+    pub tag_match: TagMatch,
+    /// Prompts with any of these tags are left out
+    pub exclude_tags: Vec<String>,
+    /// Only prompts of these types are kept, unless empty
+    pub types: Vec<PromptType>,
+}
+
+impl PromptFilter {
+    pub fn matches(&self, prompt: &PromptMetadata) -> bool {
+        let has = |filter: &String| prompt.tags.iter().any(|tag| tag_matches(tag, filter));
+        
+        let included = match (&self.tags, self.tag_match) {
+            (Some(tags), TagMatch::Any) if !tags.is_empty() => tags.iter().any(has),
+            (Some(tags), TagMatch::All) => tags.iter().all(has),
+            _ => true,
+        };
+        
+        included
+            && !self.exclude_tags.iter().any(has)
+            && (self.types.is_empty() || self.types.contains(&prompt.prompt_type))
+    }
 }
\ No newline at end of file
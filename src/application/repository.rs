@@ -1,32 +1,75 @@
 use anyhow::Result;
-use crate::application::models::{PromptMetadata, SearchType};
+use crate::application::models::{PromptMetadata, Recipe, Revision, SearchResult, TrashEntry};
 use crate::storage::FileSystem;
-use crate::utils::constants::PROMPTS_DIR;
-use std::path::Path;
+use crate::storage::history::HistoryStore;
+use crate::storage::recipes::RecipeStore;
+use crate::storage::trash::TrashStore;
+use crate::utils::constants::{PROMPTS_DIR, TEMPLATES_DIR};
+use crate::utils::frontmatter::FrontmatterDocument;
+use crate::utils::query::{find_content_matches, SearchQuery};
+use std::path::{Path, PathBuf};
+
+// The text after the frontmatter, or the whole file if it has none
+fn prompt_body(content: &str) -> String {
+    if let Ok(document) = FrontmatterDocument::parse(content)
+        && document.has_frontmatter() {
+        return document.body().trim().to_string();
+    }
+    content.to_string()
+}
 
 /// Repository pattern for data access
 pub trait PromptRepository {
     fn list_all(&self) -> Result<Vec<PromptMetadata>>;
     fn find_by_name(&self, name: &str) -> Result<Option<PromptMetadata>>;
     fn get_content(&self, file_path: &str) -> Result<String>;
-    fn search(&self, query: &str, search_type: SearchType) -> Result<Vec<PromptMetadata>>;
+    fn search(&self, query: &SearchQuery) -> Result<Vec<SearchResult>>;
+    /// Scores one prompt against the query, with its content matches, or None if it does not match
+    fn match_prompt(&self, query: &SearchQuery, prompt: &PromptMetadata) -> Option<SearchResult>;
     fn create_prompt(&self, name: &str, content: &str) -> Result<()>;
     fn prompt_exists(&self, name: &str) -> bool;
     fn get_template_content(&self, template_name: &str) -> Result<String>;
+    fn list_templates(&self) -> Result<Vec<String>>;
+    fn template_exists(&self, template_name: &str) -> bool;
+    fn save_template(&self, template_name: &str, content: &str) -> Result<()>;
     fn get_base_path(&self) -> &std::path::Path;
     fn delete_prompt(&self, file_path: &str) -> Result<()>;
     fn read_prompt(&self, metadata: &PromptMetadata) -> Result<String>;
     fn write_prompt(&self, metadata: &PromptMetadata, content: &str) -> Result<()>;
+    /// Writes content back without keeping the text it replaces, to undo a failed change
+    fn restore_prompt_content(&self, metadata: &PromptMetadata, content: &str) -> Result<()>;
+    /// Copies prompt files into `directory`, keeping their folders. Nothing is written if any
+    /// of the files already exists there.
+    fn export_prompts(&self, prompts: &[PromptMetadata], directory: &Path) -> Result<Vec<PathBuf>>;
+    fn record_revision(&self, metadata: &PromptMetadata, content: &str) -> Result<Option<Revision>>;
+    fn list_revisions(&self, metadata: &PromptMetadata) -> Result<Vec<Revision>>;
+    fn read_revision(&self, metadata: &PromptMetadata, number: u32) -> Result<Option<String>>;
+    fn list_trash(&self) -> Result<Vec<TrashEntry>>;
+    fn restore_from_trash(&self, id: &str) -> Result<TrashEntry>;
+    fn empty_trash(&self) -> Result<usize>;
+    fn purge_trash(&self, max_age_days: u32) -> Result<usize>;
+    fn list_recipes(&self) -> Result<Vec<Recipe>>;
+    fn get_recipe(&self, name: &str) -> Result<Option<Recipe>>;
+    fn save_recipe(&self, recipe: &Recipe) -> Result<()>;
+    fn delete_recipe(&self, name: &str) -> Result<bool>;
+    /// Problems met while reading prompts since the last call, such as frontmatter that does not parse
+    fn take_warnings(&self) -> Vec<String>;
 }
 
 /// Adapter to use FileSystem as a PromptRepository
 pub struct FileSystemRepository {
     storage: FileSystem,
+    history: HistoryStore,
+    trash: TrashStore,
+    recipes: RecipeStore,
 }
 
 impl FileSystemRepository {
     pub fn new(storage: FileSystem) -> Self {
-        Self { storage }
+        let history = HistoryStore::new(storage.base_path());
+        let trash = TrashStore::new(storage.base_path());
+        let recipes = RecipeStore::new(storage.base_path());
+        Self { storage, history, trash, recipes }
     }
 }
 
@@ -37,62 +80,64 @@ impl PromptRepository for FileSystemRepository {
 
     fn find_by_name(&self, name: &str) -> Result<Option<PromptMetadata>> {
         let prompts = self.list_all()?;
+        
+        // An exact path match wins so that `backend/review` never resolves to another folder's prompt
+        if let Some(index) = prompts.iter().position(|p| p.id() == name) {
+            return Ok(prompts.into_iter().nth(index));
+        }
+        
+        let name_lower = name.to_lowercase();
         Ok(prompts.into_iter().find(|p| {
-            p.name.to_lowercase() == name.to_lowercase()
-                || p.file_path.trim_end_matches(".md") == name
+            p.name.to_lowercase() == name_lower
+                || p.display_name().to_lowercase() == name_lower
         }))
     }
 
     fn get_content(&self, file_path: &str) -> Result<String> {
         let relative_path = Path::new(PROMPTS_DIR).join(file_path);
         let content = self.storage.read_to_string(&relative_path)?;
+        Ok(prompt_body(&content))
+    }
+
+    fn search(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
+        let mut results: Vec<SearchResult> = self.list_all()?
+            .iter()
+            .filter_map(|p| self.match_prompt(query, p))
+            .collect();
         
-        // Extract content after frontmatter
-        if content.starts_with("---\n") {
-            let parts: Vec<&str> = content.splitn(3, "---\n").collect();
-            if parts.len() >= 3 {
-                return Ok(parts[2].trim().to_string());
-            }
-        }
-        
-        Ok(content)
+        results.sort_by_key(|result| (std::cmp::Reverse(result.score), result.prompt.name.to_lowercase()));
+        Ok(results)
     }
 
-    fn search(&self, query: &str, search_type: SearchType) -> Result<Vec<PromptMetadata>> {
-        let all_prompts = self.list_all()?;
-        let query_lower = query.to_lowercase();
+    fn match_prompt(&self, query: &SearchQuery, prompt: &PromptMetadata) -> Option<SearchResult> {
+        // The file is only read for `content:` terms, and then once for both the match and its snippets
+        let mut file: Option<Option<String>> = None;
+        let mut content = || file.get_or_insert_with(|| self.read_prompt(prompt).ok()).as_deref().map(prompt_body);
+        let score = query.evaluate(prompt, &mut content)?;
         
-        Ok(all_prompts.into_iter().filter(|p| {
-            match search_type {
-                SearchType::Name => p.name.to_lowercase().contains(&query_lower),
-                SearchType::Tags => p.tags.iter().any(|t| t.to_lowercase().contains(&query_lower)),
-                SearchType::Content => {
-                    if let Ok(content) = self.get_content(&p.file_path) {
-                        content.to_lowercase().contains(&query_lower)
-                    } else {
-                        false
-                    }
-                }
-                SearchType::All => {
-                    p.name.to_lowercase().contains(&query_lower)
-                        || p.tags.iter().any(|t| t.to_lowercase().contains(&query_lower))
-                        || if let Ok(content) = self.get_content(&p.file_path) {
-                            content.to_lowercase().contains(&query_lower)
-                        } else {
-                            false
-                        }
-                }
-            }
-        }).collect())
+        let content_terms = query.content_terms();
+        let matches = if content_terms.is_empty() {
+            Vec::new()
+        } else {
+            file.get_or_insert_with(|| self.read_prompt(prompt).ok())
+                .as_deref()
+                .map(|file| find_content_matches(file, &content_terms))
+                .unwrap_or_default()
+        };
+        Some(SearchResult { prompt: prompt.clone(), score, matches })
     }
 
     fn create_prompt(&self, name: &str, content: &str) -> Result<()> {
         let prompts_dir = Path::new(PROMPTS_DIR);
-        self.storage.create_dir_all(prompts_dir)?;
         
         let file_name = format!("{}.md", name);
         let file_path = prompts_dir.join(&file_name);
         
+        // Create intermediate folders for nested prompts like `backend/review`
+        if let Some(parent) = file_path.parent() {
+            self.storage.create_dir_all(parent)?;
+        }
+        
         self.storage.write(&file_path, content)?;
         Ok(())
     }
@@ -104,17 +149,48 @@ impl PromptRepository for FileSystemRepository {
     }
 
     fn get_template_content(&self, template_name: &str) -> Result<String> {
-        let template_path = Path::new("templates").join(format!("{}.md", template_name));
+        let template_path = Path::new(TEMPLATES_DIR).join(format!("{}.md", template_name));
         self.storage.read_to_string(&template_path)
     }
 
+    fn list_templates(&self) -> Result<Vec<String>> {
+        let templates_dir = self.storage.join(Path::new(TEMPLATES_DIR));
+        if !templates_dir.exists() {
+            return Ok(Vec::new());
+        }
+        
+        let mut names = Vec::new();
+        for entry in std::fs::read_dir(&templates_dir)? {
+            let path = entry?.path();
+            if path.is_file()
+                && path.extension().and_then(|s| s.to_str()) == Some("md")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                && !stem.starts_with('.') {
+                names.push(stem.to_string());
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn template_exists(&self, template_name: &str) -> bool {
+        let template_path = Path::new(TEMPLATES_DIR).join(format!("{}.md", template_name));
+        self.storage.exists(&template_path)
+    }
+
+    fn save_template(&self, template_name: &str, content: &str) -> Result<()> {
+        self.storage.create_dir_all(Path::new(TEMPLATES_DIR))?;
+        let template_path = Path::new(TEMPLATES_DIR).join(format!("{}.md", template_name));
+        self.storage.write(&template_path, content)
+    }
+
     fn get_base_path(&self) -> &std::path::Path {
         self.storage.base_path()
     }
 
     fn delete_prompt(&self, file_path: &str) -> Result<()> {
-        let full_path = Path::new(PROMPTS_DIR).join(file_path);
-        self.storage.delete(&full_path)?;
+        // Deleted prompts go to the trash so they can be restored
+        self.trash.trash(file_path)?;
         Ok(())
     }
 
@@ -125,6 +201,91 @@ impl PromptRepository for FileSystemRepository {
 
     fn write_prompt(&self, metadata: &PromptMetadata, content: &str) -> Result<()> {
         let path = Path::new(PROMPTS_DIR).join(&metadata.file_path);
+        
+        // Keep the text being overwritten so it can be restored later
+        if self.storage.exists(&path) {
+            let previous = self.storage.read_to_string(&path)?;
+            if previous != content {
+                self.history.snapshot(&metadata.file_path, &previous)?;
+            }
+        }
+        
         self.storage.write(&path, content)
     }
+
+    fn restore_prompt_content(&self, metadata: &PromptMetadata, content: &str) -> Result<()> {
+        self.storage.write(&Path::new(PROMPTS_DIR).join(&metadata.file_path), content)
+    }
+
+    fn export_prompts(&self, prompts: &[PromptMetadata], directory: &Path) -> Result<Vec<PathBuf>> {
+        let target = FileSystem::new(directory.to_path_buf());
+        let mut files = Vec::new();
+        for prompt in prompts {
+            let path = Path::new(&prompt.file_path);
+            if target.exists(path) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("'{}' already exists", target.join(path).display()),
+                ).into());
+            }
+            files.push((path, self.read_prompt(prompt)?));
+        }
+        
+        for (path, content) in &files {
+            if let Some(parent) = path.parent() {
+                target.create_dir_all(parent)?;
+            }
+            target.write(path, content)?;
+        }
+        
+        Ok(files.into_iter().map(|(path, _)| target.join(path)).collect())
+    }
+
+    fn record_revision(&self, metadata: &PromptMetadata, content: &str) -> Result<Option<Revision>> {
+        self.history.snapshot(&metadata.file_path, content)
+    }
+
+    fn list_revisions(&self, metadata: &PromptMetadata) -> Result<Vec<Revision>> {
+        self.history.list(&metadata.file_path)
+    }
+
+    fn read_revision(&self, metadata: &PromptMetadata, number: u32) -> Result<Option<String>> {
+        self.history.read(&metadata.file_path, number)
+    }
+
+    fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        self.trash.list()
+    }
+
+    fn restore_from_trash(&self, id: &str) -> Result<TrashEntry> {
+        self.trash.restore(id)
+    }
+
+    fn empty_trash(&self) -> Result<usize> {
+        self.trash.empty()
+    }
+
+    fn purge_trash(&self, max_age_days: u32) -> Result<usize> {
+        self.trash.purge_older_than(max_age_days)
+    }
+
+    fn list_recipes(&self) -> Result<Vec<Recipe>> {
+        self.recipes.list()
+    }
+
+    fn get_recipe(&self, name: &str) -> Result<Option<Recipe>> {
+        self.recipes.load(name)
+    }
+
+    fn save_recipe(&self, recipe: &Recipe) -> Result<()> {
+        self.recipes.save(recipe)
+    }
+
+    fn delete_recipe(&self, name: &str) -> Result<bool> {
+        self.recipes.delete(name)
+    }
+
+    fn take_warnings(&self) -> Vec<String> {
+        self.storage.take_warnings()
+    }
 }
\ No newline at end of file
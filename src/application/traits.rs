@@ -1,20 +1,66 @@
+use std::collections::{BTreeMap, HashMap};
 use crate::utils::error::Result;
-use crate::application::models::{PromptMetadata, PromptFilter, SearchType, PromptType};
+use crate::utils::variables::VariableSpec;
+use crate::utils::output_format::OutputFormat;
+use crate::utils::query::SearchQuery;
+use crate::application::models::{PromptMetadata, PromptFilter, PromptType, Recipe, SearchResult, Revision, TagChange, TagOperation, TrashEntry};
 
 /// Application layer for business operations
 pub trait PromptApplication {
     fn list_prompts(&self, filter: Option<PromptFilter>) -> Result<Vec<PromptMetadata>>;
     fn get_prompt(&self, identifier: &str) -> Result<(PromptMetadata, String)>;
+    fn get_expanded_prompt(&self, identifier: &str) -> Result<(PromptMetadata, String)>;
+    /// Every frontmatter key of a prompt, including ones fink does not use itself
+    fn get_frontmatter(&self, identifier: &str) -> Result<serde_json::Value>;
     fn copy_to_clipboard(&self, content: &str) -> Result<()>;
-    fn search_prompts(&self, query: &str, search_type: SearchType) -> Result<Vec<PromptMetadata>>;
+    /// Searches with the query syntax of `SearchQuery`, best matches first.
+    /// With `content`, free text also matches prompt bodies.
+    fn search_prompts(&self, query: &str, content: bool) -> Result<Vec<SearchResult>>;
+    /// Scores an already listed prompt against a parsed query, or None if it does not match
+    fn match_prompt(&self, query: &SearchQuery, metadata: &PromptMetadata) -> Option<SearchResult>;
     fn create_prompt(&self, name: &str, template: Option<&str>) -> Result<()>;
     fn create_prompt_with_content(&self, name: &str, template: Option<&str>, content: Option<String>) -> Result<()>;
     fn create_prompt_with_type(&self, name: &str, template: Option<&str>, prompt_type: PromptType) -> Result<()>;
     fn create_prompt_with_content_and_type(&self, name: &str, template: Option<&str>, content: Option<String>, prompt_type: PromptType) -> Result<()>;
     fn edit_prompt(&self, name: &str) -> Result<()>;
+    fn edit_prompt_at_line(&self, name: &str, line: usize) -> Result<()>;
     fn delete_prompt(&self, name: &str, force: bool) -> Result<()>;
     fn copy_prompt(&self, name: &str) -> Result<()>;
+    fn prompt_variables(&self, name: &str) -> Result<Vec<VariableSpec>>;
+    fn render_prompt(&self, name: &str, values: &HashMap<String, String>) -> Result<String>;
+    fn copy_prompt_with_variables(&self, name: &str, values: &HashMap<String, String>) -> Result<()>;
     fn get_base_path(&self) -> &std::path::Path;
     fn update_prompt_tags(&self, name: &str, tags: Vec<String>) -> Result<()>;
+    fn update_prompt_type(&self, name: &str, prompt_type: PromptType) -> Result<()>;
+    /// Copies prompt files, frontmatter included, into `directory` keeping their folders.
+    /// Fails before writing anything if one of them is already there.
+    fn export_prompts(&self, identifiers: &[String], directory: &std::path::Path) -> Result<Vec<std::path::PathBuf>>;
+    /// Number of prompts under every tag, parents of hierarchical tags included
+    fn tag_counts(&self) -> Result<BTreeMap<String, usize>>;
+    /// The prompts a tag operation would change, without changing them
+    fn preview_tag_operation(&self, operation: &TagOperation) -> Result<Vec<TagChange>>;
+    /// Rewrites the tags of every affected prompt. If a write fails, the prompts already
+    /// rewritten are put back and the error names any that could not be.
+    fn apply_tag_operation(&self, operation: &TagOperation) -> Result<Vec<TagChange>>;
     fn get_clipboard_content(&self) -> Result<String>;
+    fn list_revisions(&self, name: &str) -> Result<Vec<Revision>>;
+    fn diff_prompt(&self, name: &str, revision: Option<u32>) -> Result<String>;
+    fn restore_prompt(&self, name: &str, revision: u32) -> Result<()>;
+    fn list_trash(&self) -> Result<Vec<TrashEntry>>;
+    fn restore_from_trash(&self, identifier: &str) -> Result<TrashEntry>;
+    fn empty_trash(&self) -> Result<usize>;
+    fn list_templates(&self) -> Result<Vec<String>>;
+    fn create_template(&self, name: &str) -> Result<std::path::PathBuf>;
+    fn edit_template(&self, name: &str) -> Result<()>;
+    fn combine_prompts(&self, identifiers: &[String], comment: &str, format: OutputFormat) -> Result<String>;
+    /// Like `combine_prompts`, but each prompt must be of the type it is given with, and they
+    /// are combined in the configured type order
+    fn combine_typed_prompts(&self, prompts: &[(PromptType, String)], comment: &str, format: OutputFormat) -> Result<String>;
+    fn list_recipes(&self) -> Result<Vec<Recipe>>;
+    fn save_recipe(&self, name: &str, prompts: Vec<String>, comment: &str) -> Result<Recipe>;
+    fn delete_recipe(&self, name: &str) -> Result<()>;
+    fn build_recipe(&self, name: &str, format: OutputFormat) -> Result<String>;
+    /// Problems met while reading prompts since the last call, such as frontmatter that does not
+    /// parse. The prompts are still used, so callers decide how to show them.
+    fn take_warnings(&self) -> Vec<String>;
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod editor;
+pub mod watcher;
 
 use anyhow::Result;
 use clipboard::{ClipboardContext, ClipboardProvider};
@@ -0,0 +1,56 @@
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use crate::utils::constants::PROMPTS_DIR;
+
+/// Watches the storage directory and reports changes to prompt files
+pub struct StorageWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Result<Event>>,
+    prompts_dir: PathBuf,
+}
+
+impl StorageWatcher {
+    pub fn new(storage_path: &Path) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        
+        // Watch the storage root so a prompts directory created later is still picked up
+        watcher.watch(storage_path, RecursiveMode::Recursive)?;
+        
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+            prompts_dir: storage_path.join(PROMPTS_DIR),
+        })
+    }
+    
+    /// Drains pending events and returns whether any of them touched the prompt library
+    pub fn has_changes(&self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.receiver.try_recv() {
+            if let Ok(event) = event
+                && !matches!(event.kind, EventKind::Access(_))
+                && event.paths.iter().any(|path| self.is_prompt_path(path)) {
+                changed = true;
+            }
+        }
+        changed
+    }
+    
+    /// Prompt files and folders below the prompts directory, ignoring hidden entries
+    fn is_prompt_path(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.prompts_dir) else {
+            return false;
+        };
+        
+        let is_hidden = relative.components().any(|c| match c {
+            Component::Normal(name) => name.to_str().is_some_and(|s| s.starts_with('.')),
+            _ => false,
+        });
+        
+        // Folder events matter too, since moving a folder renames every prompt inside it
+        !is_hidden && (path.extension().is_none() || path.extension().and_then(|s| s.to_str()) == Some("md"))
+    }
+}
@@ -1,11 +1,32 @@
 use crate::application::app::DefaultPromptApplication;
+use crate::application::models::{PromptFilter, PromptType, TagMatch, TagOperation};
 use crate::application::traits::PromptApplication;
 use anyhow::Result;
-use crate::utils::error::FinkError;
+use crate::utils::error::{FinkError, ValidationError};
 use crate::utils::config::Config;
+use crate::utils::data_format::{DataFormat, MachineFormat};
+use crate::utils::output_format::OutputFormat;
+use crate::utils::variables::parse_assignment;
 use clap::Subcommand;
 
+mod records;
+use records::{PromptDocument, PromptRecord, SearchRecord};
+
 fn handle_error(error: FinkError) -> ! {
+    handle_error_as(error, DataFormat::Text)
+}
+
+/// Exits with the error, written to stderr as
+/// `{"error": {"code": ..., "message": ..., "hint": ...}}` when JSON output was requested
+fn handle_error_as(error: FinkError, format: DataFormat) -> ! {
+    if format.is_json() {
+        let hint = error.is_recoverable().then(|| error.user_message());
+        eprintln!("{}", serde_json::json!({
+            "error": { "code": error.code(), "message": error.to_string(), "hint": hint }
+        }));
+        std::process::exit(1);
+    }
+    
     eprintln!("Error: {}", error);
     
     // Show user-friendly message if available
@@ -16,18 +37,51 @@ fn handle_error(error: FinkError) -> ! {
     std::process::exit(1);
 }
 
+/// Prints rendered output, or exits with the error in the requested format
+fn print_or_exit(output: serde_json::Result<String>, format: DataFormat) {
+    match output {
+        Ok(output) => print!("{}", output),
+        Err(e) => handle_error_as(e.into(), format),
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
-    /// List all prompts
-    List,
+    /// List prompts, e.g. `fink list --tag rust --tag review --all --exclude-tag draft`
+    List {
+        /// Only prompts with this tag or one of its child tags (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Require every --tag instead of any of them
+        #[arg(long, requires = "tags")]
+        all: bool,
+        /// Leave out prompts with this tag or one of its child tags (repeatable)
+        #[arg(long = "exclude-tag")]
+        exclude_tags: Vec<String>,
+        /// Only prompts of this type (repeatable)
+        #[arg(long = "type")]
+        types: Vec<String>,
+        /// Output format: text, json, ndjson or tsv
+        #[arg(long, default_value_t)]
+        format: DataFormat,
+    },
     /// Get a specific prompt
     Get {
         /// Name of the prompt
         name: String,
+        /// Value for a template variable, e.g. --var language=rust
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_assignment)]
+        values: Vec<(String, String)>,
+        /// Print the prompt's variables as JSON instead of its content
+        #[arg(long)]
+        vars: bool,
+        /// Output format: text, or json, ndjson and tsv with the metadata, frontmatter and body
+        #[arg(long, default_value_t)]
+        format: DataFormat,
     },
     /// Create a new prompt
     Create {
-        /// Name of the prompt
+        /// Name of the prompt (use folder/name to create it inside a folder)
         name: String,
         /// Template to use for the prompt
         #[arg(short, long)]
@@ -50,23 +104,211 @@ pub enum Commands {
     Copy {
         /// Name of the prompt to copy
         name: String,
+        /// Value for a template variable, e.g. --var language=rust
+        #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_assignment)]
+        values: Vec<(String, String)>,
     },
-    /// Search for prompts
+    /// Search for prompts, e.g. `fink search tag:rust -tag:draft review`
     Search {
-        /// Search query
-        query: String,
+        /// Search query: words, name:/tag:/type:/content:/starred: filters,
+        /// -negation, "quoted phrases" and OR. Quote the query, or put it after `--`,
+        /// when it contains negations
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+        /// Also search prompt bodies and list where each match is
+        #[arg(long)]
+        content: bool,
+        /// Output format: text, json, ndjson or tsv
+        #[arg(long, default_value_t)]
+        format: DataFormat,
+    },
+    /// Show the saved revisions of a prompt
+    History {
+        /// Name of the prompt
+        name: String,
+    },
+    /// Show changes between a saved revision and the current prompt
+    Diff {
+        /// Name of the prompt
+        name: String,
+        /// Revision number (defaults to the latest revision)
+        revision: Option<u32>,
+    },
+    /// Restore a prompt to a saved revision
+    Restore {
+        /// Name of the prompt
+        name: String,
+        /// Revision number to restore
+        revision: u32,
+    },
+    /// Manage deleted prompts
+    Trash {
+        #[command(subcommand)]
+        action: TrashAction,
+    },
+    /// Combine prompts by type or from a saved recipe (lists recipes when neither is given)
+    Build {
+        /// Name of the recipe
+        #[arg(conflicts_with_all = ["prompt", "instruction", "context", "input", "output", "etc", "comment"])]
+        recipe: Option<String>,
+        /// Prompt to include as TYPE=NAME, for any type declared in the config (repeatable)
+        #[arg(long, value_parser = parse_typed_prompt)]
+        prompt: Vec<(PromptType, String)>,
+        /// Instruction prompt to include (repeatable)
+        #[arg(long)]
+        instruction: Vec<String>,
+        /// Context prompt to include (repeatable)
+        #[arg(long)]
+        context: Vec<String>,
+        /// Input indicator prompt to include (repeatable)
+        #[arg(long)]
+        input: Vec<String>,
+        /// Output indicator prompt to include (repeatable)
+        #[arg(long)]
+        output: Vec<String>,
+        /// Additional prompt to include (repeatable)
+        #[arg(long)]
+        etc: Vec<String>,
+        /// Note appended after the combined prompts
+        #[arg(long)]
+        comment: Option<String>,
+        /// Output format: plain, markdown, xml or json (defaults to the configured build format)
+        #[arg(short, long)]
+        format: Option<OutputFormat>,
+        /// Copy the result to the clipboard instead of printing it
+        #[arg(short, long)]
+        copy: bool,
+    },
+    /// Manage prompt templates in the templates directory
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    /// Rename, merge or delete tags across every prompt
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
     },
 }
 
+impl Commands {
+    /// The data format the command prints in, text for commands without `--format`
+    fn data_format(&self) -> DataFormat {
+        match self {
+            Commands::List { format, .. } | Commands::Get { format, .. } | Commands::Search { format, .. } => *format,
+            _ => DataFormat::Text,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum TrashAction {
+    /// List deleted prompts
+    List,
+    /// Restore a deleted prompt to its original location
+    Restore {
+        /// Trash id or the prompt's original name
+        id: String,
+    },
+    /// Permanently remove all deleted prompts
+    Empty {
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TemplateAction {
+    /// List available templates
+    List,
+    /// Create a new template from a starter file
+    New {
+        /// Name of the template
+        name: String,
+    },
+    /// Edit a template in your editor
+    Edit {
+        /// Name of the template
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagAction {
+    /// List the tags in use
+    List {
+        /// Show how many prompts have each tag
+        #[arg(long)]
+        counts: bool,
+    },
+    /// Rename a tag, along with its child tags, on every prompt
+    Rename {
+        /// Current tag
+        from: String,
+        /// New tag
+        to: String,
+        /// Only show which prompts would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Replace one or more tags with an existing tag on every prompt
+    Merge {
+        /// Tags to merge away
+        #[arg(required = true, num_args = 1..)]
+        sources: Vec<String>,
+        /// Tag that replaces them
+        #[arg(long)]
+        into: String,
+        /// Only show which prompts would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove a tag, along with its child tags, from every prompt
+    Delete {
+        /// Tag to remove
+        tag: String,
+        /// Only show which prompts would change
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
 
 pub fn execute_command(command: Commands, config: &Config) -> Result<()> {
-    let application = DefaultPromptApplication::with_config(config)?;
+    let format = command.data_format();
+    let application = match DefaultPromptApplication::with_config(config) {
+        Ok(application) => application,
+        Err(e) => handle_error_as(e, format),
+    };
     
+    let result = run_command(command, config, &application);
+    // Machine formats leave stderr to the error object
+    if format.machine().is_none() {
+        for warning in application.take_warnings() {
+            eprintln!("Warning: {}", warning);
+        }
+    }
+    result
+}
+
+fn run_command(command: Commands, config: &Config, application: &DefaultPromptApplication) -> Result<()> {
     match command {
-        Commands::List => {
-            let prompts = application.list_prompts(None)?;
+        Commands::List { tags, all, exclude_tags, types, format } => {
+            let filter = PromptFilter {
+                tags: (!tags.is_empty()).then_some(tags),
+                tag_match: if all { TagMatch::All } else { TagMatch::Any },
+                exclude_tags,
+                types: types.iter().map(|t| PromptType::new(t)).collect(),
+            };
+            let prompts = match application.list_prompts(Some(filter)) {
+                Ok(prompts) => prompts,
+                Err(e) => handle_error_as(e, format),
+            };
 
-            if prompts.is_empty() {
+            if let Some(machine) = format.machine() {
+                let records: Vec<PromptRecord> = prompts.iter().map(PromptRecord::new).collect();
+                print_or_exit(machine.render(&records, PromptRecord::tsv_row), format);
+            } else if prompts.is_empty() {
                 println!("No prompts found");
             } else {
                 for prompt in prompts {
@@ -75,20 +317,46 @@ pub fn execute_command(command: Commands, config: &Config) -> Result<()> {
                     } else {
                         format!(" [{}]", prompt.tags.join(", "))
                     };
-                    println!("{}{}", prompt.name, tags_str);
+                    println!("{}{}", prompt.display_name(), tags_str);
                 }
             }
             Ok(())
         }
-        Commands::Get { name } => {
-            match application.get_prompt(&name) {
-                Ok((_, content)) => {
-                    println!("{}", content);
+        Commands::Get { name, vars: true, format, .. } => {
+            match application.prompt_variables(&name) {
+                Ok(specs) => {
+                    print_or_exit(serde_json::to_string_pretty(&specs).map(|json| json + "\n"), format);
                     Ok(())
                 }
-                Err(e) => handle_error(e),
+                Err(e) => handle_error_as(e, format),
             }
         }
+        Commands::Get { name, values, format, .. } => {
+            let Some(machine) = format.machine() else {
+                return match application.render_prompt(&name, &values.into_iter().collect()) {
+                    Ok(content) => {
+                        println!("{}", content);
+                        Ok(())
+                    }
+                    Err(e) => handle_error(e),
+                };
+            };
+            let loaded = application.render_prompt(&name, &values.into_iter().collect())
+                .and_then(|body| Ok((application.get_prompt(&name)?.0, application.get_frontmatter(&name)?, body)));
+            let (metadata, frontmatter, body) = match loaded {
+                Ok(loaded) => loaded,
+                Err(e) => handle_error_as(e, format),
+            };
+            
+            let document = PromptDocument { prompt: PromptRecord::new(&metadata), frontmatter, body: &body };
+            let output = if machine == MachineFormat::Json {
+                serde_json::to_string_pretty(&document).map(|json| json + "\n")
+            } else {
+                machine.render(std::slice::from_ref(&document), PromptDocument::tsv_row)
+            };
+            print_or_exit(output, format);
+            Ok(())
+        }
         Commands::Create { name, template } => {
             match application.create_prompt(&name, template.as_deref()) {
                 Ok(()) => Ok(()),
@@ -107,8 +375,8 @@ pub fn execute_command(command: Commands, config: &Config) -> Result<()> {
                 Err(e) => handle_error(e),
             }
         }
-        Commands::Copy { name } => {
-            match application.copy_prompt(&name) {
+        Commands::Copy { name, values } => {
+            match application.copy_prompt_with_variables(&name, &values.into_iter().collect()) {
                 Ok(()) => {
                     println!("Copied to clipboard");
                     Ok(())
@@ -116,24 +384,291 @@ pub fn execute_command(command: Commands, config: &Config) -> Result<()> {
                 Err(e) => handle_error(e),
             }
         }
-        Commands::Search { query } => {
-            use crate::application::models::SearchType;
-            let results = application.search_prompts(&query, SearchType::All)?;
+        Commands::Search { query, content, format } => {
+            let query = query.join(" ");
+            let results = match application.search_prompts(&query, content) {
+                Ok(results) => results,
+                Err(e) => handle_error_as(e, format),
+            };
             
-            if results.is_empty() {
+            if let Some(machine) = format.machine() {
+                let records: Vec<SearchRecord> = results.iter().map(SearchRecord::new).collect();
+                print_or_exit(machine.render(&records, SearchRecord::tsv_row), format);
+            } else if results.is_empty() {
                 println!("No prompts found matching '{}'", query);
             } else {
-                for prompt in results {
+                for result in results {
+                    let prompt = &result.prompt;
                     let tags_str = if prompt.tags.is_empty() {
                         String::new()
                     } else {
                         format!(" [{}]", prompt.tags.join(", "))
                     };
-                    println!("{}{}", prompt.name, tags_str);
+                    println!("{}{}", prompt.display_name(), tags_str);
+                    
+                    // Content matches as line:column, like grep -n
+                    for found in &result.matches {
+                        println!("    {}:{}: {}", found.line, found.column, found.snippet);
+                    }
                 }
             }
             Ok(())
         }
+        Commands::History { name } => {
+            match application.list_revisions(&name) {
+                Ok(revisions) if revisions.is_empty() => {
+                    println!("No history for '{}'", name);
+                    Ok(())
+                }
+                Ok(revisions) => {
+                    // Newest first, like `git log`
+                    for revision in revisions.iter().rev() {
+                        println!(
+                            "{:>4}  {}  {} bytes",
+                            revision.number,
+                            revision.created_at.format("%Y-%m-%d %H:%M:%S"),
+                            revision.size
+                        );
+                    }
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        Commands::Diff { name, revision } => {
+            match application.diff_prompt(&name, revision) {
+                Ok(diff) if diff.is_empty() => {
+                    println!("No changes");
+                    Ok(())
+                }
+                Ok(diff) => {
+                    print!("{}", diff);
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        Commands::Restore { name, revision } => {
+            match application.restore_prompt(&name, revision) {
+                Ok(()) => {
+                    println!("Restored '{}' to revision {}", name, revision);
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        Commands::Trash { action } => execute_trash_action(action, application),
+        Commands::Build { recipe: None, prompt, instruction, context, input, output, etc, comment, format, copy } => {
+            let shorthands = [
+                (PromptType::INSTRUCTION, instruction),
+                (PromptType::CONTEXT, context),
+                (PromptType::INPUT_INDICATOR, input),
+                (PromptType::OUTPUT_INDICATOR, output),
+                (PromptType::ETC, etc),
+            ];
+            let mut prompts = prompt;
+            for (prompt_type, names) in shorthands {
+                prompts.extend(names.into_iter().map(|name| (prompt_type.clone(), name)));
+            }
+            
+            if prompts.is_empty() {
+                if comment.is_some() {
+                    handle_error(FinkError::Validation(ValidationError::MissingRequired(
+                        "at least one of --prompt, --instruction, --context, --input, --output or --etc".to_string(),
+                    )));
+                }
+                return list_recipes(application);
+            }
+            
+            let format = format.unwrap_or(config.build_format());
+            match application.combine_typed_prompts(&prompts, comment.as_deref().unwrap_or_default(), format) {
+                Ok(combined) => output_build(application, &combined, copy, "combined prompt"),
+                Err(e) => handle_error(e),
+            }
+        }
+        Commands::Build { recipe: Some(name), format, copy, .. } => {
+            match application.build_recipe(&name, format.unwrap_or(config.build_format())) {
+                Ok(combined) => output_build(application, &combined, copy, &format!("recipe '{}'", name)),
+                Err(e) => handle_error(e),
+            }
+        }
+        Commands::Template { action } => execute_template_action(action, application),
+        Commands::Tag { action } => execute_tag_action(action, application),
+    }
+}
+
+/// Parses a `TYPE=NAME` pair as given to `build --prompt`
+fn parse_typed_prompt(pair: &str) -> std::result::Result<(PromptType, String), String> {
+    match pair.split_once('=') {
+        Some((prompt_type, name)) if !prompt_type.trim().is_empty() && !name.trim().is_empty() => {
+            Ok((PromptType::new(prompt_type), name.trim().to_string()))
+        }
+        _ => Err(format!("expected TYPE=NAME, got '{}'", pair)),
+    }
+}
+
+fn list_recipes(application: &DefaultPromptApplication) -> Result<()> {
+    match application.list_recipes() {
+        Ok(recipes) if recipes.is_empty() => {
+            println!("No saved recipes. Save one from build mode in the TUI.");
+            Ok(())
+        }
+        Ok(recipes) => {
+            for recipe in recipes {
+                println!("{}  {}", recipe.name, recipe.prompts.join(" + "));
+            }
+            Ok(())
+        }
+        Err(e) => handle_error(e),
+    }
+}
+
+fn output_build(application: &DefaultPromptApplication, combined: &str, copy: bool, what: &str) -> Result<()> {
+    if copy {
+        match application.copy_to_clipboard(combined) {
+            Ok(()) => println!("Copied {} to clipboard", what),
+            Err(e) => handle_error(e),
+        }
+    } else {
+        println!("{}", combined);
+    }
+    Ok(())
+}
+
+fn execute_trash_action(action: TrashAction, application: &DefaultPromptApplication) -> Result<()> {
+    match action {
+        TrashAction::List => {
+            match application.list_trash() {
+                Ok(entries) if entries.is_empty() => {
+                    println!("Trash is empty");
+                    Ok(())
+                }
+                Ok(entries) => {
+                    for entry in entries {
+                        println!(
+                            "{}  {}  {}",
+                            entry.id,
+                            entry.deleted_at.format("%Y-%m-%d %H:%M:%S"),
+                            entry.prompt_id()
+                        );
+                    }
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        TrashAction::Restore { id } => {
+            match application.restore_from_trash(&id) {
+                Ok(entry) => {
+                    println!("Restored '{}'", entry.prompt_id());
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        TrashAction::Empty { force } => {
+            if !force {
+                handle_error(FinkError::Validation(ValidationError::InvalidInput(
+                    "confirmation",
+                    "Emptying the trash cannot be undone. Use --force to confirm.".to_string(),
+                )));
+            }
+            match application.empty_trash() {
+                Ok(count) => {
+                    println!("Permanently removed {} prompt(s)", count);
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+    }
+}
+
+fn execute_template_action(action: TemplateAction, application: &DefaultPromptApplication) -> Result<()> {
+    match action {
+        TemplateAction::List => {
+            match application.list_templates() {
+                Ok(templates) => {
+                    // Built-in templates are available unless a user template of the same name replaces them
+                    for builtin in ["basic", "clipboard"] {
+                        if !templates.iter().any(|t| t == builtin) {
+                            println!("{} (built-in)", builtin);
+                        }
+                    }
+                    for template in templates {
+                        println!("{}", template);
+                    }
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        TemplateAction::New { name } => {
+            match application.create_template(&name) {
+                Ok(path) => {
+                    println!("Created template '{}' at {}", name, path.display());
+                    println!("Use {{{{name}}}}, {{{{type}}}} and {{{{date}}}} as placeholders. Edit it with 'fink template edit {}'", name);
+                    Ok(())
+                }
+                Err(e) => handle_error(e),
+            }
+        }
+        TemplateAction::Edit { name } => {
+            match application.edit_template(&name) {
+                Ok(()) => Ok(()),
+                Err(e) => handle_error(e),
+            }
+        }
     }
 }
 
+
+fn execute_tag_action(action: TagAction, application: &DefaultPromptApplication) -> Result<()> {
+    let (operation, dry_run) = match action {
+        TagAction::List { counts } => {
+            match application.tag_counts() {
+                Ok(tags) if tags.is_empty() => println!("No tags found"),
+                Ok(tags) => {
+                    for (tag, count) in tags {
+                        if counts {
+                            println!("{}  {}", tag, count);
+                        } else {
+                            println!("{}", tag);
+                        }
+                    }
+                }
+                Err(e) => handle_error(e),
+            }
+            return Ok(());
+        }
+        TagAction::Rename { from, to, dry_run } => (TagOperation::Rename { from, to }, dry_run),
+        TagAction::Merge { sources, into, dry_run } => (TagOperation::Merge { sources, into }, dry_run),
+        TagAction::Delete { tag, dry_run } => (TagOperation::Delete(tag), dry_run),
+    };
+    
+    let result = if dry_run {
+        application.preview_tag_operation(&operation)
+    } else {
+        application.apply_tag_operation(&operation)
+    };
+    let changes = match result {
+        Ok(changes) => changes,
+        Err(e) => handle_error(e),
+    };
+    
+    // One line per changed file, as `path: [old] -> [new]`
+    for change in &changes {
+        println!(
+            "{}: [{}] -> [{}]",
+            change.prompt.file_path,
+            change.before.join(", "),
+            change.after.join(", ")
+        );
+    }
+    if dry_run {
+        println!("{} prompt(s) would change", changes.len());
+    } else {
+        println!("Updated {} prompt(s)", changes.len());
+    }
+    Ok(())
+}
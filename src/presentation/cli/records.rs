@@ -0,0 +1,91 @@
+use crate::application::models::{ContentMatch, PromptMetadata, SearchResult};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+/// A prompt as printed by `--format json|ndjson|tsv`. Kept separate from `PromptMetadata`
+/// so the fields scripts rely on do not change with the index format.
+#[derive(Serialize)]
+pub struct PromptRecord<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub file_path: &'a str,
+    pub tags: &'a [String],
+    #[serde(rename = "type")]
+    pub prompt_type: &'a str,
+    pub description: &'a str,
+    pub created_at: Option<DateTime<Local>>,
+    pub modified_at: Option<DateTime<Local>>,
+}
+
+impl<'a> PromptRecord<'a> {
+    pub fn new(prompt: &'a PromptMetadata) -> Self {
+        Self {
+            id: prompt.id(),
+            name: &prompt.name,
+            file_path: &prompt.file_path,
+            tags: &prompt.tags,
+            prompt_type: prompt.prompt_type.as_str(),
+            description: &prompt.description,
+            created_at: prompt.created_at,
+            modified_at: prompt.modified_at,
+        }
+    }
+
+    /// id, name, file path, comma separated tags, type, description, created and modified time
+    pub fn tsv_row(&self) -> Vec<String> {
+        let timestamp = |time: Option<DateTime<Local>>| time.map(|time| time.to_rfc3339()).unwrap_or_default();
+        vec![
+            self.id.to_string(),
+            self.name.to_string(),
+            self.file_path.to_string(),
+            self.tags.join(","),
+            self.prompt_type.to_string(),
+            self.description.to_string(),
+            timestamp(self.created_at),
+            timestamp(self.modified_at),
+        ]
+    }
+}
+
+/// A search result with the places its content matched, empty unless `--content` was given
+#[derive(Serialize)]
+pub struct SearchRecord<'a> {
+    #[serde(flatten)]
+    pub prompt: PromptRecord<'a>,
+    pub matches: &'a [ContentMatch],
+}
+
+impl<'a> SearchRecord<'a> {
+    pub fn new(result: &'a SearchResult) -> Self {
+        Self {
+            prompt: PromptRecord::new(&result.prompt),
+            matches: &result.matches,
+        }
+    }
+
+    /// The prompt's columns followed by the matches as `line:column`, comma separated
+    pub fn tsv_row(&self) -> Vec<String> {
+        let mut row = self.prompt.tsv_row();
+        let matches: Vec<String> = self.matches.iter().map(|found| format!("{}:{}", found.line, found.column)).collect();
+        row.push(matches.join(","));
+        row
+    }
+}
+
+/// A prompt printed by `get`: its metadata, every frontmatter key and the rendered body
+#[derive(Serialize)]
+pub struct PromptDocument<'a> {
+    #[serde(flatten)]
+    pub prompt: PromptRecord<'a>,
+    pub frontmatter: serde_json::Value,
+    pub body: &'a str,
+}
+
+impl PromptDocument<'_> {
+    /// The prompt's columns followed by the body; the frontmatter is only in the JSON formats
+    pub fn tsv_row(&self) -> Vec<String> {
+        let mut row = self.prompt.tsv_row();
+        row.push(self.body.to_string());
+        row
+    }
+}
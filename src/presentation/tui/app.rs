@@ -1,16 +1,21 @@
 use crate::application::app::DefaultPromptApplication;
+use crate::application::models::{ContentMatch, PromptFilter, PromptMetadata, TagChange, TagMatch, TagOperation};
 use crate::application::traits::PromptApplication;
-use crate::presentation::tui::components::{PromptList, confirmation_dialog::{ConfirmationDialog as Dialog, ConfirmationAction}, TagManagementDialog, TagFilterDialog, CreateDialog, BuildPanel, InteractiveBuildPanel};
+use crate::presentation::tui::components::{PromptList, confirmation_dialog::{ConfirmationDialog as Dialog, ConfirmationAction, BulkAction}, TagManagementDialog, TagFilterDialog, CreateDialog, BuildPanel, InteractiveBuildPanel, HistoryDialog, TrashDialog, VariableDialog};
 use crate::presentation::tui::screens::ConfigScreen;
 use crate::utils::config::Config;
+use crate::utils::query::{QueryError, SearchQuery};
+use crate::utils::output_format::OutputFormat;
 use crate::utils::state::AppState;
-use crate::utils::constants::PROMPTS_DIR;
+use crate::utils::constants::{PROMPTS_DIR, STARRED_TAG};
+use crate::utils::tags;
 use anyhow::Result;
 use ratatui::widgets::ListState;
 use std::path::PathBuf;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-const STARRED_TAG: &str = "starred";
+/// Content matches listed under a search result before the rest are summarized
+pub const MAX_SNIPPETS: usize = 3;
 
 #[derive(Debug, PartialEq)]
 pub enum AppMode {
@@ -23,6 +28,8 @@ pub enum AppMode {
 #[derive(Debug, PartialEq)]
 pub enum PendingAction {
     Edit,
+    /// Open the selected prompt with the cursor on a line, from a content search snippet
+    EditAtLine(usize),
 }
 
 pub struct TUIApp {
@@ -34,8 +41,24 @@ pub struct TUIApp {
     confirmation_dialog: Option<Dialog>,
     search_active: bool,
     search_query: String,
+    // Last query that parsed, used to filter while the one being typed does not
+    search_filter: SearchQuery,
+    // Score of each prompt against `search_filter`, None if it does not match. Evaluated once
+    // per query and reload, as `content:` terms read every prompt body.
+    search_scores: HashMap<String, Option<i64>>,
+    // Where the `content:` terms matched in each matching prompt file, found with the scores
+    content_matches: HashMap<String, Vec<ContentMatch>>,
+    // Content match of the selected result picked with Tab while searching
+    selected_snippet: Option<usize>,
+    // Warnings from reading prompts already shown in the status line, so each shows once
+    shown_warnings: HashSet<String>,
     tag_filter_active: bool,
     active_tag_filters: HashSet<String>,
+    excluded_tag_filters: HashSet<String>,
+    tag_match: TagMatch,
+    // Ids of the prompts marked for a bulk action, and where the last range started
+    marked: HashSet<String>,
+    mark_anchor: Option<String>,
     tag_management_active: bool,
     pub tag_dialog: Option<TagManagementDialog>,
     tag_filter_dialog_active: bool,
@@ -43,6 +66,9 @@ pub struct TUIApp {
     create_dialog_active: bool,
     pub create_dialog: Option<CreateDialog>,
     build_panel: Option<BuildPanel>,
+    history_dialog: Option<HistoryDialog>,
+    trash_dialog: Option<TrashDialog>,
+    variable_dialog: Option<VariableDialog>,
     interactive_build_panel: Option<InteractiveBuildPanel>,
     error_message: Option<String>,
     success_message: Option<String>,
@@ -104,8 +130,17 @@ impl TUIApp {
             confirmation_dialog: None,
             search_active: false,
             search_query: String::new(),
+            search_filter: SearchQuery::And(Vec::new()),
+            search_scores: HashMap::new(),
+            content_matches: HashMap::new(),
+            selected_snippet: None,
+            shown_warnings: HashSet::new(),
             tag_filter_active: false,
             active_tag_filters: HashSet::new(),
+            excluded_tag_filters: HashSet::new(),
+            tag_match: TagMatch::Any,
+            marked: HashSet::new(),
+            mark_anchor: None,
             tag_management_active: false,
             tag_dialog: None,
             tag_filter_dialog_active: false,
@@ -113,6 +148,9 @@ impl TUIApp {
             create_dialog_active: false,
             create_dialog: None,
             build_panel: None,
+            history_dialog: None,
+            trash_dialog: None,
+            variable_dialog: None,
             interactive_build_panel: None,
             error_message: None,
             success_message: None,
@@ -139,19 +177,20 @@ impl TUIApp {
     pub fn save_state(&mut self) {
         // Update state with current selection
         if let Some(selected) = self.prompt_list.get_selected() {
-            self.app_state.set_last_selected_prompt(Some(selected.name.clone()));
+            self.app_state.set_last_selected_prompt(Some(selected.id().to_string()));
         } else {
             self.app_state.set_last_selected_prompt(None);
         }
         
         // Save state to file
         if let Err(e) = self.app_state.save() {
-            // Log error but don't prevent quitting
-            eprintln!("Failed to save app state: {}", e);
+            // Report the error but don't prevent quitting
+            self.set_error(format!("Failed to save app state: {}", e));
         }
     }
 
     pub fn next(&mut self) {
+        self.selected_snippet = None;
         // Always navigate through the sorted/filtered list since UI always shows it sorted
         let filtered_prompts = self.get_filtered_prompts();
         if filtered_prompts.is_empty() {
@@ -160,23 +199,23 @@ impl TUIApp {
         
         // Find current prompt in filtered list
         if let Some(current) = self.prompt_list.get_selected() {
-            if let Some(current_index) = filtered_prompts.iter().position(|p| p.name == current.name) {
+            if let Some(current_index) = filtered_prompts.iter().position(|p| p.file_path == current.file_path) {
                 // Get next index in filtered list
                 let next_index = (current_index + 1) % filtered_prompts.len();
                 let next_prompt = &filtered_prompts[next_index];
                 
                 // Find and select in main list
-                self.prompt_list.find_and_select(&next_prompt.name);
+                self.prompt_list.find_and_select(next_prompt.id());
             } else {
                 // Current selection not in filtered list, select first filtered item
                 if let Some(first) = filtered_prompts.first() {
-                    self.prompt_list.find_and_select(&first.name);
+                    self.prompt_list.find_and_select(first.id());
                 }
             }
         } else {
             // No selection, select first filtered item
             if let Some(first) = filtered_prompts.first() {
-                self.prompt_list.find_and_select(&first.name);
+                self.prompt_list.find_and_select(first.id());
             }
         }
         
@@ -185,6 +224,7 @@ impl TUIApp {
     }
 
     pub fn previous(&mut self) {
+        self.selected_snippet = None;
         // Always navigate through the sorted/filtered list since UI always shows it sorted
         let filtered_prompts = self.get_filtered_prompts();
         if filtered_prompts.is_empty() {
@@ -193,7 +233,7 @@ impl TUIApp {
         
         // Find current prompt in filtered list
         if let Some(current) = self.prompt_list.get_selected() {
-            if let Some(current_index) = filtered_prompts.iter().position(|p| p.name == current.name) {
+            if let Some(current_index) = filtered_prompts.iter().position(|p| p.file_path == current.file_path) {
                 // Get previous index in filtered list
                 let prev_index = if current_index == 0 {
                     filtered_prompts.len() - 1
@@ -203,17 +243,17 @@ impl TUIApp {
                 let prev_prompt = &filtered_prompts[prev_index];
                 
                 // Find and select in main list
-                self.prompt_list.find_and_select(&prev_prompt.name);
+                self.prompt_list.find_and_select(prev_prompt.id());
             } else {
                 // Current selection not in filtered list, select last filtered item
                 if let Some(last) = filtered_prompts.last() {
-                    self.prompt_list.find_and_select(&last.name);
+                    self.prompt_list.find_and_select(last.id());
                 }
             }
         } else {
             // No selection, select last filtered item
             if let Some(last) = filtered_prompts.last() {
-                self.prompt_list.find_and_select(&last.name);
+                self.prompt_list.find_and_select(last.id());
             }
         }
         
@@ -225,10 +265,13 @@ impl TUIApp {
         self.prompt_list.selected()
     }
 
+    /// Content of the selected prompt with includes expanded. Falls back to the raw body when an
+    /// include cannot be resolved, so the preview still shows something useful.
     pub fn get_selected_content(&self) -> Option<String> {
         self.prompt_list.get_selected().and_then(|prompt| {
             self.application
-                .get_prompt(&prompt.name)
+                .get_expanded_prompt(prompt.id())
+                .or_else(|_| self.application.get_prompt(prompt.id()))
                 .map(|(_, content)| content)
                 .ok()
         })
@@ -248,7 +291,7 @@ impl TUIApp {
         // Always use filtered prompts since they are always sorted (starred first)
         let filtered_prompts = self.get_filtered_prompts();
         if let Some(selected) = self.prompt_list.get_selected() {
-            if let Some(index) = filtered_prompts.iter().position(|p| p.name == selected.name) {
+            if let Some(index) = filtered_prompts.iter().position(|p| p.file_path == selected.file_path) {
                 state.select(Some(index));
             } else {
                 // Selected item not in filtered list
@@ -268,7 +311,24 @@ impl TUIApp {
     pub fn enter_build_mode(&mut self) {
         self.mode = AppMode::Build;
         let build_prompts = self.get_build_prompts();
-        self.interactive_build_panel = Some(InteractiveBuildPanel::new(build_prompts));
+        let contents = self.load_build_contents(&build_prompts);
+        let mut panel = InteractiveBuildPanel::with_types(build_prompts, self.config.prompt_types().clone());
+        panel.set_contents(contents);
+        panel.set_recipes(self.application.list_recipes().unwrap_or_default());
+        panel.set_format(self.config.build_format());
+        self.interactive_build_panel = Some(panel);
+    }
+
+    // Prompts that fail to expand fall back to a metadata-only preview
+    fn load_build_contents(&self, prompts: &[crate::application::models::PromptMetadata]) -> HashMap<String, String> {
+        prompts
+            .iter()
+            .filter_map(|prompt| {
+                self.application.get_expanded_prompt(prompt.id())
+                    .ok()
+                    .map(|(_, content)| (prompt.id().to_string(), content))
+            })
+            .collect()
     }
 
     pub fn exit_build_mode(&mut self) {
@@ -310,45 +370,62 @@ impl TUIApp {
     }
 
     pub fn get_build_prompts(&self) -> Vec<crate::application::models::PromptMetadata> {
-        use crate::application::models::PromptType;
+        let build_types = self.config.build_types();
         
         self.prompt_list.prompts()
             .iter()
-            .filter(|p| !matches!(p.prompt_type, PromptType::Whole))
+            .filter(|p| build_types.contains(&p.prompt_type))
             .cloned()
             .collect()
     }
 
+    /// Copies the selected prompt. Prompts with `{{variables}}` open the fill-in form instead,
+    /// and are copied once it is submitted.
     pub fn copy_selected_to_clipboard(&mut self) -> Result<()> {
         if let Some(content) = self.get_selected_content() {
-            // Build content with prefix/postfix and proper newlines
-            let mut final_content = String::new();
-            
-            // Add prefix with newline if prefix exists
-            let prefix = self.config.clipboard_prefix();
-            if !prefix.is_empty() {
-                final_content.push_str(prefix);
-                final_content.push('\n');
+            let specs = match self.prompt_list.get_selected() {
+                Some(prompt) => self.application.prompt_variables(prompt.id())?,
+                None => Vec::new(),
+            };
+            if !specs.is_empty() {
+                let name = self.get_selected_prompt_name().unwrap_or_default();
+                self.variable_dialog = Some(VariableDialog::with_specs(name, content, specs));
+                return Ok(());
             }
             
-            // Add main content
-            final_content.push_str(&content);
-            
-            // Add postfix with newline before it if postfix exists
-            let postfix = self.config.clipboard_postfix();
-            if !postfix.is_empty() {
-                final_content.push('\n');
-                final_content.push_str(postfix);
-            }
-            
-            self.application.copy_to_clipboard(&final_content)?;
-            Ok(())
+            self.copy_with_affixes(&content)
         } else {
             Err(anyhow::anyhow!("No prompt selected"))
         }
     }
+    
+    fn copy_with_affixes(&mut self, content: &str) -> Result<()> {
+        // Build content with prefix/postfix and proper newlines
+        let mut final_content = String::new();
+        
+        // Add prefix with newline if prefix exists
+        let prefix = self.config.clipboard_prefix();
+        if !prefix.is_empty() {
+            final_content.push_str(prefix);
+            final_content.push('\n');
+        }
+        
+        // Add main content
+        final_content.push_str(content);
+        
+        // Add postfix with newline before it if postfix exists
+        let postfix = self.config.clipboard_postfix();
+        if !postfix.is_empty() {
+            final_content.push('\n');
+            final_content.push_str(postfix);
+        }
+        
+        self.application.copy_to_clipboard(&final_content)?;
+        Ok(())
+    }
 
     pub fn toggle_mode(&mut self) {
+        self.clear_marks();
         self.mode = match self.mode {
             AppMode::QuickSelect => AppMode::Management,
             AppMode::Management => AppMode::QuickSelect,
@@ -358,6 +435,15 @@ impl TUIApp {
     }
 
     pub fn edit_selected(&mut self) -> Result<()> {
+        self.edit_selected_at(None)
+    }
+    
+    /// Opens the selected prompt with the editor's cursor on `line`
+    pub fn edit_selected_at_line(&mut self, line: usize) -> Result<()> {
+        self.edit_selected_at(Some(line))
+    }
+    
+    fn edit_selected_at(&mut self, line: Option<usize>) -> Result<()> {
         if let Some(prompt) = self.prompt_list.get_selected() {
             // Check if editor is external (VS Code)
             let is_vscode = self.config.editor() == "code";
@@ -366,7 +452,10 @@ impl TUIApp {
             }
             
             // Delegate to the application layer
-            self.application.edit_prompt(&prompt.name)?;
+            match line {
+                Some(line) => self.application.edit_prompt_at_line(prompt.id(), line)?,
+                None => self.application.edit_prompt(prompt.id())?,
+            }
             
             // Reload prompts after editing
             self.reload_prompts()?;
@@ -410,6 +499,55 @@ impl TUIApp {
     pub fn reload_prompts(&mut self) -> Result<()> {
         let prompts_metadata = self.application.list_prompts(None)?;
         self.prompt_list.update_prompts(prompts_metadata);
+        self.prune_marks();
+        self.update_search_results();
+        Ok(())
+    }
+    
+    /// Reloads the library after a change made outside the TUI (git pull, sync, another terminal).
+    /// Keeps the selection, follows a renamed prompt, and stays near the old position if it was deleted.
+    pub fn refresh_from_disk(&mut self) -> Result<()> {
+        let previous_id = self.prompt_list.get_selected().map(|p| p.id().to_string());
+        let previous_index = self.prompt_list.selected();
+        let old_ids: HashSet<String> = self.prompt_list.prompts()
+            .iter()
+            .map(|p| p.id().to_string())
+            .collect();
+        
+        let prompts_metadata = self.application.list_prompts(None)?;
+        let added: Vec<String> = prompts_metadata
+            .iter()
+            .filter(|p| !old_ids.contains(p.id()))
+            .map(|p| p.id().to_string())
+            .collect();
+        
+        self.prompt_list.update_prompts(prompts_metadata);
+        self.prune_marks();
+        self.update_search_results();
+        
+        if let Some(previous_id) = previous_id
+            && !self.prompt_list.find_and_select(&previous_id) {
+            // A single new prompt appearing as the selected one vanished is treated as a rename
+            if let [renamed] = added.as_slice() {
+                self.prompt_list.find_and_select(renamed);
+            } else if !self.prompt_list.is_empty() {
+                self.prompt_list.set_selected(previous_index.min(self.prompt_list.len() - 1));
+            }
+        }
+        
+        // Keep build previews showing the edited content
+        if self.interactive_build_panel.is_some() {
+            let contents = self.load_build_contents(&self.get_build_prompts());
+            if let Some(panel) = self.interactive_build_panel.as_mut() {
+                panel.set_contents(contents);
+            }
+        }
+        
+        // Keep an open tag dialog in sync with the selected prompt
+        if self.tag_dialog.is_some() && !self.tag_dialog.as_ref().is_some_and(|d| d.is_in_input_mode()) {
+            self.tag_dialog = Some(TagManagementDialog::new(self.get_selected_prompt_tags()));
+        }
+        
         Ok(())
     }
 
@@ -440,8 +578,8 @@ impl TUIApp {
     pub fn show_delete_confirmation(&mut self) {
         if let Some(prompt) = self.prompt_list.get_selected() {
             self.confirmation_dialog = Some(Dialog::new(
-                format!("Are you sure you want to delete '{}'?", prompt.name),
-                ConfirmationAction::Delete(prompt.name.clone()),
+                format!("Are you sure you want to delete '{}'?", prompt.display_name()),
+                ConfirmationAction::Delete(prompt.id().to_string()),
             ));
         }
     }
@@ -452,9 +590,21 @@ impl TUIApp {
 
     pub fn confirm_action(&mut self) -> Result<()> {
         if let Some(dialog) = self.confirmation_dialog.take() {
-            if let ConfirmationAction::Delete(name) = dialog.get_action() {
-                self.application.delete_prompt(name, true)?;
-                self.reload_prompts()?;
+            match dialog.get_action() {
+                ConfirmationAction::Delete(name) => {
+                    self.application.delete_prompt(name, true)?;
+                    self.reload_prompts()?;
+                }
+                ConfirmationAction::Restore(name, revision) => {
+                    self.application.restore_prompt(name, *revision)?;
+                    self.reload_prompts()?;
+                    self.close_history();
+                    self.set_success(format!("Restored revision {}", revision));
+                }
+                ConfirmationAction::Bulk(action, ids) => {
+                    self.apply_bulk_action(action, ids)?;
+                }
+                ConfirmationAction::Overwrite(_) => {}
             }
         }
         Ok(())
@@ -467,55 +617,130 @@ impl TUIApp {
     pub fn activate_search(&mut self) {
         self.search_active = true;
         self.search_query.clear();
+        self.search_filter = SearchQuery::And(Vec::new());
+        self.search_scores.clear();
+        self.content_matches.clear();
     }
 
     pub fn deactivate_search(&mut self) {
         self.search_active = false;
         self.search_query.clear();
+        self.search_filter = SearchQuery::And(Vec::new());
+        self.search_scores.clear();
+        self.content_matches.clear();
     }
 
     pub fn set_search_query(&mut self, query: &str) {
         self.search_query = query.to_string();
+        self.selected_snippet = None;
+        if let Ok(filter) = SearchQuery::parse(query) {
+            self.search_filter = filter;
+            self.update_search_results();
+        }
+    }
+    
+    /// Scores every listed prompt against the current search and finds its content matches
+    fn update_search_results(&mut self) {
+        self.search_scores.clear();
+        self.content_matches.clear();
+        for prompt in self.prompt_list.prompts() {
+            let result = self.application.match_prompt(&self.search_filter, prompt);
+            self.search_scores.insert(prompt.id().to_string(), result.as_ref().map(|r| r.score));
+            if let Some(result) = result.filter(|r| !r.matches.is_empty()) {
+                self.content_matches.insert(prompt.id().to_string(), result.matches);
+            }
+        }
+    }
+    
+    fn evaluate_search(&self, prompt: &PromptMetadata) -> Option<i64> {
+        self.application.match_prompt(&self.search_filter, prompt).map(|result| result.score)
     }
 
     pub fn get_search_query(&self) -> &str {
         &self.search_query
     }
+    
+    /// Why the current search query does not parse, shown in the search bar
+    pub fn search_error(&self) -> Option<QueryError> {
+        SearchQuery::parse(&self.search_query).err()
+    }
+    
+    /// Where the `content:` terms of the search matched inside a prompt file
+    pub fn get_content_matches(&self, prompt: &PromptMetadata) -> &[ContentMatch] {
+        self.content_matches.get(prompt.id()).map(Vec::as_slice).unwrap_or_default()
+    }
+    
+    pub fn selected_snippet(&self) -> Option<usize> {
+        self.selected_snippet
+    }
+    
+    /// Moves through the content matches of the selected result, then back to the result itself
+    pub fn cycle_snippet(&mut self) {
+        let count = match self.prompt_list.get_selected() {
+            Some(prompt) => self.get_content_matches(prompt).len().min(MAX_SNIPPETS),
+            None => 0,
+        };
+        self.selected_snippet = match self.selected_snippet {
+            None if count > 0 => Some(0),
+            Some(index) if index + 1 < count => Some(index + 1),
+            _ => None,
+        };
+    }
+    
+    /// Queues the editor for the selected snippet's line; false if no snippet is selected
+    pub fn open_selected_snippet(&mut self) -> bool {
+        let line = self.selected_snippet.and_then(|index| {
+            let prompt = self.prompt_list.get_selected()?;
+            self.get_content_matches(prompt).get(index).map(|found| found.line)
+        });
+        match line {
+            Some(line) => {
+                self.pending_action = Some(PendingAction::EditAtLine(line));
+                true
+            }
+            None => false,
+        }
+    }
+    
+    /// Words of the search query to highlight in matching names
+    pub fn search_highlight_terms(&self) -> String {
+        self.search_filter.highlight_terms().join(" ")
+    }
 
     pub fn get_filtered_prompts(&self) -> Vec<crate::application::models::PromptMetadata> {
         let base_prompts = self.prompt_list.prompts();
+        let tag_filter = self.tag_filter();
         
         // Only clone if we need to filter, otherwise work with references
-        let filtered: Vec<&crate::application::models::PromptMetadata> = base_prompts
+        let filtered: Vec<(&crate::application::models::PromptMetadata, i64)> = base_prompts
             .iter()
             .filter(|p| {
-                // Apply tag filter if active
-                if !self.active_tag_filters.is_empty() && !self.active_tag_filters.iter().any(|tag| p.tags.contains(tag)) {
-                    return false;
-                }
-                
-                // Apply search filter if active
-                if !self.search_query.is_empty() {
-                    let query_lower = self.search_query.to_lowercase();
-                    if !p.name.to_lowercase().contains(&query_lower) {
-                        return false;
-                    }
-                }
-                
-                true
+                // Apply tag filter if active, a parent tag matching all of its descendants
+                tag_filter.matches(p)
+            })
+            .filter_map(|p| {
+                // Apply the search if active, keeping the score for ranking. Prompts listed
+                // since the last evaluation are scored on the spot.
+                let score = match self.search_scores.get(p.id()) {
+                    Some(score) => *score,
+                    None => self.evaluate_search(p),
+                };
+                score.map(|score| (p, score))
             })
             .collect();
         
         // Now clone and sort
-        let mut prompts: Vec<_> = filtered.into_iter().cloned().collect();
+        let mut prompts: Vec<_> = filtered.into_iter().map(|(p, score)| (p.clone(), score)).collect();
         
-        // Sort prompts: starred first, then alphabetically within each group
-        prompts.sort_by_cached_key(|p| {
+        // Sort prompts: best match first while searching, otherwise starred first, then alphabetically
+        let searching = self.search_active;
+        prompts.sort_by_cached_key(|(p, score)| {
+            let rank = std::cmp::Reverse(if searching { *score } else { 0 });
             let is_starred = !p.tags.iter().any(|t| t == STARRED_TAG);
-            (is_starred, p.name.to_lowercase())
+            (rank, is_starred, p.display_name().to_lowercase())
         });
         
-        prompts
+        prompts.into_iter().map(|(p, _)| p).collect()
     }
     
     // Tag filtering methods
@@ -533,7 +758,7 @@ impl TUIApp {
     
     pub fn set_tag_filters(&mut self, tags: HashSet<String>) {
         self.active_tag_filters = tags;
-        self.tag_filter_active = !self.active_tag_filters.is_empty();
+        self.update_tag_filter_active();
     }
     
     pub fn add_tag_filter(&mut self, tag: &str) {
@@ -543,14 +768,70 @@ impl TUIApp {
     
     pub fn remove_tag_filter(&mut self, tag: &str) {
         self.active_tag_filters.remove(tag);
-        self.tag_filter_active = !self.active_tag_filters.is_empty();
+        self.update_tag_filter_active();
     }
     
     pub fn clear_tag_filters(&mut self) {
         self.active_tag_filters.clear();
+        self.excluded_tag_filters.clear();
+        self.tag_match = TagMatch::Any;
         self.tag_filter_active = false;
     }
     
+    /// Tags whose prompts are hidden
+    pub fn get_excluded_tag_filters(&self) -> &HashSet<String> {
+        &self.excluded_tag_filters
+    }
+    
+    pub fn set_excluded_tag_filters(&mut self, tags: HashSet<String>) {
+        self.excluded_tag_filters = tags;
+        self.update_tag_filter_active();
+    }
+    
+    /// Whether prompts need any or all of the active tag filters
+    pub fn get_tag_match(&self) -> TagMatch {
+        self.tag_match
+    }
+    
+    pub fn set_tag_match(&mut self, tag_match: TagMatch) {
+        self.tag_match = tag_match;
+    }
+    
+    fn update_tag_filter_active(&mut self) {
+        self.tag_filter_active = !self.active_tag_filters.is_empty() || !self.excluded_tag_filters.is_empty();
+    }
+    
+    /// The tag filters as a `PromptFilter`, as `fink list` would apply them
+    pub fn tag_filter(&self) -> PromptFilter {
+        PromptFilter {
+            tags: Some(self.active_tag_filters.iter().cloned().collect()),
+            tag_match: self.tag_match,
+            exclude_tags: self.excluded_tag_filters.iter().cloned().collect(),
+            types: Vec::new(),
+        }
+    }
+    
+    /// Active tag filters for headers, e.g. `all of rust, review, -draft`; None without filters
+    pub fn tag_filter_summary(&self) -> Option<String> {
+        if !self.tag_filter_active {
+            return None;
+        }
+        
+        let mut included: Vec<&str> = self.active_tag_filters.iter().map(String::as_str).collect();
+        let mut excluded: Vec<String> = self.excluded_tag_filters.iter().map(|tag| format!("-{}", tag)).collect();
+        included.sort();
+        excluded.sort();
+        
+        let mut parts: Vec<String> = included.iter().map(|tag| tag.to_string()).collect();
+        parts.extend(excluded);
+        let summary = parts.join(", ");
+        if self.tag_match == TagMatch::All && included.len() > 1 {
+            Some(format!("all of {}", summary))
+        } else {
+            Some(summary)
+        }
+    }
+    
     pub fn get_all_tags(&self) -> Vec<String> {
         let mut tags = std::collections::HashSet::<&str>::new();
         
@@ -565,6 +846,13 @@ impl TUIApp {
         sorted_tags
     }
     
+    /// Number of prompts under each tag, including parents of hierarchical tags like `lang/rust`
+    pub fn get_tag_counts(&self) -> HashMap<String, usize> {
+        tags::count_tags(self.prompt_list.prompts().iter().map(|p| p.tags.as_slice()))
+            .into_iter()
+            .collect()
+    }
+    
     // Tag management methods
     pub fn add_tag_to_selected(&mut self, tag: &str) -> Result<()> {
         if let Some(prompt) = self.prompt_list.get_selected() {
@@ -579,7 +867,7 @@ impl TUIApp {
             tags.push(tag.to_string());
             
             // Update the prompt with new tags
-            self.application.update_prompt_tags(&prompt.name, tags)?;
+            self.application.update_prompt_tags(prompt.id(), tags)?;
             
             // Reload prompts to reflect changes
             self.reload_prompts()?;
@@ -603,7 +891,7 @@ impl TUIApp {
             tags.retain(|t| t != tag);
             
             // Update the prompt with new tags
-            self.application.update_prompt_tags(&prompt.name, tags)?;
+            self.application.update_prompt_tags(prompt.id(), tags)?;
             
             // Reload prompts to reflect changes
             self.reload_prompts()?;
@@ -621,12 +909,12 @@ impl TUIApp {
             if tags.iter().any(|t| t == STARRED_TAG) {
                 // Remove star
                 tags.retain(|t| t != STARRED_TAG);
-                self.application.update_prompt_tags(&prompt.name, tags)?;
+                self.application.update_prompt_tags(prompt.id(), tags)?;
                 self.set_success("Removed star from prompt".to_string());
             } else {
                 // Add star
                 tags.push(STARRED_TAG.to_string());
-                self.application.update_prompt_tags(&prompt.name, tags)?;
+                self.application.update_prompt_tags(prompt.id(), tags)?;
                 self.set_success("Added star to prompt".to_string());
             }
             
@@ -639,9 +927,30 @@ impl TUIApp {
         }
     }
     
+    /// The prompts a library-wide tag change would touch
+    pub fn preview_tag_operation(&self, operation: &TagOperation) -> Result<Vec<TagChange>> {
+        Ok(self.application.preview_tag_operation(operation)?)
+    }
+    
+    /// Applies a tag change to every prompt and returns how many changed
+    pub fn apply_tag_operation(&mut self, operation: &TagOperation) -> Result<usize> {
+        let changes = self.application.apply_tag_operation(operation)?;
+        self.reload_prompts()?;
+        Ok(changes.len())
+    }
+    
+    /// Opens the tag dialog for the selected prompt, or for every marked prompt if any are marked
     pub fn open_tag_management(&mut self) {
-        let tags = self.get_selected_prompt_tags();
-        self.tag_dialog = Some(TagManagementDialog::new(tags));
+        if self.has_marks() {
+            let marked = self.get_marked_prompts();
+            let mut tags: Vec<String> = marked.iter().flat_map(|p| p.tags.iter().cloned()).collect();
+            tags.sort();
+            tags.dedup();
+            self.tag_dialog = Some(TagManagementDialog::new(tags).with_marked_count(marked.len()));
+        } else {
+            let tags = self.get_selected_prompt_tags();
+            self.tag_dialog = Some(TagManagementDialog::new(tags));
+        }
         self.tag_management_active = true;
     }
     
@@ -670,10 +979,206 @@ impl TUIApp {
         }
     }
     
+    // Multi-select methods
+    pub fn has_marks(&self) -> bool {
+        !self.marked.is_empty()
+    }
+    
+    pub fn is_marked(&self, prompt: &PromptMetadata) -> bool {
+        self.marked.contains(prompt.id())
+    }
+    
+    pub fn marked_count(&self) -> usize {
+        self.marked.len()
+    }
+    
+    /// Marks or unmarks the selected prompt, starting the next range from it
+    pub fn toggle_mark_selected(&mut self) {
+        if let Some(id) = self.prompt_list.get_selected().map(|p| p.id().to_string()) {
+            if !self.marked.remove(&id) {
+                self.marked.insert(id.clone());
+            }
+            self.mark_anchor = Some(id);
+        }
+    }
+    
+    /// Marks every listed prompt between the last marked one and the selected one
+    pub fn mark_range_to_selected(&mut self) {
+        let Some(selected) = self.prompt_list.get_selected().map(|p| p.id().to_string()) else {
+            return;
+        };
+        let ids: Vec<String> = self.get_filtered_prompts().iter().map(|p| p.id().to_string()).collect();
+        let end = ids.iter().position(|id| *id == selected);
+        let start = self.mark_anchor.as_ref().and_then(|anchor| ids.iter().position(|id| id == anchor)).or(end);
+        
+        if let (Some(start), Some(end)) = (start, end) {
+            let (from, to) = if start <= end { (start, end) } else { (end, start) };
+            self.marked.extend(ids[from..=to].iter().cloned());
+        }
+        self.mark_anchor = Some(selected);
+    }
+    
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+        self.mark_anchor = None;
+    }
+    
+    // Drops marks of prompts that no longer exist
+    fn prune_marks(&mut self) {
+        let ids: HashSet<&str> = self.prompt_list.prompts().iter().map(|p| p.id()).collect();
+        self.marked.retain(|id| ids.contains(id.as_str()));
+    }
+    
+    /// Marked prompts in list order, followed by any that the filters currently hide
+    pub fn get_marked_prompts(&self) -> Vec<PromptMetadata> {
+        let mut prompts: Vec<PromptMetadata> = self.get_filtered_prompts()
+            .into_iter()
+            .filter(|p| self.is_marked(p))
+            .collect();
+        let hidden: Vec<PromptMetadata> = self.prompt_list.prompts()
+            .iter()
+            .filter(|p| self.is_marked(p) && !prompts.iter().any(|listed| listed.id() == p.id()))
+            .cloned()
+            .collect();
+        prompts.extend(hidden);
+        prompts
+    }
+    
+    // The marked prompts, or the selected one when nothing is marked
+    fn bulk_targets(&self) -> Vec<PromptMetadata> {
+        if self.has_marks() {
+            self.get_marked_prompts()
+        } else {
+            self.prompt_list.get_selected().cloned().into_iter().collect()
+        }
+    }
+    
+    /// Asks to apply `action` to the marked prompts (or the selected one), listing them all
+    pub fn show_bulk_confirmation(&mut self, action: BulkAction) {
+        let targets = self.bulk_targets();
+        if targets.is_empty() {
+            return;
+        }
+        self.confirmation_dialog = Some(
+            Dialog::new(
+                action.describe(targets.len()),
+                ConfirmationAction::Bulk(action, targets.iter().map(|p| p.id().to_string()).collect()),
+            )
+            .with_details(targets.iter().map(|p| p.display_name()).collect()),
+        );
+    }
+    
+    /// Stars the marked prompts, or removes the star if every one of them has it
+    pub fn show_bulk_star_confirmation(&mut self) {
+        let all_starred = self.bulk_targets().iter().all(|p| p.tags.iter().any(|t| t == STARRED_TAG));
+        self.show_bulk_confirmation(if all_starred { BulkAction::Unstar } else { BulkAction::Star });
+    }
+    
+    /// Asks to change the type of the marked prompts, starting from the type after the first one's
+    pub fn show_bulk_type_confirmation(&mut self) {
+        if let Some(first) = self.bulk_targets().first() {
+            let prompt_type = self.config.prompt_types().next(&first.prompt_type);
+            self.show_bulk_confirmation(BulkAction::SetType(prompt_type));
+        }
+    }
+    
+    /// Asks to export the marked prompts into a new timestamped folder in the working directory
+    pub fn show_bulk_export_confirmation(&mut self) -> Result<()> {
+        let directory = std::env::current_dir()?
+            .join(format!("fink-export-{}", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+        self.show_bulk_confirmation(BulkAction::Export(directory));
+        Ok(())
+    }
+    
+    /// Picks the next or previous type in a pending bulk type change
+    pub fn cycle_bulk_type(&mut self, forward: bool) {
+        let types = self.config.prompt_types().clone();
+        if let Some(dialog) = self.confirmation_dialog.as_mut()
+            && let ConfirmationAction::Bulk(BulkAction::SetType(prompt_type), ids) = dialog.get_action() {
+            let prompt_type = if forward { types.next(prompt_type) } else { types.previous(prompt_type) };
+            let action = BulkAction::SetType(prompt_type);
+            let ids = ids.clone();
+            dialog.set_action(action.describe(ids.len()), ConfirmationAction::Bulk(action, ids));
+        }
+    }
+    
+    fn apply_bulk_action(&mut self, action: &BulkAction, ids: &[String]) -> Result<()> {
+        let prompts: Vec<PromptMetadata> = ids
+            .iter()
+            .filter_map(|id| self.prompt_list.prompts().iter().find(|p| p.id() == id).cloned())
+            .collect();
+        
+        // Reload even when a prompt fails part way, so the list shows what did change
+        let result = self.run_bulk_action(action, &prompts);
+        self.reload_prompts()?;
+        let message = result?;
+        
+        self.clear_marks();
+        self.set_success(message);
+        Ok(())
+    }
+    
+    fn run_bulk_action(&self, action: &BulkAction, prompts: &[PromptMetadata]) -> Result<String> {
+        let count = prompts.len();
+        let add_tag = |tag: &str| -> Result<usize> {
+            let untagged: Vec<_> = prompts.iter().filter(|p| !p.tags.iter().any(|t| t == tag)).collect();
+            for prompt in &untagged {
+                let mut tags = prompt.tags.clone();
+                tags.push(tag.to_string());
+                self.application.update_prompt_tags(prompt.id(), tags)?;
+            }
+            Ok(untagged.len())
+        };
+        let remove_tag = |tag: &str| -> Result<usize> {
+            let tagged: Vec<_> = prompts.iter().filter(|p| p.tags.iter().any(|t| t == tag)).collect();
+            for prompt in &tagged {
+                let tags = prompt.tags.iter().filter(|t| *t != tag).cloned().collect();
+                self.application.update_prompt_tags(prompt.id(), tags)?;
+            }
+            Ok(tagged.len())
+        };
+        
+        Ok(match action {
+            BulkAction::Delete => {
+                for prompt in prompts {
+                    self.application.delete_prompt(prompt.id(), true)?;
+                }
+                format!("Deleted {} prompt(s)", count)
+            }
+            BulkAction::AddTag(tag) => format!("Added '{}' to {} prompt(s)", tag, add_tag(tag)?),
+            BulkAction::RemoveTag(tag) => format!("Removed '{}' from {} prompt(s)", tag, remove_tag(tag)?),
+            BulkAction::Star => format!("Starred {} prompt(s)", add_tag(STARRED_TAG)?),
+            BulkAction::Unstar => format!("Removed the star from {} prompt(s)", remove_tag(STARRED_TAG)?),
+            BulkAction::SetType(prompt_type) => {
+                let changed: Vec<_> = prompts.iter().filter(|p| p.prompt_type != *prompt_type).collect();
+                for prompt in &changed {
+                    self.application.update_prompt_type(prompt.id(), prompt_type.clone())?;
+                }
+                format!("Changed {} prompt(s) to {}", changed.len(), self.config.prompt_types().display_name(prompt_type))
+            }
+            BulkAction::Export(directory) => {
+                let ids: Vec<String> = prompts.iter().map(|p| p.id().to_string()).collect();
+                let files = self.application.export_prompts(&ids, directory)?;
+                format!("Exported {} prompt(s) to {}", files.len(), directory.display())
+            }
+            BulkAction::Copy => {
+                let ids: Vec<String> = prompts.iter().map(|p| p.id().to_string()).collect();
+                let combined = self.application.combine_prompts(&ids, "", self.config.build_format())?;
+                self.application.copy_to_clipboard(&combined)?;
+                format!("Combined {} prompts and copied to clipboard!", count)
+            }
+        })
+    }
+    
     // Tag filter dialog methods
     pub fn open_tag_filter(&mut self) {
         let all_tags = self.get_all_tags();
-        self.tag_filter_dialog = Some(TagFilterDialog::new(all_tags, self.active_tag_filters.clone()));
+        self.tag_filter_dialog = Some(
+            TagFilterDialog::new(all_tags, self.active_tag_filters.clone())
+                .with_counts(self.get_tag_counts())
+                .with_exclusions(self.excluded_tag_filters.clone())
+                .with_tag_match(self.tag_match)
+        );
         self.tag_filter_dialog_active = true;
     }
     
@@ -696,7 +1201,9 @@ impl TUIApp {
     
     // Create dialog methods
     pub fn open_create_dialog(&mut self) {
-        self.create_dialog = Some(CreateDialog::new());
+        let user_templates = self.application.list_templates().unwrap_or_default();
+        self.create_dialog = Some(CreateDialog::with_templates(user_templates)
+            .with_prompt_types(self.config.prompt_types().clone()));
         self.create_dialog_active = true;
     }
     
@@ -718,37 +1225,172 @@ impl TUIApp {
     }
     
     pub fn confirm_create(&mut self) -> Result<()> {
-        if let Some(dialog) = &self.create_dialog {
-            if dialog.is_valid() {
-                let filename = dialog.get_normalized_filename();
-                let prompt_type = dialog.get_prompt_type();
-                
-                match dialog.get_template() {
-                    crate::presentation::tui::components::CreateTemplate::FromClipboard => {
-                        // Get clipboard content and create prompt with it
-                        let clipboard_content = self.application.get_clipboard_content().ok();
-                        self.application.create_prompt_with_content_and_type(&filename, Some("clipboard"), clipboard_content, prompt_type)?;
-                    },
-                    crate::presentation::tui::components::CreateTemplate::Basic => {
-                        self.application.create_prompt_with_type(&filename, Some("basic"), prompt_type)?;
-                    },
-                    crate::presentation::tui::components::CreateTemplate::Default => {
-                        self.application.create_prompt_with_type(&filename, None, prompt_type)?;
-                    },
-                };
-                
-                self.close_create_dialog();
-                self.reload_prompts()?;
+        if let Some(dialog) = &self.create_dialog
+            && dialog.is_valid() {
+            let filename = dialog.get_normalized_filename();
+            let prompt_type = dialog.get_prompt_type();
+
+            match dialog.get_template() {
+                crate::presentation::tui::components::CreateTemplate::FromClipboard => {
+                    // Get clipboard content and create prompt with it
+                    let clipboard_content = self.application.get_clipboard_content().ok();
+                    self.application.create_prompt_with_content_and_type(&filename, Some("clipboard"), clipboard_content, prompt_type)?;
+                },
+                crate::presentation::tui::components::CreateTemplate::Basic => {
+                    self.application.create_prompt_with_type(&filename, Some("basic"), prompt_type)?;
+                },
+                crate::presentation::tui::components::CreateTemplate::Default => {
+                    self.application.create_prompt_with_type(&filename, None, prompt_type)?;
+                },
+                crate::presentation::tui::components::CreateTemplate::User(template) => {
+                    self.application.create_prompt_with_type(&filename, Some(&template), prompt_type)?;
+                },
+            };
+
+            self.close_create_dialog();
+            self.reload_prompts()?;
+        }
+        Ok(())
+    }
+    
+    // History dialog methods
+    pub fn open_history(&mut self) -> Result<()> {
+        if let Some(prompt) = self.prompt_list.get_selected() {
+            let revisions = self.application.list_revisions(prompt.id())?;
+            self.history_dialog = Some(HistoryDialog::new(prompt.id().to_string(), prompt.display_name(), revisions));
+            self.refresh_history_diff()?;
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No prompt selected"))
+        }
+    }
+    
+    pub fn close_history(&mut self) {
+        self.history_dialog = None;
+    }
+    
+    pub fn is_history_active(&self) -> bool {
+        self.history_dialog.is_some()
+    }
+    
+    pub fn get_history_dialog(&self) -> Option<&HistoryDialog> {
+        self.history_dialog.as_ref()
+    }
+    
+    pub fn get_history_dialog_mut(&mut self) -> Option<&mut HistoryDialog> {
+        self.history_dialog.as_mut()
+    }
+    
+    /// Recomputes the diff for the revision selected in the history dialog
+    pub fn refresh_history_diff(&mut self) -> Result<()> {
+        if let Some(dialog) = &self.history_dialog
+            && let Some(revision) = dialog.selected_revision() {
+            let diff = self.application.diff_prompt(dialog.prompt_id(), Some(revision))?;
+            if let Some(dialog) = self.history_dialog.as_mut() {
+                dialog.set_diff(diff);
             }
         }
         Ok(())
     }
     
+    pub fn show_restore_confirmation(&mut self) {
+        if let Some(dialog) = &self.history_dialog
+            && let Some(revision) = dialog.selected_revision() {
+            self.confirmation_dialog = Some(Dialog::new(
+                format!("Restore '{}' to revision {}?", dialog.prompt_name(), revision),
+                ConfirmationAction::Restore(dialog.prompt_id().to_string(), revision),
+            ));
+        }
+    }
+    
+    // Trash dialog methods
+    pub fn open_trash(&mut self) -> Result<()> {
+        let entries = self.application.list_trash()?;
+        self.trash_dialog = Some(TrashDialog::new(entries));
+        Ok(())
+    }
+    
+    pub fn close_trash(&mut self) {
+        self.trash_dialog = None;
+    }
+    
+    pub fn is_trash_active(&self) -> bool {
+        self.trash_dialog.is_some()
+    }
+    
+    pub fn get_trash_dialog(&self) -> Option<&TrashDialog> {
+        self.trash_dialog.as_ref()
+    }
+    
+    pub fn get_trash_dialog_mut(&mut self) -> Option<&mut TrashDialog> {
+        self.trash_dialog.as_mut()
+    }
+    
+    pub fn restore_selected_trash_entry(&mut self) -> Result<()> {
+        let id = self.trash_dialog.as_ref()
+            .and_then(|dialog| dialog.selected_entry())
+            .map(|entry| entry.id.clone());
+        
+        if let Some(id) = id {
+            let entry = self.application.restore_from_trash(&id)?;
+            self.close_trash();
+            self.reload_prompts()?;
+            self.prompt_list.find_and_select(entry.prompt_id());
+            self.set_success(format!("Restored '{}'", entry.prompt_id()));
+        }
+        Ok(())
+    }
+    
+    // Variable dialog methods
+    pub fn close_variable_dialog(&mut self) {
+        self.variable_dialog = None;
+    }
+    
+    pub fn is_variable_dialog_active(&self) -> bool {
+        self.variable_dialog.is_some()
+    }
+    
+    pub fn get_variable_dialog(&self) -> Option<&VariableDialog> {
+        self.variable_dialog.as_ref()
+    }
+    
+    pub fn get_variable_dialog_mut(&mut self) -> Option<&mut VariableDialog> {
+        self.variable_dialog.as_mut()
+    }
+    
+    /// Copies the prompt with the values entered in the variable form
+    /// Returns false and keeps the form open when a value is missing or invalid.
+    pub fn submit_variable_dialog(&mut self) -> Result<bool> {
+        let Some(dialog) = self.variable_dialog.as_mut() else {
+            return Ok(false);
+        };
+        
+        match dialog.submit() {
+            Ok(content) => {
+                self.variable_dialog = None;
+                self.copy_with_affixes(&content)?;
+                Ok(true)
+            }
+            Err(e) => {
+                dialog.set_error(e.to_string());
+                Ok(false)
+            }
+        }
+    }
+    
     // Error message methods
     pub fn set_error(&mut self, message: String) {
         self.error_message = Some(message);
     }
     
+    /// Shows the latest new warning from reading prompts, such as frontmatter that does not parse
+    pub fn show_warnings(&mut self) {
+        let warnings = self.application.take_warnings();
+        if let Some(warning) = warnings.into_iter().filter(|w| self.shown_warnings.insert(w.clone())).last() {
+            self.set_error(format!("Warning: {}", warning));
+        }
+    }
+    
     pub fn clear_error(&mut self) {
         self.error_message = None;
     }
@@ -830,11 +1472,16 @@ impl TUIApp {
     }
     
     pub fn accept_type_prompts_dialog(&mut self) -> Result<()> {
-        use crate::utils::default_prompts::initialize_type_specific_prompts;
+        use crate::utils::default_prompts::initialize_type_specific_prompts_for;
         
-        // Initialize type-specific prompts
+        // Initialize type-specific prompts for the configured types only
         let prompts_dir = self.get_base_path().join(PROMPTS_DIR);
-        initialize_type_specific_prompts(&prompts_dir)?;
+        let types: Vec<_> = self.config.prompt_types()
+            .definitions()
+            .iter()
+            .map(|definition| definition.id.clone())
+            .collect();
+        initialize_type_specific_prompts_for(&prompts_dir, &types)?;
         
         // Reload prompts to show them
         self.reload_prompts()?;
@@ -858,6 +1505,57 @@ impl TUIApp {
         self.interactive_build_panel.as_mut()
     }
     
+    /// Makes the format picked in build mode the default for the next build
+    fn remember_build_format(&mut self, format: OutputFormat) {
+        if self.config.build_format() != format {
+            self.config.set_build_format(format);
+            if let Err(e) = self.config.save(&self.config_path) {
+                self.set_error(format!("Failed to save build format: {}", e));
+            }
+        }
+    }
+    
+    /// Saves the finished interactive build under the name typed on the completion screen
+    pub fn save_build_as_recipe(&mut self) -> Result<()> {
+        let Some(panel) = self.interactive_build_panel.as_mut() else {
+            return Err(anyhow::anyhow!("Build panel not initialized"));
+        };
+        let name = panel.recipe_name().unwrap_or_default().trim().to_string();
+        
+        match self.application.save_recipe(&name, panel.get_selected_prompt_ids(), panel.get_comment()) {
+            Ok(recipe) => {
+                panel.cancel_saving_recipe();
+                panel.set_recipe_message(format!("Saved recipe '{}'. Run it with 'fink build {}'", recipe.name, recipe.name));
+                Ok(())
+            }
+            Err(e) => {
+                panel.set_recipe_message(format!("Could not save recipe: {}", e));
+                Err(e.into())
+            }
+        }
+    }
+    
+    /// Combines and copies a saved recipe, then leaves build mode
+    pub fn run_recipe(&mut self, index: usize) -> Result<()> {
+        let Some(recipe) = self.interactive_build_panel
+            .as_ref()
+            .and_then(|panel| panel.recipes().get(index))
+            .cloned() else {
+            return Ok(());
+        };
+        
+        // The format picked in the panel, as for a build made by hand
+        let format = self.interactive_build_panel
+            .as_ref()
+            .map_or_else(|| self.config.build_format(), |panel| panel.format());
+        let combined_content = self.application.combine_prompts(&recipe.prompts, &recipe.comment, format)?;
+        self.application.copy_to_clipboard(&combined_content)?;
+        self.set_success(format!("Copied recipe '{}' to clipboard!", recipe.name));
+        self.remember_build_format(format);
+        self.exit_build_mode();
+        Ok(())
+    }
+    
     pub fn combine_and_copy_selected_prompts(&mut self) -> Result<()> {
         if let Some(interactive_panel) = &self.interactive_build_panel {
             let selected_prompts = interactive_panel.get_selected_prompt_names();
@@ -866,27 +1564,12 @@ impl TUIApp {
                 return Err(anyhow::anyhow!("No prompts selected"));
             }
             
-            let mut combined_content = String::new();
-            
-            // Add prompts in order
-            for (_, prompt_name) in &selected_prompts {
-                if !combined_content.is_empty() {
-                    combined_content.push_str("\n\n");
-                }
-                
-                // Get the actual content of the prompt
-                if let Ok((_, content)) = self.application.get_prompt(prompt_name) {
-                    combined_content.push_str(&content);
-                }
-            }
-            
-            // Add comment if provided
-            let comment = interactive_panel.get_comment();
-            if !comment.is_empty() {
-                combined_content.push_str("\n\n");
-                combined_content.push_str("# Additional Notes:\n");
-                combined_content.push_str(comment);
-            }
+            let format = interactive_panel.format();
+            let combined_content = self.application.combine_prompts(
+                &interactive_panel.get_selected_prompt_ids(),
+                interactive_panel.get_comment(),
+                format,
+            )?;
             
             // Copy to clipboard
             self.application.copy_to_clipboard(&combined_content)?;
@@ -894,6 +1577,8 @@ impl TUIApp {
             // Set success message with count
             let prompt_count = selected_prompts.len();
             self.set_success(format!("Successfully combined {} prompts and copied to clipboard!", prompt_count));
+            // After the success message, which would clear a failure to save the format
+            self.remember_build_format(format);
             
             // Exit build mode after successful copy
             self.exit_build_mode();
@@ -908,14 +1593,7 @@ impl TUIApp {
             }
             
             // Combine prompts by type order
-            use crate::application::models::PromptType;
-            let type_order = [
-                PromptType::Instruction,
-                PromptType::Context,
-                PromptType::InputIndicator,
-                PromptType::OutputIndicator,
-                PromptType::Etc,
-            ];
+            let type_order = self.config.build_types();
             
             let mut combined_content = String::new();
             
@@ -933,7 +1611,7 @@ impl TUIApp {
                     }
                     
                     // Get the actual content of the prompt
-                    if let Ok((_, content)) = self.application.get_prompt(&prompt.name) {
+                    if let Ok((_, content)) = self.application.get_expanded_prompt(prompt.id()) {
                         combined_content.push_str(&content);
                     }
                 }
@@ -1,4 +1,4 @@
-use crate::application::models::{PromptMetadata, PromptType};
+use crate::application::models::PromptMetadata;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
@@ -70,13 +70,12 @@ impl BuildPanel {
     }
     
     pub fn toggle_selection(&mut self) {
-        if let Some(selected) = self.list_state.selected() {
-            if let Some(prompt) = self.prompts.get(selected) {
-                if self.selected_prompts.contains(&prompt.name) {
-                    self.selected_prompts.remove(&prompt.name);
-                } else {
-                    self.selected_prompts.insert(prompt.name.clone());
-                }
+        if let Some(selected) = self.list_state.selected()
+            && let Some(prompt) = self.prompts.get(selected) {
+            if self.selected_prompts.contains(&prompt.name) {
+                self.selected_prompts.remove(&prompt.name);
+            } else {
+                self.selected_prompts.insert(prompt.name.clone());
             }
         }
     }
@@ -102,13 +101,13 @@ impl BuildPanel {
         let items: Vec<ListItem> = self.prompts
             .iter()
             .map(|prompt| {
-                let type_indicator = match prompt.prompt_type {
-                    PromptType::Instruction => "📝",
-                    PromptType::Context => "📚",
-                    PromptType::InputIndicator => "⬇️",
-                    PromptType::OutputIndicator => "⬆️",
-                    PromptType::Etc => "🔧",
-                    PromptType::Whole => "📦",
+                let type_indicator = match prompt.prompt_type.as_str() {
+                    "instruction" => "📝",
+                    "context" => "📚",
+                    "input_indicator" => "⬇️",
+                    "output_indicator" => "⬆️",
+                    "whole" => "📦",
+                    _ => "🔧",
                 };
                 
                 let selected_indicator = if self.selected_prompts.contains(&prompt.name) {
@@ -154,6 +153,7 @@ impl BuildPanel {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::application::models::PromptType;
     use tempfile::tempdir;
     
     #[test]
@@ -166,7 +166,8 @@ mod tests {
                 name: "test1".to_string(),
                 file_path: test_file.to_string_lossy().to_string(),
                 tags: vec![],
-                prompt_type: PromptType::Instruction,
+                prompt_type: PromptType::INSTRUCTION,
+                ..Default::default()
             },
         ];
         
@@ -185,7 +186,8 @@ mod tests {
                 name: "test1".to_string(),
                 file_path: test_file.to_string_lossy().to_string(),
                 tags: vec![],
-                prompt_type: PromptType::Instruction,
+                prompt_type: PromptType::INSTRUCTION,
+                ..Default::default()
             },
         ];
         
@@ -5,25 +5,76 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
+use crate::application::models::PromptType;
+use std::path::PathBuf;
+
+/// Affected prompts listed in a confirmation before the rest are summarized
+const MAX_DETAILS: usize = 8;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfirmationAction {
     Delete(String),
     Overwrite(String),
+    /// Restore the prompt with the given id to a revision number
+    Restore(String, u32),
+    /// Apply one action to every prompt id, from the prompts marked in management mode
+    Bulk(BulkAction, Vec<String>),
+}
+
+/// What to do with the marked prompts
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkAction {
+    Delete,
+    AddTag(String),
+    RemoveTag(String),
+    Star,
+    Unstar,
+    SetType(PromptType),
+    /// Copy the prompt files into a new directory
+    Export(PathBuf),
+    /// Combine the prompts into one clipboard payload
+    Copy,
+}
+
+impl BulkAction {
+    /// Question asked before the action, e.g. `Delete 3 prompts?`
+    pub fn describe(&self, count: usize) -> String {
+        let prompts = if count == 1 { "1 prompt".to_string() } else { format!("{} prompts", count) };
+        match self {
+            BulkAction::Delete => format!("Delete {}?", prompts),
+            BulkAction::AddTag(tag) => format!("Add tag '{}' to {}?", tag, prompts),
+            BulkAction::RemoveTag(tag) => format!("Remove tag '{}' from {}?", tag, prompts),
+            BulkAction::Star => format!("Star {}?", prompts),
+            BulkAction::Unstar => format!("Remove the star from {}?", prompts),
+            BulkAction::SetType(prompt_type) => format!("Change {} to type {}? (←/→ to pick)", prompts, prompt_type),
+            BulkAction::Export(directory) => format!("Export {} to {}?", prompts, directory.display()),
+            BulkAction::Copy => format!("Combine {} and copy to clipboard?", prompts),
+        }
+    }
 }
 
 pub struct ConfirmationDialog {
     message: String,
     action: ConfirmationAction,
+    // Names of the prompts the action affects, listed under the message
+    details: Vec<String>,
 }
 
 impl ConfirmationDialog {
     pub fn new(message: String, action: ConfirmationAction) -> Self {
-        Self { message, action }
+        Self { message, action, details: Vec::new() }
+    }
+    
+    pub fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
     }
     
     pub fn get_dimensions(&self) -> (u16, u16) {
-        (60, 7) // width, height
+        // One line per listed prompt, plus one for the summary of the rest
+        let listed = self.details.len().min(MAX_DETAILS + 1);
+        let details_height = if listed == 0 { 0 } else { listed as u16 + 1 };
+        (60, 7 + details_height) // width, height
     }
     
     pub fn get_action(&self) -> &ConfirmationAction {
@@ -34,6 +85,16 @@ impl ConfirmationDialog {
         &self.message
     }
     
+    pub fn get_details(&self) -> &[String] {
+        &self.details
+    }
+    
+    /// Replaces the action and its message, keeping the listed prompts
+    pub fn set_action(&mut self, message: String, action: ConfirmationAction) {
+        self.message = message;
+        self.action = action;
+    }
+    
     pub fn render(&self, f: &mut Frame, area: Rect) {
         let (width, height) = self.get_dimensions();
         
@@ -52,18 +113,31 @@ impl ConfirmationDialog {
         f.render_widget(Clear, dialog_area);
         
         // Create dialog content
-        let text = vec![
+        let mut text = vec![
             Line::from(""),
             Line::from(Span::raw(&self.message)),
             Line::from(""),
-            Line::from(vec![
+        ];
+        if !self.details.is_empty() {
+            let shown = if self.details.len() > MAX_DETAILS + 1 { MAX_DETAILS } else { self.details.len() };
+            text.extend(self.details.iter().take(shown).map(|name| {
+                Line::from(Span::styled(name.as_str(), Style::default().fg(Color::Gray)))
+            }));
+            if self.details.len() > shown {
+                text.push(Line::from(Span::styled(
+                    format!("… and {} more", self.details.len() - shown),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            text.push(Line::from(""));
+        }
+        text.push(Line::from(vec![
                 Span::raw("Press "),
                 Span::styled("[Y]es", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
                 Span::raw(" to confirm or "),
                 Span::styled("[N]o", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
                 Span::raw(" to cancel"),
-            ]),
-        ];
+            ]));
         
         let dialog = Paragraph::new(text)
             .block(Block::default()
@@ -5,7 +5,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Widget},
 };
-use crate::application::models::PromptType;
+use crate::application::models::{PromptType, PromptTypes};
 use crate::presentation::tui::components::input_field::InputField;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -15,11 +15,13 @@ pub enum DialogField {
     Template,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CreateTemplate {
     FromClipboard,
     Default,
     Basic,
+    /// A template file from the templates directory
+    User(String),
 }
 
 impl CreateTemplate {
@@ -28,14 +30,16 @@ impl CreateTemplate {
             CreateTemplate::FromClipboard => "From Clipboard",
             CreateTemplate::Default => "Default",
             CreateTemplate::Basic => "Basic Template",
+            CreateTemplate::User(name) => name,
         }
     }
     
-    pub fn description(&self) -> &str {
+    pub fn description(&self) -> String {
         match self {
-            CreateTemplate::FromClipboard => "Create from your current clipboard content",
-            CreateTemplate::Default => "Empty prompt with default frontmatter",
-            CreateTemplate::Basic => "Start with a structured template",
+            CreateTemplate::FromClipboard => "Create from your current clipboard content".to_string(),
+            CreateTemplate::Default => "Empty prompt with default frontmatter".to_string(),
+            CreateTemplate::Basic => "Start with a structured template".to_string(),
+            CreateTemplate::User(name) => format!("Your template from templates/{}.md", name),
         }
     }
 }
@@ -43,6 +47,8 @@ impl CreateTemplate {
 pub struct CreateDialog {
     filename: String,
     template: CreateTemplate,
+    user_templates: Vec<String>,
+    prompt_types: PromptTypes,
     prompt_type: PromptType,
     current_field: DialogField,
 }
@@ -52,6 +58,8 @@ impl Default for CreateDialog {
         Self {
             filename: String::new(),
             template: CreateTemplate::FromClipboard,
+            user_templates: Vec::new(),
+            prompt_types: PromptTypes::default(),
             prompt_type: PromptType::default(),
             current_field: DialogField::Filename,
         }
@@ -63,6 +71,30 @@ impl CreateDialog {
         Self::default()
     }
     
+    /// Offers the given user templates after the built-in ones
+    pub fn with_templates(user_templates: Vec<String>) -> Self {
+        Self {
+            user_templates,
+            ..Self::default()
+        }
+    }
+    
+    /// Offers the given prompt types, starting at "whole" when it is declared
+    pub fn with_prompt_types(mut self, prompt_types: PromptTypes) -> Self {
+        if prompt_types.get(&PromptType::default()).is_none()
+            && let Some(first) = prompt_types.definitions().first() {
+            self.prompt_type = first.id.clone();
+        }
+        self.prompt_types = prompt_types;
+        self
+    }
+    
+    fn template_options(&self) -> Vec<CreateTemplate> {
+        let mut options = vec![CreateTemplate::FromClipboard, CreateTemplate::Default, CreateTemplate::Basic];
+        options.extend(self.user_templates.iter().cloned().map(CreateTemplate::User));
+        options
+    }
+    
     pub fn get_filename(&self) -> &str {
         &self.filename
     }
@@ -72,11 +104,11 @@ impl CreateDialog {
     }
     
     pub fn get_template(&self) -> CreateTemplate {
-        self.template
+        self.template.clone()
     }
     
     pub fn get_prompt_type(&self) -> PromptType {
-        self.prompt_type
+        self.prompt_type.clone()
     }
     
     pub fn current_field(&self) -> DialogField {
@@ -113,47 +145,29 @@ impl CreateDialog {
     
     pub fn next_template(&mut self) {
         if self.current_field == DialogField::Template {
-            self.template = match self.template {
-                CreateTemplate::FromClipboard => CreateTemplate::Default,
-                CreateTemplate::Default => CreateTemplate::Basic,
-                CreateTemplate::Basic => CreateTemplate::FromClipboard,
-            };
+            let options = self.template_options();
+            let index = options.iter().position(|t| *t == self.template).unwrap_or(0);
+            self.template = options[(index + 1) % options.len()].clone();
         }
     }
     
     pub fn previous_template(&mut self) {
         if self.current_field == DialogField::Template {
-            self.template = match self.template {
-                CreateTemplate::FromClipboard => CreateTemplate::Basic,
-                CreateTemplate::Basic => CreateTemplate::Default,
-                CreateTemplate::Default => CreateTemplate::FromClipboard,
-            };
+            let options = self.template_options();
+            let index = options.iter().position(|t| *t == self.template).unwrap_or(0);
+            self.template = options[(index + options.len() - 1) % options.len()].clone();
         }
     }
     
     pub fn next_type(&mut self) {
         if self.current_field == DialogField::Type {
-            self.prompt_type = match self.prompt_type {
-                PromptType::Whole => PromptType::Instruction,
-                PromptType::Instruction => PromptType::Context,
-                PromptType::Context => PromptType::InputIndicator,
-                PromptType::InputIndicator => PromptType::OutputIndicator,
-                PromptType::OutputIndicator => PromptType::Etc,
-                PromptType::Etc => PromptType::Whole,
-            };
+            self.prompt_type = self.prompt_types.next(&self.prompt_type);
         }
     }
     
     pub fn previous_type(&mut self) {
         if self.current_field == DialogField::Type {
-            self.prompt_type = match self.prompt_type {
-                PromptType::Whole => PromptType::Etc,
-                PromptType::Instruction => PromptType::Whole,
-                PromptType::Context => PromptType::Instruction,
-                PromptType::InputIndicator => PromptType::Context,
-                PromptType::OutputIndicator => PromptType::InputIndicator,
-                PromptType::Etc => PromptType::OutputIndicator,
-            };
+            self.prompt_type = self.prompt_types.previous(&self.prompt_type);
         }
     }
     
@@ -264,23 +278,11 @@ impl Widget for &CreateDialog {
             .border_type(ratatui::widgets::BorderType::Rounded)
             .border_style(type_style);
         
-        let type_name = match self.prompt_type {
-            PromptType::Whole => "Whole",
-            PromptType::Instruction => "Instruction",
-            PromptType::Context => "Context",
-            PromptType::InputIndicator => "Input Indicator",
-            PromptType::OutputIndicator => "Output Indicator",
-            PromptType::Etc => "Etc",
-        };
-        
-        let type_description = match self.prompt_type {
-            PromptType::Whole => "Complete prompt (default)",
-            PromptType::Instruction => "Task or instruction for the model",
-            PromptType::Context => "External information or context",
-            PromptType::InputIndicator => "Input or question marker",
-            PromptType::OutputIndicator => "Output format indicator",
-            PromptType::Etc => "Other component type",
-        };
+        let type_name = self.prompt_types.display_name(&self.prompt_type);
+        let type_description = self.prompt_types
+            .get(&self.prompt_type)
+            .map(|definition| definition.description.clone())
+            .unwrap_or_default();
         
         let type_text = vec![
             Line::from(vec![
@@ -313,7 +315,7 @@ impl Widget for &CreateDialog {
             DialogField::Filename => {
                 vec![
                     Line::from(vec![
-                        Span::raw("Enter filename • "),
+                        Span::raw("Enter filename (folder/name for folders) • "),
                         Span::styled("Tab", Style::default().fg(Color::Cyan)),
                         Span::raw(" to switch fields • "),
                         Span::styled("Enter", Style::default().fg(Color::Green)),
@@ -0,0 +1,201 @@
+use crate::application::models::Revision;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Lists the saved revisions of a prompt next to a diff against the current text
+pub struct HistoryDialog {
+    prompt_id: String,
+    prompt_name: String,
+    // Newest first
+    revisions: Vec<Revision>,
+    selected_index: usize,
+    diff: String,
+    diff_scroll: u16,
+}
+
+impl HistoryDialog {
+    pub fn new(prompt_id: String, prompt_name: String, mut revisions: Vec<Revision>) -> Self {
+        revisions.reverse();
+        Self {
+            prompt_id,
+            prompt_name,
+            revisions,
+            selected_index: 0,
+            diff: String::new(),
+            diff_scroll: 0,
+        }
+    }
+
+    pub fn prompt_id(&self) -> &str {
+        &self.prompt_id
+    }
+
+    pub fn prompt_name(&self) -> &str {
+        &self.prompt_name
+    }
+
+    pub fn revisions(&self) -> &[Revision] {
+        &self.revisions
+    }
+
+    pub fn selected_revision(&self) -> Option<u32> {
+        self.revisions.get(self.selected_index).map(|r| r.number)
+    }
+
+    pub fn set_diff(&mut self, diff: String) {
+        self.diff = diff;
+        self.diff_scroll = 0;
+    }
+
+    pub fn diff(&self) -> &str {
+        &self.diff
+    }
+
+    pub fn move_up(&mut self) {
+        if !self.revisions.is_empty() {
+            if self.selected_index == 0 {
+                self.selected_index = self.revisions.len() - 1;
+            } else {
+                self.selected_index -= 1;
+            }
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if !self.revisions.is_empty() {
+            self.selected_index = (self.selected_index + 1) % self.revisions.len();
+        }
+    }
+
+    pub fn scroll_diff_up(&mut self) {
+        self.diff_scroll = self.diff_scroll.saturating_sub(10);
+    }
+
+    pub fn scroll_diff_down(&mut self) {
+        let max_scroll = self.diff.lines().count().saturating_sub(1) as u16;
+        self.diff_scroll = (self.diff_scroll + 10).min(max_scroll);
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let modal_area = Rect {
+            x: area.x + area.width / 10,
+            y: area.y + area.height / 10,
+            width: area.width - area.width / 5,
+            height: area.height - area.height / 5,
+        };
+
+        f.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title(format!("History: {}", self.prompt_name))
+            .title_alignment(Alignment::Center)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner_area = block.inner(modal_area);
+        f.render_widget(block, modal_area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),        // Revisions and diff
+                Constraint::Length(1),     // Help text
+            ])
+            .split(inner_area);
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Length(28),
+                Constraint::Min(0),
+            ])
+            .split(rows[0]);
+
+        self.render_revision_list(f, columns[0]);
+        self.render_diff(f, columns[1]);
+
+        let help_widget = Paragraph::new("↑↓: Select  PgUp/PgDn: Scroll diff  r: Restore  Esc: Close")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(help_widget, rows[1]);
+    }
+
+    fn render_revision_list(&self, f: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title("Revisions");
+
+        if self.revisions.is_empty() {
+            let empty_msg = Paragraph::new("No saved revisions")
+                .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+                .alignment(Alignment::Center)
+                .block(block);
+            f.render_widget(empty_msg, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = self.revisions
+            .iter()
+            .map(|revision| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("#{:<4}", revision.number), Style::default().fg(Color::Cyan)),
+                    Span::raw(revision.created_at.format("%Y-%m-%d %H:%M").to_string()),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            );
+
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.selected_index));
+        f.render_stateful_widget(list, area, &mut list_state);
+    }
+
+    fn render_diff(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = if self.diff.is_empty() && !self.revisions.is_empty() {
+            vec![Line::from(Span::styled(
+                "No changes since this revision",
+                Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC),
+            ))]
+        } else {
+            self.diff.lines().map(diff_line).collect()
+        };
+
+        let diff_widget = Paragraph::new(lines)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title("Diff (revision → current)"))
+            .scroll((self.diff_scroll, 0));
+        f.render_widget(diff_widget, area);
+    }
+}
+
+/// Colors a unified diff line by its prefix
+fn diff_line(line: &str) -> Line<'_> {
+    let style = if line.starts_with("+++") || line.starts_with("---") {
+        Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+    } else if line.starts_with('+') {
+        Style::default().fg(Color::Green)
+    } else if line.starts_with('-') {
+        Style::default().fg(Color::Red)
+    } else if line.starts_with("@@") {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+    Line::from(Span::styled(line, style))
+}
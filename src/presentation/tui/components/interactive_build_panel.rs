@@ -1,94 +1,233 @@
-use crate::application::models::{PromptMetadata, PromptType};
+use crate::application::models::{PromptMetadata, PromptType, PromptTypes, Recipe};
+use crate::utils::output_format::OutputFormat;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum BuildStep {
-    SelectInstruction,
-    SelectContext,
-    SelectInputIndicator,
-    SelectOutputIndicator,
-    SelectEtc,
+    /// Choosing the prompts of one type from the configured build order
+    Select(PromptType),
     AddComment,
     Complete,
 }
 
+// Steps of the built-in types
+impl BuildStep {
+    pub const SELECT_INSTRUCTION: BuildStep = BuildStep::Select(PromptType::INSTRUCTION);
+    pub const SELECT_CONTEXT: BuildStep = BuildStep::Select(PromptType::CONTEXT);
+    pub const SELECT_INPUT_INDICATOR: BuildStep = BuildStep::Select(PromptType::INPUT_INDICATOR);
+    pub const SELECT_OUTPUT_INDICATOR: BuildStep = BuildStep::Select(PromptType::OUTPUT_INDICATOR);
+    pub const SELECT_ETC: BuildStep = BuildStep::Select(PromptType::ETC);
+}
+
 impl BuildStep {
-    fn next(&self) -> Self {
-        match self {
-            BuildStep::SelectInstruction => BuildStep::SelectContext,
-            BuildStep::SelectContext => BuildStep::SelectInputIndicator,
-            BuildStep::SelectInputIndicator => BuildStep::SelectOutputIndicator,
-            BuildStep::SelectOutputIndicator => BuildStep::SelectEtc,
-            BuildStep::SelectEtc => BuildStep::AddComment,
-            BuildStep::AddComment => BuildStep::Complete,
-            BuildStep::Complete => BuildStep::Complete,
-        }
-    }
-    
     fn get_prompt_type(&self) -> Option<PromptType> {
         match self {
-            BuildStep::SelectInstruction => Some(PromptType::Instruction),
-            BuildStep::SelectContext => Some(PromptType::Context),
-            BuildStep::SelectInputIndicator => Some(PromptType::InputIndicator),
-            BuildStep::SelectOutputIndicator => Some(PromptType::OutputIndicator),
-            BuildStep::SelectEtc => Some(PromptType::Etc),
+            BuildStep::Select(prompt_type) => Some(prompt_type.clone()),
             _ => None,
         }
     }
-    
-    fn get_title(&self) -> &str {
-        match self {
-            BuildStep::SelectInstruction => "Select Instruction Prompt",
-            BuildStep::SelectContext => "Select Context Prompt",
-            BuildStep::SelectInputIndicator => "Select Input Indicator",
-            BuildStep::SelectOutputIndicator => "Select Output Indicator",
-            BuildStep::SelectEtc => "Select Additional Prompt",
-            BuildStep::AddComment => "Add Optional Comment",
-            BuildStep::Complete => "Build Complete",
-        }
-    }
-    
-    fn get_description(&self) -> &str {
-        match self {
-            BuildStep::SelectInstruction => "Choose an instruction prompt that defines the AI's role and behavior",
-            BuildStep::SelectContext => "Choose a context prompt that provides background information",
-            BuildStep::SelectInputIndicator => "Choose an input indicator to mark where user input goes",
-            BuildStep::SelectOutputIndicator => "Choose an output indicator to specify expected output format",
-            BuildStep::SelectEtc => "Choose any additional prompts or utilities",
-            BuildStep::AddComment => "Add an optional comment to be included at the end (press Enter to skip)",
-            BuildStep::Complete => "Your prompts have been combined and copied to clipboard!",
-        }
-    }
 }
 
 pub struct InteractiveBuildPanel {
     all_prompts: Vec<PromptMetadata>,
+    prompt_types: PromptTypes,
+    // Types that get a step, in the order their prompts are combined
+    build_order: Vec<PromptType>,
     pub current_step: BuildStep,
-    selected_prompts: HashMap<PromptType, Option<String>>, // None means skip this type
+    selected_prompts: HashMap<PromptType, Vec<PromptMetadata>>, // Kept in selection order; empty means skip this type
     list_state: ListState,
     comment: String,
     comment_cursor: usize,
+    // Final combine order, arranged by hand on the review screen
+    review_order: Vec<PromptMetadata>,
+    review_state: ListState,
+    format: OutputFormat,
+    // Expanded bodies of the candidate prompts by id, used for previews
+    contents: HashMap<String, String>,
+    recipes: Vec<Recipe>,
+    // Name being typed while saving the finished build as a recipe
+    recipe_name_input: Option<String>,
+    recipe_message: Option<String>,
 }
 
 impl InteractiveBuildPanel {
     pub fn new(prompts: Vec<PromptMetadata>) -> Self {
+        Self::with_types(prompts, PromptTypes::default())
+    }
+    
+    /// Builds with one step per type in the build order of `prompt_types`
+    pub fn with_types(prompts: Vec<PromptMetadata>, prompt_types: PromptTypes) -> Self {
         let mut list_state = ListState::default();
         list_state.select(Some(0)); // Select "None" by default
         
+        let build_order = prompt_types.build_order();
+        let current_step = build_order
+            .first()
+            .map(|prompt_type| BuildStep::Select(prompt_type.clone()))
+            .unwrap_or(BuildStep::AddComment);
+        
         Self {
             all_prompts: prompts,
-            current_step: BuildStep::SelectInstruction,
+            prompt_types,
+            build_order,
+            current_step,
             selected_prompts: HashMap::new(),
             list_state,
             comment: String::new(),
             comment_cursor: 0,
+            review_order: Vec::new(),
+            review_state: ListState::default(),
+            format: OutputFormat::default(),
+            contents: HashMap::new(),
+            recipes: Vec::new(),
+            recipe_name_input: None,
+            recipe_message: None,
         }
     }
     
+    fn step_after(&self, step: &BuildStep) -> BuildStep {
+        match step {
+            BuildStep::Select(prompt_type) => {
+                let index = self.build_order.iter().position(|t| t == prompt_type);
+                match index.and_then(|i| self.build_order.get(i + 1)) {
+                    Some(next) => BuildStep::Select(next.clone()),
+                    None => BuildStep::AddComment,
+                }
+            }
+            BuildStep::AddComment | BuildStep::Complete => BuildStep::Complete,
+        }
+    }
+    
+    fn step_before(&self, step: &BuildStep) -> BuildStep {
+        match step {
+            BuildStep::Select(prompt_type) => {
+                let index = self.build_order.iter().position(|t| t == prompt_type).unwrap_or(0);
+                BuildStep::Select(self.build_order[index.saturating_sub(1)].clone())
+            }
+            BuildStep::AddComment => self.build_order
+                .last()
+                .map(|prompt_type| BuildStep::Select(prompt_type.clone()))
+                .unwrap_or(BuildStep::AddComment),
+            BuildStep::Complete => BuildStep::AddComment,
+        }
+    }
+    
+    fn step_title(&self) -> String {
+        match &self.current_step {
+            BuildStep::Select(prompt_type) => match prompt_type.as_str() {
+                "instruction" => "Select Instruction Prompt".to_string(),
+                "context" => "Select Context Prompt".to_string(),
+                "input_indicator" => "Select Input Indicator".to_string(),
+                "output_indicator" => "Select Output Indicator".to_string(),
+                "etc" => "Select Additional Prompt".to_string(),
+                _ => format!("Select {} Prompt", self.prompt_types.display_name(prompt_type)),
+            },
+            BuildStep::AddComment => "Add Optional Comment".to_string(),
+            BuildStep::Complete => "Review Build".to_string(),
+        }
+    }
+    
+    fn step_description(&self) -> String {
+        match &self.current_step {
+            BuildStep::Select(prompt_type) => match prompt_type.as_str() {
+                "instruction" => "Choose an instruction prompt that defines the AI's role and behavior".to_string(),
+                "context" => "Choose a context prompt that provides background information".to_string(),
+                "input_indicator" => "Choose an input indicator to mark where user input goes".to_string(),
+                "output_indicator" => "Choose an output indicator to specify expected output format".to_string(),
+                "etc" => "Choose any additional prompts or utilities".to_string(),
+                _ => match self.prompt_types.get(prompt_type).map(|d| d.description.as_str()) {
+                    Some(description) if !description.is_empty() => description.to_string(),
+                    _ => format!("Choose any {} prompts to include", self.prompt_types.display_name(prompt_type)),
+                },
+            },
+            BuildStep::AddComment => "Add an optional comment to be included at the end (press Enter to skip)".to_string(),
+            BuildStep::Complete => "Reorder or remove pieces, then press Enter to copy the combined prompt".to_string(),
+        }
+    }
+    
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+    
+    pub fn set_format(&mut self, format: OutputFormat) {
+        self.format = format;
+    }
+    
+    pub fn cycle_format(&mut self) {
+        self.format = self.format.next();
+    }
+    
+    pub fn set_contents(&mut self, contents: HashMap<String, String>) {
+        self.contents = contents;
+    }
+    
+    /// Expanded body of the highlighted candidate
+    pub fn get_selected_prompt_content(&self) -> Option<&str> {
+        self.get_selected_prompt_for_preview()
+            .and_then(|prompt| self.contents.get(prompt.id()))
+            .map(String::as_str)
+    }
+    
+    /// The combined output of everything chosen so far, in the selected format
+    pub fn assembled_preview(&self) -> String {
+        let sections: Vec<(PromptType, String)> = self.selected_in_order()
+            .into_iter()
+            .map(|(prompt_type, prompt)| {
+                let content = self.contents.get(prompt.id()).cloned().unwrap_or_default();
+                (prompt_type, content)
+            })
+            .collect();
+        
+        self.format.combine(&sections, &self.prompt_types, &self.comment)
+    }
+    
+    /// Saved recipes offered for one-key reuse, in the order of their number keys
+    pub fn set_recipes(&mut self, recipes: Vec<Recipe>) {
+        self.recipes = recipes;
+    }
+    
+    pub fn recipes(&self) -> &[Recipe] {
+        &self.recipes
+    }
+    
+    pub fn start_saving_recipe(&mut self) {
+        self.recipe_name_input = Some(String::new());
+        self.recipe_message = None;
+    }
+    
+    pub fn cancel_saving_recipe(&mut self) {
+        self.recipe_name_input = None;
+    }
+    
+    pub fn is_naming_recipe(&self) -> bool {
+        self.recipe_name_input.is_some()
+    }
+    
+    pub fn add_recipe_name_char(&mut self, c: char) {
+        if let Some(name) = self.recipe_name_input.as_mut() {
+            name.push(c);
+        }
+    }
+    
+    pub fn delete_recipe_name_char(&mut self) {
+        if let Some(name) = self.recipe_name_input.as_mut() {
+            name.pop();
+        }
+    }
+    
+    pub fn recipe_name(&self) -> Option<&str> {
+        self.recipe_name_input.as_deref()
+    }
+    
+    /// Shows the outcome of saving a recipe on the completion screen
+    pub fn set_recipe_message(&mut self, message: String) {
+        self.recipe_message = Some(message);
+    }
+    
     pub fn get_current_prompts(&self) -> Vec<(String, &PromptMetadata)> {
         let mut prompts = vec![("None".to_string(), None)];
         
@@ -145,28 +284,52 @@ impl InteractiveBuildPanel {
         self.list_state.select(Some(previous));
     }
     
-    pub fn select_current(&mut self) {
-        if let Some(selected_idx) = self.list_state.selected() {
-            if let Some(prompt_type) = self.current_step.get_prompt_type() {
-                if selected_idx == 0 {
-                    // User selected "None"
-                    self.selected_prompts.insert(prompt_type, None);
+    /// Toggles the highlighted prompt in or out of the current step's selection.
+    /// Toggling "None" clears the selection for this type.
+    pub fn toggle_current(&mut self) {
+        let Some(prompt_type) = self.current_step.get_prompt_type() else {
+            return;
+        };
+        let highlighted = self.get_selected_prompt_for_preview().cloned();
+        let selection = self.selected_prompts.entry(prompt_type).or_default();
+        
+        match highlighted {
+            None => selection.clear(),
+            Some(prompt) => {
+                if let Some(pos) = selection.iter().position(|p| p.id() == prompt.id()) {
+                    selection.remove(pos);
                 } else {
-                    // Get the actual prompt
-                    let prompts: Vec<_> = self.all_prompts
-                        .iter()
-                        .filter(|p| p.prompt_type == prompt_type)
-                        .collect();
-                    
-                    if let Some(prompt) = prompts.get(selected_idx - 1) {
-                        self.selected_prompts.insert(prompt_type, Some(prompt.name.clone()));
-                    }
+                    selection.push(prompt);
                 }
             }
         }
+    }
+    
+    /// Position (1-based) of a prompt within the current step's selection
+    pub fn selection_position(&self, prompt: &PromptMetadata) -> Option<usize> {
+        let prompt_type = self.current_step.get_prompt_type()?;
+        self.selected_prompts
+            .get(&prompt_type)?
+            .iter()
+            .position(|p| p.id() == prompt.id())
+            .map(|pos| pos + 1)
+    }
+    
+    /// Advances to the next step. Without toggled prompts the highlighted one is used,
+    /// so a single prompt can still be picked with Enter alone.
+    pub fn select_current(&mut self) {
+        if let Some(prompt_type) = self.current_step.get_prompt_type() {
+            let has_selection = self.selected_prompts
+                .get(&prompt_type)
+                .is_some_and(|selection| !selection.is_empty());
+            if !has_selection
+                && let Some(prompt) = self.get_selected_prompt_for_preview().cloned() {
+                self.selected_prompts.insert(prompt_type, vec![prompt]);
+            }
+        }
         
         // Move to next step
-        self.current_step = self.current_step.next();
+        self.current_step = self.step_after(&self.current_step);
         self.list_state.select(Some(0)); // Reset selection for next step
     }
     
@@ -207,29 +370,124 @@ impl InteractiveBuildPanel {
     
     pub fn finish_comment(&mut self) {
         self.current_step = BuildStep::Complete;
+        self.sync_review_order();
     }
     
-    pub fn is_complete(&self) -> bool {
-        self.current_step == BuildStep::Complete
+    /// Returns to the previous step, keeping every choice made so far
+    pub fn go_back(&mut self) {
+        self.recipe_name_input = None;
+        self.current_step = self.step_before(&self.current_step);
+        self.list_state.select(Some(0));
     }
     
-    pub fn get_selected_prompt_names(&self) -> Vec<(PromptType, String)> {
-        let mut result = Vec::new();
-        let type_order = [
-            PromptType::Instruction,
-            PromptType::Context,
-            PromptType::InputIndicator,
-            PromptType::OutputIndicator,
-            PromptType::Etc,
-        ];
+    // Keeps the hand-arranged order for pieces that are still selected and
+    // appends newly selected ones in type order
+    fn sync_review_order(&mut self) {
+        let selected: Vec<PromptMetadata> = self.selected_by_type()
+            .map(|(_, prompt)| prompt.clone())
+            .collect();
         
-        for prompt_type in &type_order {
-            if let Some(Some(name)) = self.selected_prompts.get(prompt_type) {
-                result.push((*prompt_type, name.clone()));
+        self.review_order.retain(|prompt| selected.iter().any(|p| p.id() == prompt.id()));
+        for prompt in selected {
+            if !self.review_order.iter().any(|p| p.id() == prompt.id()) {
+                self.review_order.push(prompt);
             }
         }
         
-        result
+        let selected_idx = self.review_state.selected().unwrap_or(0);
+        self.review_state.select(if self.review_order.is_empty() {
+            None
+        } else {
+            Some(selected_idx.min(self.review_order.len() - 1))
+        });
+    }
+    
+    pub fn review_order(&self) -> &[PromptMetadata] {
+        &self.review_order
+    }
+    
+    pub fn review_selected(&self) -> Option<usize> {
+        self.review_state.selected()
+    }
+    
+    pub fn review_next(&mut self) {
+        if !self.review_order.is_empty() {
+            let selected = self.review_state.selected().unwrap_or(0);
+            self.review_state.select(Some((selected + 1) % self.review_order.len()));
+        }
+    }
+    
+    pub fn review_previous(&mut self) {
+        if !self.review_order.is_empty() {
+            let selected = self.review_state.selected().unwrap_or(0);
+            self.review_state.select(Some(if selected == 0 { self.review_order.len() - 1 } else { selected - 1 }));
+        }
+    }
+    
+    pub fn move_review_item_up(&mut self) {
+        if let Some(selected) = self.review_state.selected()
+            && selected > 0 {
+            self.review_order.swap(selected, selected - 1);
+            self.review_state.select(Some(selected - 1));
+        }
+    }
+    
+    pub fn move_review_item_down(&mut self) {
+        if let Some(selected) = self.review_state.selected()
+            && selected + 1 < self.review_order.len() {
+            self.review_order.swap(selected, selected + 1);
+            self.review_state.select(Some(selected + 1));
+        }
+    }
+    
+    /// Drops the highlighted piece from the build, also unselecting it in its step
+    pub fn remove_review_item(&mut self) {
+        if let Some(selected) = self.review_state.selected()
+            && selected < self.review_order.len() {
+            let removed = self.review_order.remove(selected);
+            if let Some(selection) = self.selected_prompts.get_mut(&removed.prompt_type) {
+                selection.retain(|p| p.id() != removed.id());
+            }
+            self.sync_review_order();
+        }
+    }
+    
+    pub fn is_complete(&self) -> bool {
+        self.current_step == BuildStep::Complete
+    }
+    
+    fn selected_by_type(&self) -> impl Iterator<Item = (PromptType, &PromptMetadata)> {
+        self.build_order.iter().flat_map(move |prompt_type| {
+            self.selected_prompts
+                .get(prompt_type)
+                .into_iter()
+                .flatten()
+                .map(move |prompt| (prompt_type.clone(), prompt))
+        })
+    }
+    
+    // The review order once the build reached the review screen, type order before that
+    fn selected_in_order(&self) -> Vec<(PromptType, &PromptMetadata)> {
+        if self.current_step == BuildStep::Complete {
+            self.review_order.iter().map(|prompt| (prompt.prompt_type.clone(), prompt)).collect()
+        } else {
+            self.selected_by_type().collect()
+        }
+    }
+    
+    pub fn get_selected_prompt_names(&self) -> Vec<(PromptType, String)> {
+        self.selected_in_order()
+            .into_iter()
+            .map(|(prompt_type, prompt)| (prompt_type, prompt.name.clone()))
+            .collect()
+    }
+    
+    /// Ids of the selected prompts in combine order, as stored in recipes
+    pub fn get_selected_prompt_ids(&self) -> Vec<String> {
+        self.selected_in_order()
+            .into_iter()
+            .map(|(_, prompt)| prompt.id().to_string())
+            .collect()
     }
     
     pub fn get_comment(&self) -> &str {
@@ -269,7 +527,7 @@ impl InteractiveBuildPanel {
         self.render_header(frame, chunks[0]);
         
         // Main content - different based on step
-        match self.current_step {
+        match &self.current_step {
             BuildStep::AddComment => self.render_comment_input(frame, chunks[1]),
             BuildStep::Complete => self.render_complete(frame, chunks[1]),
             _ => self.render_prompt_selection(frame, chunks[1]),
@@ -282,11 +540,11 @@ impl InteractiveBuildPanel {
     fn render_header(&self, frame: &mut Frame, area: Rect) {
         let header_text = vec![
             Line::from(Span::styled(
-                self.current_step.get_title(),
+                self.step_title(),
                 Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
-            Line::from(self.current_step.get_description()),
+            Line::from(self.step_description()),
         ];
         
         let header = Paragraph::new(header_text)
@@ -307,16 +565,22 @@ impl InteractiveBuildPanel {
             ])
             .split(area);
         
-        // Render options list
+        // Render options list, numbering toggled prompts in selection order
         let options = self.get_current_options();
+        let prompts = self.get_current_prompts();
         let items: Vec<ListItem> = options
             .iter()
             .enumerate()
             .map(|(idx, option)| {
                 if idx == 0 {
-                    ListItem::new(Span::styled(option, Style::default().fg(Color::DarkGray)))
-                } else {
-                    ListItem::new(option.as_str())
+                    return ListItem::new(Span::styled(option, Style::default().fg(Color::DarkGray)));
+                }
+                match prompts.get(idx - 1).and_then(|(_, prompt)| self.selection_position(prompt)) {
+                    Some(position) => ListItem::new(Line::from(vec![
+                        Span::styled(format!("[{}] ", position), Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+                        Span::raw(option.as_str()),
+                    ])),
+                    None => ListItem::new(format!("[ ] {}", option)),
                 }
             })
             .collect();
@@ -328,10 +592,51 @@ impl InteractiveBuildPanel {
                 .title(" Available Options "))
             .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
         
-        frame.render_stateful_widget(list, chunks[0], &mut self.list_state);
+        let list_area = if self.recipes.is_empty() {
+            chunks[0]
+        } else {
+            let list_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(3),
+                    Constraint::Length(self.recipes.len().min(9) as u16 + 2),
+                ])
+                .split(chunks[0]);
+            self.render_recipes(frame, list_chunks[1]);
+            list_chunks[0]
+        };
+        
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
         
-        // Render preview (without content for now - will be provided from app)
-        self.render_preview(frame, chunks[1], None);
+        let preview_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
+            ])
+            .split(chunks[1]);
+        
+        let prompt_content = self.get_selected_prompt_content().map(str::to_string);
+        self.render_preview(frame, preview_chunks[0], prompt_content);
+        self.render_assembled(frame, preview_chunks[1]);
+    }
+    
+    fn render_assembled(&self, frame: &mut Frame, area: Rect) {
+        let assembled = self.assembled_preview();
+        let content = if assembled.is_empty() {
+            Text::styled("Nothing selected yet", Style::default().fg(Color::DarkGray))
+        } else {
+            Text::raw(assembled)
+        };
+        
+        let preview = Paragraph::new(content)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title(format!(" Assembled So Far ({}) ", self.format)))
+            .wrap(Wrap { trim: false });
+        
+        frame.render_widget(preview, area);
     }
     
     pub fn render_preview(&self, frame: &mut Frame, area: Rect, prompt_content: Option<String>) {
@@ -340,9 +645,9 @@ impl InteractiveBuildPanel {
                 content
             } else {
                 format!(
-                    "Name: {}\nType: {}\nTags: {}\n\n[Loading content...]",
+                    "Name: {}\nType: {}\nTags: {}\n\n[Content could not be loaded]",
                     prompt.name,
-                    prompt.prompt_type,
+                    self.prompt_types.display_name(&prompt.prompt_type),
                     prompt.tags.join(", ")
                 )
             }
@@ -360,7 +665,38 @@ impl InteractiveBuildPanel {
         frame.render_widget(preview, area);
     }
     
+    fn render_recipes(&self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self.recipes
+            .iter()
+            .take(9)
+            .enumerate()
+            .map(|(i, recipe)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{} ", i + 1), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    Span::raw(recipe.name.clone()),
+                    Span::styled(format!(" ({} prompts)", recipe.prompts.len()), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title(" Saved Recipes "));
+        
+        frame.render_widget(list, area);
+    }
+    
     fn render_comment_input(&self, frame: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Comment input
+                Constraint::Min(3),     // Assembled output
+            ])
+            .split(area);
+        
         let input_text = format!("{}_", self.comment);
         
         let input = Paragraph::new(input_text)
@@ -370,51 +706,87 @@ impl InteractiveBuildPanel {
                 .title(" Optional Comment (press Enter to skip or finish) "))
             .style(Style::default().fg(Color::Yellow));
         
-        frame.render_widget(input, area);
+        frame.render_widget(input, chunks[0]);
+        self.render_assembled(frame, chunks[1]);
     }
     
-    fn render_complete(&self, frame: &mut Frame, area: Rect) {
-        let selected_count = self.selected_prompts
-            .values()
-            .filter(|v| v.is_some())
-            .count();
+    fn render_complete(&mut self, frame: &mut Frame, area: Rect) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Percentage(60),
+            ])
+            .split(area);
         
-        let complete_text = vec![
-            Line::from(""),
-            Line::from(Span::styled(
-                "✓ Build Complete!",
-                Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from(format!("Selected {} prompts", selected_count)),
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Ordered pieces
+                Constraint::Length(6),  // Format, comment and recipe status
+            ])
+            .split(columns[0]);
+        
+        self.render_assembled(frame, columns[1]);
+        
+        let items: Vec<ListItem> = self.review_order
+            .iter()
+            .enumerate()
+            .map(|(i, prompt)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{}. ", i + 1), Style::default().fg(Color::Yellow)),
+                    Span::raw(prompt.name.clone()),
+                    Span::styled(format!("  ({})", self.prompt_types.display_name(&prompt.prompt_type)), Style::default().fg(Color::DarkGray)),
+                ]))
+            })
+            .collect();
+        
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title(format!(" Combine Order ({} prompts) ", self.review_order.len())))
+            .highlight_style(Style::default().bg(Color::DarkGray).fg(Color::White));
+        
+        frame.render_stateful_widget(list, chunks[0], &mut self.review_state);
+        
+        let mut status_text = vec![
+            Line::from(vec![
+                Span::raw("Format: "),
+                Span::styled(self.format.to_string(), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            ]),
             if !self.comment.is_empty() {
-                Line::from(format!("Added comment: {}", self.comment))
+                Line::from(format!("Comment: {}", self.comment))
             } else {
                 Line::from("No comment added")
             },
-            Line::from(""),
-            Line::from("Your combined prompt has been copied to the clipboard."),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Press Enter or Esc to exit build mode",
-                Style::default().fg(Color::DarkGray),
-            )),
         ];
         
-        let complete = Paragraph::new(complete_text)
+        if let Some(name) = &self.recipe_name_input {
+            status_text.push(Line::from(vec![
+                Span::raw("Recipe name: "),
+                Span::styled(format!("{}_", name), Style::default().fg(Color::Yellow)),
+            ]));
+        } else if let Some(message) = &self.recipe_message {
+            status_text.push(Line::from(Span::styled(message.clone(), Style::default().fg(Color::Cyan))));
+        }
+        
+        let status = Paragraph::new(status_text)
             .block(Block::default()
                 .borders(Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded))
-            .alignment(Alignment::Center);
+            .wrap(Wrap { trim: true });
         
-        frame.render_widget(complete, area);
+        frame.render_widget(status, chunks[1]);
     }
     
     fn render_status_bar(&self, frame: &mut Frame, area: Rect) {
-        let keys = match self.current_step {
-            BuildStep::AddComment => "Type comment | Enter: Finish | Esc: Skip comment",
-            BuildStep::Complete => "Enter/Esc: Exit build mode",
-            _ => "↑↓: Navigate | Enter: Select | Esc: Cancel",
+        let keys = match &self.current_step {
+            BuildStep::AddComment => "Type comment | Enter: Finish | Esc: Skip comment | Shift+Tab: Back",
+            BuildStep::Complete if self.is_naming_recipe() => "Type recipe name | Enter: Save | Esc: Cancel",
+            BuildStep::Complete => "↑↓: Navigate | K/J: Move up/down | d: Remove | f: Format | Enter: Copy & exit | s: Save as recipe | Shift+Tab: Back | Esc: Cancel",
+            _ if !self.recipes.is_empty() => "↑↓: Navigate | Space: Toggle | Enter: Next step | Shift+Tab: Back | 1-9: Run saved recipe | Esc: Cancel",
+            _ => "↑↓: Navigate | Space: Toggle | Enter: Next step | Shift+Tab: Back | Esc: Cancel",
         };
         
         let status = Paragraph::new(keys)
@@ -10,9 +10,12 @@ pub mod init_dialog;
 pub mod type_prompts_dialog;
 pub mod input_field;
 pub mod editing_dialog;
+pub mod history_dialog;
+pub mod trash_dialog;
+pub mod variable_dialog;
 
 pub use prompt_list::PromptList;
-pub use tag_dialog::{TagManagementDialog, TagInputMode};
+pub use tag_dialog::{TagManagementDialog, TagInputMode, LibraryTagAction};
 pub use tag_filter::TagFilterDialog;
 pub use create_dialog::{CreateDialog, DialogField, CreateTemplate};
 pub use build_panel::BuildPanel;
@@ -20,3 +23,6 @@ pub use interactive_build_panel::{InteractiveBuildPanel, BuildStep};
 pub use init_dialog::InitDialog;
 pub use type_prompts_dialog::TypePromptsDialog;
 pub use editing_dialog::EditingDialog;
+pub use history_dialog::HistoryDialog;
+pub use trash_dialog::TrashDialog;
+pub use variable_dialog::VariableDialog;
@@ -57,8 +57,11 @@ impl PromptList {
         }
     }
     
+    /// Selects a prompt by its path identifier (e.g. `backend/review`), falling back to its name
     pub fn find_and_select(&mut self, name: &str) -> bool {
-        if let Some(index) = self.prompts.iter().position(|p| p.name == name) {
+        let position = self.prompts.iter().position(|p| p.id() == name)
+            .or_else(|| self.prompts.iter().position(|p| p.name == name));
+        if let Some(index) = position {
             self.selected = index;
             true
         } else {
@@ -68,7 +71,7 @@ impl PromptList {
     
     pub fn update_prompts(&mut self, new_prompts: Vec<PromptMetadata>) {
         // Save current selection name
-        let current_name = self.get_selected().map(|p| p.name.clone());
+        let current_name = self.get_selected().map(|p| p.id().to_string());
         
         // Update prompts
         self.prompts = new_prompts;
@@ -1,3 +1,5 @@
+use crate::utils::fuzzy;
+
 #[derive(Debug, PartialEq)]
 pub struct Segment {
     pub text: String,
@@ -73,6 +75,22 @@ impl HighlightedText {
         
         HighlightedResult { segments }
     }
+
+    /// Marks the characters of `text` that the fuzzy search matched for `query`
+    pub fn highlight_fuzzy(&self, text: &str, query: &str) -> HighlightedResult {
+        let positions = fuzzy::match_positions(text, query);
+        let mut segments: Vec<Segment> = Vec::new();
+        
+        for (index, c) in text.chars().enumerate() {
+            let is_match = positions.binary_search(&index).is_ok();
+            match segments.last_mut() {
+                Some(last) if last.is_match == is_match => last.text.push(c),
+                _ => segments.push(Segment { text: c.to_string(), is_match }),
+            }
+        }
+        
+        HighlightedResult { segments }
+    }
 }
 
 pub struct HighlightedResult {
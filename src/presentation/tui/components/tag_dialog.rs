@@ -3,14 +3,21 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
+use crate::application::models::{TagChange, TagOperation};
 
 pub struct TagManagementDialog {
     current_tags: Vec<String>,
     input_mode: TagInputMode,
     input_buffer: String,
     selected_tag_index: usize,
+    // Tag picked for a library-wide change, and the change waiting for confirmation
+    library_tag: Option<String>,
+    pending_operation: Option<TagOperation>,
+    preview: Vec<TagChange>,
+    // Number of marked prompts the dialog adds and removes tags on, if more than the selected one
+    marked_count: Option<usize>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -18,6 +25,20 @@ pub enum TagInputMode {
     ViewTags,
     AddingTag,
     RemovingTag,
+    /// Picking one of the prompt's tags to change on every prompt
+    ChoosingLibraryTag(LibraryTagAction),
+    /// Typing the tag to rename to or merge into
+    EnteringTarget(LibraryTagAction),
+    /// Reviewing the prompts a library-wide change will touch
+    ConfirmingLibraryChange,
+}
+
+/// Tag changes that apply to every prompt rather than the selected one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LibraryTagAction {
+    Rename,
+    Merge,
+    Delete,
 }
 
 impl TagManagementDialog {
@@ -27,8 +48,22 @@ impl TagManagementDialog {
             input_mode: TagInputMode::ViewTags,
             input_buffer: String::new(),
             selected_tag_index: 0,
+            library_tag: None,
+            pending_operation: None,
+            preview: Vec::new(),
+            marked_count: None,
         }
     }
+    
+    /// Shows the combined tags of the marked prompts, so added and removed tags apply to all of them
+    pub fn with_marked_count(mut self, count: usize) -> Self {
+        self.marked_count = Some(count);
+        self
+    }
+    
+    pub fn marked_count(&self) -> Option<usize> {
+        self.marked_count
+    }
 
     pub fn render(&self, f: &mut Frame, area: Rect) {
         // Create a centered modal
@@ -68,30 +103,46 @@ impl TagManagementDialog {
             .split(inner_area);
         
         // Render instructions
-        let instructions = match self.input_mode {
-            TagInputMode::ViewTags => "Current tags for this prompt:",
-            TagInputMode::AddingTag => "Type new tag and press Enter:",
-            TagInputMode::RemovingTag => "Select tag to remove and press Enter:",
+        let library_tag = self.library_tag.as_deref().unwrap_or_default();
+        let instructions = match &self.input_mode {
+            TagInputMode::ViewTags => match self.marked_count {
+                Some(count) => format!("Tags of {} marked prompts:", count),
+                None => "Current tags for this prompt:".to_string(),
+            },
+            TagInputMode::AddingTag => "Type new tag and press Enter:".to_string(),
+            TagInputMode::RemovingTag => "Select tag to remove and press Enter:".to_string(),
+            TagInputMode::ChoosingLibraryTag(action) => format!("Select tag to {} in all prompts:", action.verb()),
+            TagInputMode::EnteringTarget(LibraryTagAction::Merge) => format!("Merge '{}' into tag:", library_tag),
+            TagInputMode::EnteringTarget(_) => format!("Rename '{}' to:", library_tag),
+            TagInputMode::ConfirmingLibraryChange => format!("{} prompt(s) will change:", self.preview.len()),
         };
         let instructions_widget = Paragraph::new(instructions)
             .style(Style::default().fg(Color::Yellow));
         f.render_widget(instructions_widget, chunks[0]);
         
-        // Render tag list
-        self.render_tag_list(f, chunks[1]);
+        // Render tag list, or the files a library-wide change touches
+        if self.input_mode == TagInputMode::ConfirmingLibraryChange {
+            self.render_preview(f, chunks[1]);
+        } else {
+            self.render_tag_list(f, chunks[1]);
+        }
         
         // Render input area
         self.render_input_area(f, chunks[2]);
         
         // Render help text
         let help_text = match self.input_mode {
-            TagInputMode::ViewTags => "a: Add tag  r: Remove tag  Esc: Close",
+            TagInputMode::ViewTags => "a: Add  r: Remove  R/M/D: Rename/Merge/Delete in all prompts  Esc: Close",
             TagInputMode::AddingTag => "Enter: Add  Esc: Cancel",
             TagInputMode::RemovingTag => "↑↓: Select  Enter: Remove  Esc: Cancel",
+            TagInputMode::ChoosingLibraryTag(_) => "↑↓: Select  Enter: Choose  Esc: Cancel",
+            TagInputMode::EnteringTarget(_) => "Enter: Preview  Esc: Cancel",
+            TagInputMode::ConfirmingLibraryChange => "Enter/y: Apply  Esc/n: Cancel",
         };
         let help_widget = Paragraph::new(help_text)
             .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
         f.render_widget(help_widget, chunks[3]);
     }
     
@@ -106,10 +157,14 @@ impl TagManagementDialog {
                 .iter()
                 .enumerate()
                 .map(|(i, tag)| {
-                    let style = if self.input_mode == TagInputMode::RemovingTag && i == self.selected_tag_index {
-                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-                    } else {
-                        Style::default()
+                    let style = match self.input_mode {
+                        TagInputMode::RemovingTag if i == self.selected_tag_index => {
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                        }
+                        TagInputMode::ChoosingLibraryTag(_) if i == self.selected_tag_index => {
+                            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                        }
+                        _ => Style::default(),
                     };
                     ListItem::new(Line::from(vec![
                         Span::raw("• "),
@@ -131,7 +186,7 @@ impl TagManagementDialog {
             
             // Create list state for scrolling
             let mut list_state = ListState::default();
-            if self.input_mode == TagInputMode::RemovingTag {
+            if self.is_choosing_tag() {
                 list_state.select(Some(self.selected_tag_index));
             }
                 
@@ -139,8 +194,31 @@ impl TagManagementDialog {
         }
     }
     
+    fn render_preview(&self, f: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self.preview
+            .iter()
+            .map(|change| {
+                ListItem::new(vec![
+                    Line::from(Span::styled(change.prompt.file_path.clone(), Style::default().add_modifier(Modifier::BOLD))),
+                    Line::from(vec![
+                        Span::styled(format!("  [{}]", change.before.join(", ")), Style::default().fg(Color::Red)),
+                        Span::raw(" → "),
+                        Span::styled(format!("[{}]", change.after.join(", ")), Style::default().fg(Color::Green)),
+                    ]),
+                ])
+            })
+            .collect();
+        
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title("Preview"));
+        f.render_widget(list, area);
+    }
+    
     fn render_input_area(&self, f: &mut Frame, area: Rect) {
-        if self.input_mode == TagInputMode::AddingTag {
+        if matches!(self.input_mode, TagInputMode::AddingTag | TagInputMode::EnteringTarget(_)) {
             let input_block = Block::default()
                 .borders(Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded)
@@ -167,19 +245,76 @@ impl TagManagementDialog {
         }
     }
     
+    pub fn start_library_action(&mut self, action: LibraryTagAction) {
+        if !self.current_tags.is_empty() {
+            self.input_mode = TagInputMode::ChoosingLibraryTag(action);
+            self.selected_tag_index = 0;
+        }
+    }
+    
+    /// Takes the highlighted tag for the library-wide action. Deleting needs nothing else, so
+    /// its operation is returned right away; renaming and merging go on to ask for the target.
+    pub fn choose_library_tag(&mut self) -> Option<TagOperation> {
+        let TagInputMode::ChoosingLibraryTag(action) = self.input_mode else {
+            return None;
+        };
+        let tag = self.current_tags.get(self.selected_tag_index)?.clone();
+        
+        self.library_tag = Some(tag.clone());
+        if action == LibraryTagAction::Delete {
+            return Some(TagOperation::Delete(tag));
+        }
+        
+        // Renaming usually changes part of the tag, so start from the current one
+        self.input_buffer = if action == LibraryTagAction::Rename { tag } else { String::new() };
+        self.input_mode = TagInputMode::EnteringTarget(action);
+        None
+    }
+    
+    /// The rename or merge described by the chosen tag and the typed target
+    pub fn get_library_operation(&self) -> Option<TagOperation> {
+        let TagInputMode::EnteringTarget(action) = self.input_mode else {
+            return None;
+        };
+        let from = self.library_tag.clone()?;
+        let target = self.input_buffer.trim();
+        if target.is_empty() {
+            return None;
+        }
+        
+        Some(match action {
+            LibraryTagAction::Merge => TagOperation::Merge { sources: vec![from], into: target.to_string() },
+            _ => TagOperation::Rename { from, to: target.to_string() },
+        })
+    }
+    
+    /// Shows the prompts the operation changes and waits for confirmation
+    pub fn show_preview(&mut self, operation: TagOperation, changes: Vec<TagChange>) {
+        self.pending_operation = Some(operation);
+        self.preview = changes;
+        self.input_mode = TagInputMode::ConfirmingLibraryChange;
+    }
+    
+    pub fn pending_operation(&self) -> Option<&TagOperation> {
+        self.pending_operation.as_ref()
+    }
+    
     pub fn cancel_input(&mut self) {
         self.input_mode = TagInputMode::ViewTags;
         self.input_buffer.clear();
+        self.library_tag = None;
+        self.pending_operation = None;
+        self.preview.clear();
     }
     
     pub fn add_char(&mut self, c: char) {
-        if self.input_mode == TagInputMode::AddingTag {
+        if matches!(self.input_mode, TagInputMode::AddingTag | TagInputMode::EnteringTarget(_)) {
             self.input_buffer.push(c);
         }
     }
     
     pub fn delete_char(&mut self) {
-        if self.input_mode == TagInputMode::AddingTag && !self.input_buffer.is_empty() {
+        if matches!(self.input_mode, TagInputMode::AddingTag | TagInputMode::EnteringTarget(_)) {
             self.input_buffer.pop();
         }
     }
@@ -201,7 +336,7 @@ impl TagManagementDialog {
     }
     
     pub fn move_selection_up(&mut self) {
-        if self.input_mode == TagInputMode::RemovingTag && !self.current_tags.is_empty() {
+        if self.is_choosing_tag() && !self.current_tags.is_empty() {
             if self.selected_tag_index == 0 {
                 self.selected_tag_index = self.current_tags.len() - 1;
             } else {
@@ -211,13 +346,18 @@ impl TagManagementDialog {
     }
     
     pub fn move_selection_down(&mut self) {
-        if self.input_mode == TagInputMode::RemovingTag && !self.current_tags.is_empty() {
+        if self.is_choosing_tag() && !self.current_tags.is_empty() {
             self.selected_tag_index = (self.selected_tag_index + 1) % self.current_tags.len();
         }
     }
     
+    // Whether ↑↓ move through the prompt's tags
+    fn is_choosing_tag(&self) -> bool {
+        matches!(self.input_mode, TagInputMode::RemovingTag | TagInputMode::ChoosingLibraryTag(_))
+    }
+    
     pub fn is_in_input_mode(&self) -> bool {
-        matches!(self.input_mode, TagInputMode::AddingTag | TagInputMode::RemovingTag)
+        self.input_mode != TagInputMode::ViewTags
     }
     
     pub fn input_mode(&self) -> &TagInputMode {
@@ -225,6 +365,16 @@ impl TagManagementDialog {
     }
 }
 
+impl LibraryTagAction {
+    fn verb(&self) -> &'static str {
+        match self {
+            LibraryTagAction::Rename => "rename",
+            LibraryTagAction::Merge => "merge",
+            LibraryTagAction::Delete => "delete",
+        }
+    }
+}
+
 // Helper function to create centered rect with fixed size
 fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
     // Ensure we don't exceed the available area
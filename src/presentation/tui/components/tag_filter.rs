@@ -5,13 +5,27 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use crate::application::models::TagMatch;
 use crate::presentation::tui::components::input_field::InputField;
+use crate::utils::tags::{self, TAG_SEPARATOR};
+
+// A tag in the tree, listed after its parent
+struct TagRow {
+    path: String,
+    depth: usize,
+    has_children: bool,
+}
 
 pub struct TagFilterDialog {
-    available_tags: Vec<String>,
+    // Tags and their implied parents in tree order
+    rows: Vec<TagRow>,
+    counts: HashMap<String, usize>,
+    collapsed: HashSet<String>,
     selected_index: usize,
     selected_tags: HashSet<String>,
+    excluded_tags: HashSet<String>,
+    tag_match: TagMatch,
     search_query: String,
     cursor_position: usize,
     pub is_searching: bool,
@@ -20,29 +34,85 @@ pub struct TagFilterDialog {
 impl TagFilterDialog {
     pub fn new(available_tags: Vec<String>, active_filters: HashSet<String>) -> Self {
         Self {
-            available_tags,
+            rows: build_rows(&available_tags),
+            counts: HashMap::new(),
+            collapsed: HashSet::new(),
             selected_index: 0,
             selected_tags: active_filters,
+            excluded_tags: HashSet::new(),
+            tag_match: TagMatch::Any,
             search_query: String::new(),
             cursor_position: 0,
             is_searching: true,
         }
     }
     
-    /// Get filtered tags based on search query
-    pub fn get_filtered_tags(&self) -> Vec<String> {
+    /// Shows how many prompts are under each tag
+    pub fn with_counts(mut self, counts: HashMap<String, usize>) -> Self {
+        self.counts = counts;
+        self
+    }
+    
+    /// Starts with these tags marked as excluded
+    pub fn with_exclusions(mut self, excluded: HashSet<String>) -> Self {
+        self.excluded_tags = excluded;
+        self
+    }
+    
+    /// Starts with prompts needing any or all of the selected tags
+    pub fn with_tag_match(mut self, tag_match: TagMatch) -> Self {
+        self.tag_match = tag_match;
+        self
+    }
+    
+    // Rows matching the search, or the expanded part of the tree when not searching
+    fn visible_rows(&self) -> Vec<&TagRow> {
         if self.search_query.is_empty() {
-            self.available_tags.clone()
+            self.rows
+                .iter()
+                .filter(|row| {
+                    !tags::ancestors(&row.path)
+                        .into_iter()
+                        .any(|parent| parent != row.path && self.collapsed.contains(parent))
+                })
+                .collect()
         } else {
             let query_lower = self.search_query.to_lowercase();
-            self.available_tags
+            self.rows
                 .iter()
-                .filter(|tag| tag.to_lowercase().contains(&query_lower))
-                .cloned()
+                .filter(|row| row.path.to_lowercase().contains(&query_lower))
                 .collect()
         }
     }
     
+    /// Get filtered tags based on search query
+    pub fn get_filtered_tags(&self) -> Vec<String> {
+        self.visible_rows().into_iter().map(|row| row.path.clone()).collect()
+    }
+    
+    /// Shows the children of the highlighted tag
+    pub fn expand_selected(&mut self) {
+        if let Some(tag) = self.get_selected_tag() {
+            self.collapsed.remove(&tag);
+        }
+    }
+    
+    /// Hides the children of the highlighted tag, or moves to its parent if it has none shown
+    pub fn collapse_selected(&mut self) {
+        let visible = self.visible_rows();
+        let Some(row) = visible.get(self.selected_index) else {
+            return;
+        };
+        
+        if row.has_children && self.search_query.is_empty() && !self.collapsed.contains(&row.path) {
+            let path = row.path.clone();
+            self.collapsed.insert(path);
+        } else if let Some((parent, _)) = row.path.rsplit_once(TAG_SEPARATOR)
+            && let Some(index) = visible.iter().position(|candidate| candidate.path == parent) {
+            self.selected_index = index;
+        }
+    }
+    
     /// Toggle selection of the currently highlighted tag
     pub fn toggle_selected_tag(&mut self) {
         let filtered_tags = self.get_filtered_tags();
@@ -50,19 +120,47 @@ impl TagFilterDialog {
             if self.selected_tags.contains(tag) {
                 self.selected_tags.remove(tag);
             } else {
+                self.excluded_tags.remove(tag);
                 self.selected_tags.insert(tag.clone());
             }
         }
     }
     
+    /// Toggle whether prompts with the highlighted tag are hidden
+    pub fn toggle_excluded_tag(&mut self) {
+        if let Some(tag) = self.get_selected_tag()
+            && !self.excluded_tags.remove(&tag) {
+            self.selected_tags.remove(&tag);
+            self.excluded_tags.insert(tag);
+        }
+    }
+    
+    /// Switch between prompts needing any or all of the selected tags
+    pub fn toggle_tag_match(&mut self) {
+        self.tag_match = match self.tag_match {
+            TagMatch::Any => TagMatch::All,
+            TagMatch::All => TagMatch::Any,
+        };
+    }
+    
     /// Get the currently selected tags
     pub fn get_selected_tags(&self) -> HashSet<String> {
         self.selected_tags.clone()
     }
     
-    /// Clear all selected tags
+    /// Get the tags whose prompts are hidden
+    pub fn get_excluded_tags(&self) -> HashSet<String> {
+        self.excluded_tags.clone()
+    }
+    
+    pub fn tag_match(&self) -> TagMatch {
+        self.tag_match
+    }
+    
+    /// Clear all selected and excluded tags
     pub fn clear_selection(&mut self) {
         self.selected_tags.clear();
+        self.excluded_tags.clear();
     }
     
     /// Add character to search query
@@ -164,12 +262,20 @@ impl TagFilterDialog {
         
         // Show current filter status
         let selected_count = self.selected_tags.len();
-        let status_text = if selected_count > 0 {
+        let mode = match self.tag_match {
+            TagMatch::Any => "any",
+            TagMatch::All => "all",
+        };
+        let mut status_text = if selected_count > 0 {
             let tags: Vec<String> = self.selected_tags.iter().cloned().collect();
-            format!("Selected {} tag(s): {}", selected_count, tags.join(", "))
+            format!("Match {} of {} tag(s): {}", mode, selected_count, tags.join(", "))
         } else {
             "No tags selected".to_string()
         };
+        if !self.excluded_tags.is_empty() {
+            let tags: Vec<String> = self.excluded_tags.iter().cloned().collect();
+            status_text.push_str(&format!(" • Excluding: {}", tags.join(", ")));
+        }
         
         let status = Paragraph::new(status_text)
             .style(Style::default().fg(Color::Cyan))
@@ -185,25 +291,58 @@ impl TagFilterDialog {
                 .alignment(Alignment::Center);
             f.render_widget(empty_msg, chunks[2]);
         } else {
-            let items: Vec<ListItem> = filtered_tags
-                .iter()
+            let in_tree = self.search_query.is_empty();
+            let items: Vec<ListItem> = self.visible_rows()
+                .into_iter()
                 .enumerate()
-                .map(|(i, tag)| {
-                    let is_selected = self.selected_tags.contains(tag);
+                .map(|(i, row)| {
+                    let is_selected = self.selected_tags.contains(&row.path);
+                    let is_excluded = self.excluded_tags.contains(&row.path);
+                    // Children of a selected or excluded parent are matched through it
+                    let parents = tags::ancestors(&row.path);
+                    let is_covered = !is_selected && !is_excluded
+                        && parents.iter().any(|parent| self.selected_tags.contains(*parent) || self.excluded_tags.contains(*parent));
                     let is_highlighted = i == self.selected_index && !self.is_searching;
                     
-                    let checkbox = if is_selected { "[✓]" } else { "[ ]" };
-                    let style = match (is_highlighted, is_selected) {
-                        (true, true) => Style::default().fg(Color::Yellow).bg(Color::DarkGray),
-                        (true, false) => Style::default().bg(Color::DarkGray),
-                        (false, true) => Style::default().fg(Color::Green),
-                        (false, false) => Style::default(),
+                    let checkbox = if is_selected {
+                        "[✓]"
+                    } else if is_excluded {
+                        "[✗]"
+                    } else if is_covered {
+                        "[·]"
+                    } else {
+                        "[ ]"
+                    };
+                    let style = match (is_highlighted, is_excluded, is_selected || is_covered) {
+                        (true, true, _) => Style::default().fg(Color::Red).bg(Color::DarkGray),
+                        (true, false, true) => Style::default().fg(Color::Yellow).bg(Color::DarkGray),
+                        (true, false, false) => Style::default().bg(Color::DarkGray),
+                        (false, true, _) => Style::default().fg(Color::Red),
+                        (false, false, true) => Style::default().fg(Color::Green),
+                        (false, false, false) => Style::default(),
+                    };
+                    
+                    // The tree shows each level's own name; search results show the full path
+                    let (indent, marker, label) = if in_tree {
+                        let marker = match (row.has_children, self.collapsed.contains(&row.path)) {
+                            (true, true) => "▸ ",
+                            (true, false) => "▾ ",
+                            (false, _) => "  ",
+                        };
+                        let label = row.path.rsplit(TAG_SEPARATOR).next().unwrap_or(&row.path);
+                        ("  ".repeat(row.depth), marker, label)
+                    } else {
+                        (String::new(), "", row.path.as_str())
                     };
                     
-                    ListItem::new(Line::from(vec![
-                        Span::raw(format!("{} ", checkbox)),
-                        Span::styled(tag, style),
-                    ]))
+                    let mut spans = vec![
+                        Span::raw(format!("{} {}{}", checkbox, indent, marker)),
+                        Span::styled(label.to_string(), style),
+                    ];
+                    if let Some(count) = self.counts.get(&row.path) {
+                        spans.push(Span::styled(format!(" ({})", count), Style::default().fg(Color::DarkGray)));
+                    }
+                    ListItem::new(Line::from(spans))
                 })
                 .collect();
             
@@ -221,7 +360,7 @@ impl TagFilterDialog {
         let help_text = if self.is_searching {
             "Type to search • Tab: Switch to selection • Enter: Apply • Esc: Cancel"
         } else {
-            "↑↓: Navigate • Space: Toggle • Tab: Back to search • Enter: Apply • c: Clear all • Esc: Cancel"
+            "↑↓: Navigate • ←→: Collapse/Expand • Space: Include • x: Exclude • m: Any/All • Tab: Search • Enter: Apply • c: Clear all • Esc: Cancel"
         };
         let help = Paragraph::new(help_text)
             .style(Style::default().fg(Color::Gray))
@@ -254,6 +393,41 @@ impl TagFilterDialog {
     }
 }
 
+// Lays out tags as a tree in the order they first appear, adding parents that only exist
+// through their children, so `lang/rust` also lists `lang`
+fn build_rows(available_tags: &[String]) -> Vec<TagRow> {
+    struct Node {
+        path: String,
+        children: Vec<Node>,
+    }
+    
+    let mut roots: Vec<Node> = Vec::new();
+    for tag in available_tags {
+        let mut level = &mut roots;
+        for path in tags::ancestors(tag) {
+            let index = match level.iter().position(|node| node.path == path) {
+                Some(index) => index,
+                None => {
+                    level.push(Node { path: path.to_string(), children: Vec::new() });
+                    level.len() - 1
+                }
+            };
+            level = &mut level[index].children;
+        }
+    }
+    
+    fn flatten(nodes: Vec<Node>, depth: usize, rows: &mut Vec<TagRow>) {
+        for node in nodes {
+            rows.push(TagRow { path: node.path, depth, has_children: !node.children.is_empty() });
+            flatten(node.children, depth + 1, rows);
+        }
+    }
+    
+    let mut rows = Vec::new();
+    flatten(roots, 0, &mut rows);
+    rows
+}
+
 // Helper function to create centered rect
 fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
     let width = width.min(r.width.saturating_sub(2));
@@ -0,0 +1,114 @@
+use crate::application::models::TrashEntry;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// "Recently deleted" view listing prompts in the trash
+pub struct TrashDialog {
+    entries: Vec<TrashEntry>,
+    selected_index: usize,
+}
+
+impl TrashDialog {
+    pub fn new(entries: Vec<TrashEntry>) -> Self {
+        Self {
+            entries,
+            selected_index: 0,
+        }
+    }
+
+    pub fn entries(&self) -> &[TrashEntry] {
+        &self.entries
+    }
+
+    pub fn selected_entry(&self) -> Option<&TrashEntry> {
+        self.entries.get(self.selected_index)
+    }
+
+    pub fn move_up(&mut self) {
+        if !self.entries.is_empty() {
+            if self.selected_index == 0 {
+                self.selected_index = self.entries.len() - 1;
+            } else {
+                self.selected_index -= 1;
+            }
+        }
+    }
+
+    pub fn move_down(&mut self) {
+        if !self.entries.is_empty() {
+            self.selected_index = (self.selected_index + 1) % self.entries.len();
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let modal_width = 70.min(area.width.saturating_sub(2));
+        let modal_height = 20.min(area.height.saturating_sub(2));
+        let modal_area = Rect {
+            x: area.x + (area.width.saturating_sub(modal_width)) / 2,
+            y: area.y + (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        f.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title("Recently Deleted")
+            .title_alignment(Alignment::Center)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner_area = block.inner(modal_area);
+        f.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),        // Entries
+                Constraint::Length(1),     // Help text
+            ])
+            .split(inner_area);
+
+        if self.entries.is_empty() {
+            let empty_msg = Paragraph::new("Trash is empty")
+                .style(Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+                .alignment(Alignment::Center);
+            f.render_widget(empty_msg, chunks[0]);
+        } else {
+            let items: Vec<ListItem> = self.entries
+                .iter()
+                .map(|entry| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{}  ", entry.deleted_at.format("%Y-%m-%d %H:%M")),
+                            Style::default().fg(Color::DarkGray),
+                        ),
+                        Span::raw(entry.prompt_id()),
+                    ]))
+                })
+                .collect();
+
+            let list = List::new(items)
+                .highlight_style(
+                    Style::default()
+                        .bg(Color::DarkGray)
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD)
+                );
+
+            let mut list_state = ListState::default();
+            list_state.select(Some(self.selected_index));
+            f.render_stateful_widget(list, chunks[0], &mut list_state);
+        }
+
+        let help_widget = Paragraph::new("↑↓: Select  Enter/r: Restore  Esc: Close")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(help_widget, chunks[1]);
+    }
+}
@@ -5,14 +5,39 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use crate::application::models::PromptTypes;
+use crate::utils::default_prompts::get_type_specific_prompts;
 
-pub struct TypePromptsDialog;
+pub struct TypePromptsDialog {
+    // Configured types that ship defaults: display name, description and prompt count
+    entries: Vec<(String, String, usize)>,
+}
 
 impl TypePromptsDialog {
+    pub fn new(prompt_types: &PromptTypes) -> Self {
+        let defaults = get_type_specific_prompts();
+        let entries = prompt_types
+            .definitions()
+            .iter()
+            .filter_map(|definition| {
+                let count = defaults.get(&definition.id).map(Vec::len)?;
+                Some((definition.name.clone(), definition.description.clone(), count))
+            })
+            .collect();
+        
+        Self { entries }
+    }
+    
+    /// Number of prompts that accepting the dialog would add
+    pub fn prompt_count(&self) -> usize {
+        self.entries.iter().map(|(_, _, count)| count).sum()
+    }
+    
+
     pub fn render(&self, f: &mut Frame<'_>, area: Rect) {
         // Calculate centered dialog size
         let dialog_width = 65.min(area.width - 4);
-        let dialog_height = 15.min(area.height - 4);
+        let dialog_height = (self.entries.len() as u16 + 11).min(area.height - 4);
         
         let x = (area.width - dialog_width) / 2;
         let y = (area.height - dialog_height) / 2;
@@ -30,19 +55,24 @@ impl TypePromptsDialog {
             .border_style(Style::default().fg(Color::Cyan));
         
         // Create the content
-        let text = vec![
+        let mut text = vec![
             Line::from(""),
             Line::from("Great! Basic prompts have been initialized."),
             Line::from(""),
             Line::from("Would you also like to add type-specific prompts?"),
             Line::from("These prompts are organized by their purpose:"),
             Line::from(""),
-            Line::from("  • Instruction - Step-by-step guides & how-tos"),
-            Line::from("  • Context - Project & code background info"),
-            Line::from("  • Input/Output - Data format specifications"),
-            Line::from("  • Etc - Brainstorming & checklists"),
+        ];
+        for (name, description, _) in &self.entries {
+            if description.is_empty() {
+                text.push(Line::from(format!("  • {}", name)));
+            } else {
+                text.push(Line::from(format!("  • {} - {}", name, description)));
+            }
+        }
+        text.extend([
             Line::from(""),
-            Line::from("This will add 12 more specialized prompts."),
+            Line::from(format!("This will add {} more specialized prompts.", self.prompt_count())),
             Line::from(""),
             Line::from(vec![
                 Span::raw("Press "),
@@ -51,7 +81,7 @@ impl TypePromptsDialog {
                 Span::styled("N", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
                 Span::raw(" to skip"),
             ]),
-        ];
+        ]);
         
         let paragraph = Paragraph::new(text)
             .block(block)
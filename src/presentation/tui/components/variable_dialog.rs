@@ -0,0 +1,308 @@
+use crate::utils::variables::{self, VariableKind, VariableSpec};
+use std::collections::HashMap;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Form for filling a prompt's `{{variables}}` before it is copied
+pub struct VariableDialog {
+    prompt_name: String,
+    content: String,
+    specs: Vec<VariableSpec>,
+    values: Vec<String>,
+    selected_index: usize,
+    error: Option<String>,
+}
+
+impl VariableDialog {
+    pub fn new(prompt_name: String, content: String) -> Self {
+        let specs = variables::resolve_specs(&content, &[]);
+        Self::with_specs(prompt_name, content, specs)
+    }
+
+    /// Builds the form from declared variables, starting each field at its default
+    pub fn with_specs(prompt_name: String, content: String, specs: Vec<VariableSpec>) -> Self {
+        let values = specs
+            .iter()
+            .map(|spec| match (&spec.default, spec.kind) {
+                (Some(default), _) => default.clone(),
+                (None, VariableKind::Bool) => "false".to_string(),
+                (None, VariableKind::Choice) => spec.choices.first().cloned().unwrap_or_default(),
+                (None, _) => String::new(),
+            })
+            .collect();
+        Self {
+            prompt_name,
+            content,
+            specs,
+            values,
+            selected_index: 0,
+            error: None,
+        }
+    }
+
+    pub fn prompt_name(&self) -> &str {
+        &self.prompt_name
+    }
+
+    pub fn variables(&self) -> Vec<String> {
+        self.specs.iter().map(|spec| spec.name.clone()).collect()
+    }
+
+    pub fn specs(&self) -> &[VariableSpec] {
+        &self.specs
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    pub fn selected_kind(&self) -> Option<VariableKind> {
+        self.specs.get(self.selected_index).map(|spec| spec.kind)
+    }
+
+    pub fn is_last_field(&self) -> bool {
+        self.selected_index + 1 >= self.specs.len()
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn next_field(&mut self) {
+        if !self.specs.is_empty() {
+            self.selected_index = (self.selected_index + 1) % self.specs.len();
+        }
+    }
+
+    pub fn previous_field(&mut self) {
+        if !self.specs.is_empty() {
+            if self.selected_index == 0 {
+                self.selected_index = self.specs.len() - 1;
+            } else {
+                self.selected_index -= 1;
+            }
+        }
+    }
+
+    /// Types into the selected field; bool and choice fields cycle on space instead
+    pub fn add_char(&mut self, c: char) {
+        match self.selected_kind() {
+            Some(VariableKind::Bool | VariableKind::Choice) if c == ' ' => self.cycle_value(true),
+            Some(VariableKind::Bool | VariableKind::Choice) => {}
+            Some(VariableKind::Number) if !(c.is_ascii_digit() || matches!(c, '-' | '.')) => {}
+            Some(_) => {
+                self.values[self.selected_index].push(c);
+                self.error = None;
+            }
+            None => {}
+        }
+    }
+
+    pub fn delete_char(&mut self) {
+        if matches!(self.selected_kind(), Some(VariableKind::String | VariableKind::Multiline | VariableKind::Number)) {
+            self.values[self.selected_index].pop();
+        }
+    }
+
+    pub fn insert_newline(&mut self) {
+        if self.selected_kind() == Some(VariableKind::Multiline) {
+            self.values[self.selected_index].push('\n');
+        }
+    }
+
+    /// Moves a bool or choice field to its next (or previous) option
+    pub fn cycle_value(&mut self, forward: bool) {
+        let Some(spec) = self.specs.get(self.selected_index) else {
+            return;
+        };
+        let options: Vec<String> = match spec.kind {
+            VariableKind::Bool => vec!["false".to_string(), "true".to_string()],
+            VariableKind::Choice => spec.choices.clone(),
+            _ => return,
+        };
+        if options.is_empty() {
+            return;
+        }
+
+        let value = &mut self.values[self.selected_index];
+        let current = options.iter().position(|option| option == value);
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % options.len(),
+            (Some(0), false) | (None, false) => options.len() - 1,
+            (Some(i), false) => i - 1,
+            (None, true) => 0,
+        };
+        *value = options[next].clone();
+        self.error = None;
+    }
+
+    pub fn set_value(&mut self, name: &str, value: &str) {
+        if let Some(index) = self.specs.iter().position(|spec| spec.name == name) {
+            self.values[index] = value.to_string();
+        }
+    }
+
+    pub fn values(&self) -> HashMap<String, String> {
+        self.specs
+            .iter()
+            .map(|spec| spec.name.clone())
+            .zip(self.values.iter().cloned())
+            .collect()
+    }
+
+    /// The prompt body with the values entered so far
+    pub fn rendered(&self) -> String {
+        variables::substitute(&self.content, &self.values())
+    }
+
+    /// The final text, checked against the variable declarations
+    pub fn submit(&self) -> crate::utils::error::Result<String> {
+        variables::render_with_specs(&self.content, &self.specs, &self.values())
+    }
+
+    pub fn render(&self, f: &mut Frame, area: Rect) {
+        let fields_height = (self.specs.len() as u16 + 2).min(10);
+        let modal_height = fields_height + 15;
+        let modal_area = centered_rect(70, modal_height, area);
+
+        f.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .title(format!("Fill Variables: {}", self.prompt_name))
+            .title_alignment(Alignment::Center)
+            .border_style(Style::default().fg(Color::Yellow));
+        let inner_area = block.inner(modal_area);
+        f.render_widget(block, modal_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(fields_height),     // Fields
+                Constraint::Length(1),                 // Description or error
+                Constraint::Min(3),                    // Preview
+                Constraint::Length(1),                 // Help text
+            ])
+            .split(inner_area);
+
+        self.render_fields(f, chunks[0]);
+        self.render_hint(f, chunks[1]);
+
+        let preview = Paragraph::new(self.rendered())
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title("Preview"))
+            .wrap(Wrap { trim: false });
+        f.render_widget(preview, chunks[2]);
+
+        let help_text = match self.selected_kind() {
+            Some(VariableKind::Bool | VariableKind::Choice) => "Tab/↑↓: Switch field  ←→/Space: Change  Enter: Next / Copy  Ctrl+S: Copy  Esc: Cancel",
+            Some(VariableKind::Multiline) => "Tab/↑↓: Switch field  Enter: New line  Ctrl+S: Copy  Esc: Cancel",
+            _ => "Tab/↑↓: Switch field  Enter: Next / Copy  Ctrl+S: Copy  Esc: Cancel",
+        };
+        let help_widget = Paragraph::new(help_text)
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(help_widget, chunks[3]);
+    }
+
+    fn render_hint(&self, f: &mut Frame, area: Rect) {
+        let hint = if let Some(error) = &self.error {
+            Span::styled(error.clone(), Style::default().fg(Color::Red))
+        } else if let Some(spec) = self.specs.get(self.selected_index) {
+            let mut text = spec.description.clone().unwrap_or_default();
+            let kind = match spec.kind {
+                VariableKind::String => None,
+                VariableKind::Multiline => Some("multiline".to_string()),
+                VariableKind::Number => Some("number".to_string()),
+                VariableKind::Bool => Some("true / false".to_string()),
+                VariableKind::Choice => Some(spec.choices.join(" | ")),
+            };
+            if let Some(kind) = kind {
+                if !text.is_empty() {
+                    text.push_str("  ");
+                }
+                text.push_str(&format!("({})", kind));
+            }
+            if spec.required {
+                text.push_str("  *required");
+            }
+            Span::styled(text, Style::default().fg(Color::DarkGray).add_modifier(Modifier::ITALIC))
+        } else {
+            Span::raw("")
+        };
+        f.render_widget(Paragraph::new(Line::from(hint)), area);
+    }
+
+    fn render_fields(&self, f: &mut Frame, area: Rect) {
+        let label_width = self.specs.iter().map(|spec| spec.name.chars().count()).max().unwrap_or(0);
+
+        // Keep the selected field visible when there are more fields than rows
+        let visible_rows = area.height.saturating_sub(2) as usize;
+        let first_row = (self.selected_index + 1).saturating_sub(visible_rows);
+
+        let lines: Vec<Line> = self.specs
+            .iter()
+            .zip(self.values.iter())
+            .enumerate()
+            .skip(first_row)
+            .map(|(i, (spec, value))| {
+                let is_selected = i == self.selected_index;
+                let label_style = if is_selected {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Cyan)
+                };
+                // Bool and choice fields are picked rather than typed, so they get arrows instead of a cursor
+                let shown = match spec.kind {
+                    VariableKind::Bool | VariableKind::Choice if is_selected => format!("◀ {} ▶", value),
+                    VariableKind::Multiline => value.replace('\n', "⏎"),
+                    _ => value.clone(),
+                };
+                let cursor = if is_selected && !matches!(spec.kind, VariableKind::Bool | VariableKind::Choice) { "_" } else { "" };
+                Line::from(vec![
+                    Span::styled(format!("{:>width$}: ", spec.name, width = label_width), label_style),
+                    Span::raw(format!("{}{}", shown, cursor)),
+                ])
+            })
+            .collect();
+
+        let fields = Paragraph::new(lines)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .title("Variables"));
+        f.render_widget(fields, area);
+    }
+}
+
+// Helper function to create centered rect with fixed size
+fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
+    let width = width.min(r.width.saturating_sub(2));
+    let height = height.min(r.height.saturating_sub(2));
+
+    let x = r.x + (r.width.saturating_sub(width)) / 2;
+    let y = r.y + (r.height.saturating_sub(height)) / 2;
+
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
@@ -58,7 +58,7 @@ impl StandardLayoutBuilder {
 pub struct UIStyles;
 
 impl UIStyles {
-    pub fn header_block(title: &str) -> Block {
+    pub fn header_block(title: &str) -> Block<'_> {
         Block::default()
             .borders(Borders::ALL)
             .title(title)
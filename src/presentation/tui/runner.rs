@@ -9,6 +9,12 @@ use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use std::io;
 use std::path::PathBuf;
+use std::time::Duration;
+use crate::external::watcher::StorageWatcher;
+use crate::utils::variables::VariableKind;
+
+/// How long to wait for input before checking the storage watcher
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(250);
 
 // TUI runner functions for managing the terminal user interface
 
@@ -83,11 +89,19 @@ impl EventHandler {
     fn handle_confirmation_dialog(&self, app: &mut TUIApp, key: &KeyEvent) -> Result<()> {
         match key.code {
             KeyCode::Char('y') | KeyCode::Char('Y') => {
-                app.confirm_action()?;
+                if let Err(e) = app.confirm_action() {
+                    app.set_error(format!("Error: {}", e));
+                }
             }
             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                 app.cancel_confirmation();
             }
+            KeyCode::Left => {
+                app.cycle_bulk_type(false);
+            }
+            KeyCode::Right => {
+                app.cycle_bulk_type(true);
+            }
             _ => {} // Ignore other keys while confirmation dialog is showing
         }
         Ok(())
@@ -134,6 +148,12 @@ impl EventHandler {
                     KeyCode::Down => {
                         filter_dialog.move_down();
                     }
+                    KeyCode::Left => {
+                        filter_dialog.collapse_selected();
+                    }
+                    KeyCode::Right => {
+                        filter_dialog.expand_selected();
+                    }
                     KeyCode::Char(' ') => {
                         filter_dialog.toggle_selected_tag();
                     }
@@ -141,6 +161,12 @@ impl EventHandler {
                         should_apply = true;
                         should_close = true;
                     }
+                    KeyCode::Char('x') => {
+                        filter_dialog.toggle_excluded_tag();
+                    }
+                    KeyCode::Char('m') => {
+                        filter_dialog.toggle_tag_match();
+                    }
                     KeyCode::Char('c') => {
                         filter_dialog.clear_selection();
                     }
@@ -149,11 +175,14 @@ impl EventHandler {
             }
         }
         
-        if should_apply {
-            if let Some(filter_dialog) = app.get_tag_filter_dialog() {
-                let selected_tags = filter_dialog.get_selected_tags();
-                app.set_tag_filters(selected_tags);
-            }
+        if should_apply
+            && let Some(filter_dialog) = app.get_tag_filter_dialog() {
+            let selected_tags = filter_dialog.get_selected_tags();
+            let excluded_tags = filter_dialog.get_excluded_tags();
+            let tag_match = filter_dialog.tag_match();
+            app.set_tag_filters(selected_tags);
+            app.set_excluded_tag_filters(excluded_tags);
+            app.set_tag_match(tag_match);
         }
         
         if should_close {
@@ -164,11 +193,14 @@ impl EventHandler {
     }
     
     fn handle_tag_management_dialog(&self, app: &mut TUIApp, key: &KeyEvent) -> Result<()> {
-        use crate::presentation::tui::components::TagInputMode;
+        use crate::presentation::tui::components::{LibraryTagAction, TagInputMode};
+        use crate::presentation::tui::components::confirmation_dialog::BulkAction;
         
         let mut should_close = false;
         let mut new_tag_to_add = None;
         let mut tag_to_remove = None;
+        let mut operation_to_preview = None;
+        let mut operation_to_apply = None;
         let mut should_refresh = false;
         
         // First, handle the dialog input
@@ -185,6 +217,15 @@ impl EventHandler {
                         KeyCode::Char('r') => {
                             tag_dialog.start_removing_tag();
                         }
+                        KeyCode::Char('R') => {
+                            tag_dialog.start_library_action(LibraryTagAction::Rename);
+                        }
+                        KeyCode::Char('M') => {
+                            tag_dialog.start_library_action(LibraryTagAction::Merge);
+                        }
+                        KeyCode::Char('D') => {
+                            tag_dialog.start_library_action(LibraryTagAction::Delete);
+                        }
                         _ => {}
                     }
                 }
@@ -226,6 +267,53 @@ impl EventHandler {
                         _ => {}
                     }
                 }
+                TagInputMode::ChoosingLibraryTag(_) => {
+                    match key.code {
+                        KeyCode::Esc => {
+                            tag_dialog.cancel_input();
+                        }
+                        KeyCode::Up => {
+                            tag_dialog.move_selection_up();
+                        }
+                        KeyCode::Down => {
+                            tag_dialog.move_selection_down();
+                        }
+                        KeyCode::Enter => {
+                            operation_to_preview = tag_dialog.choose_library_tag();
+                        }
+                        _ => {}
+                    }
+                }
+                TagInputMode::EnteringTarget(_) => {
+                    match key.code {
+                        KeyCode::Esc => {
+                            tag_dialog.cancel_input();
+                        }
+                        KeyCode::Enter => {
+                            operation_to_preview = tag_dialog.get_library_operation();
+                        }
+                        KeyCode::Char(c) => {
+                            tag_dialog.add_char(c);
+                        }
+                        KeyCode::Backspace => {
+                            tag_dialog.delete_char();
+                        }
+                        _ => {}
+                    }
+                }
+                TagInputMode::ConfirmingLibraryChange => {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('n') => {
+                            tag_dialog.cancel_input();
+                        }
+                        KeyCode::Enter | KeyCode::Char('y') => {
+                            operation_to_apply = tag_dialog.pending_operation().cloned();
+                            tag_dialog.cancel_input();
+                            should_refresh = true;
+                        }
+                        _ => {}
+                    }
+                }
             }
         }
         
@@ -234,22 +322,54 @@ impl EventHandler {
             app.close_tag_management();
         }
         
-        if let Some(new_tag) = new_tag_to_add {
-            if let Err(e) = app.add_tag_to_selected(&new_tag) {
-                app.set_error(format!("Error adding tag: {}", e));
+        // With prompts marked, adding or removing a tag is confirmed for all of them at once
+        if app.has_marks() && (new_tag_to_add.is_some() || tag_to_remove.is_some()) {
+            app.close_tag_management();
+            if let Some(tag) = new_tag_to_add.take() {
+                app.show_bulk_confirmation(BulkAction::AddTag(tag));
             }
+            if let Some(tag) = tag_to_remove.take() {
+                app.show_bulk_confirmation(BulkAction::RemoveTag(tag));
+            }
+            return Ok(());
+        }
+        
+        if let Some(new_tag) = new_tag_to_add
+            && let Err(e) = app.add_tag_to_selected(&new_tag) {
+            app.set_error(format!("Error adding tag: {}", e));
+        }
+        
+        if let Some(tag) = tag_to_remove
+            && let Err(e) = app.remove_tag_from_selected(&tag) {
+            app.set_error(format!("Error removing tag: {}", e));
         }
         
-        if let Some(tag) = tag_to_remove {
-            if let Err(e) = app.remove_tag_from_selected(&tag) {
-                app.set_error(format!("Error removing tag: {}", e));
+        if let Some(operation) = operation_to_preview {
+            match app.preview_tag_operation(&operation) {
+                Ok(changes) => {
+                    if let Some(tag_dialog) = app.get_tag_dialog_mut() {
+                        tag_dialog.show_preview(operation, changes);
+                    }
+                }
+                Err(e) => {
+                    app.set_error(format!("Error changing tag: {}", e));
+                    if let Some(tag_dialog) = app.get_tag_dialog_mut() {
+                        tag_dialog.cancel_input();
+                    }
+                }
+            }
+        }
+        
+        if let Some(operation) = operation_to_apply {
+            match app.apply_tag_operation(&operation) {
+                Ok(count) => app.set_success(format!("Updated tags in {} prompt(s)", count)),
+                Err(e) => app.set_error(format!("Error changing tag: {}", e)),
             }
         }
         
         if should_refresh {
             // Refresh the dialog with updated tags
-            let updated_tags = app.get_selected_prompt_tags();
-            app.tag_dialog = Some(crate::presentation::tui::components::TagManagementDialog::new(updated_tags));
+            app.open_tag_management();
         }
         
         Ok(())
@@ -269,10 +389,8 @@ impl EventHandler {
                 KeyCode::Tab => {
                     create_dialog.next_field();
                 }
-                KeyCode::Enter => {
-                    if create_dialog.is_valid() {
-                        should_confirm = true;
-                    }
+                KeyCode::Enter if create_dialog.is_valid() => {
+                    should_confirm = true;
                 }
                 KeyCode::Left => {
                     if create_dialog.current_field() == DialogField::Template {
@@ -306,15 +424,11 @@ impl EventHandler {
                         create_dialog.add_char('l');
                     }
                 }
-                KeyCode::Char(c) => {
-                    if create_dialog.current_field() == DialogField::Filename {
-                        create_dialog.add_char(c);
-                    }
+                KeyCode::Char(c) if create_dialog.current_field() == DialogField::Filename => {
+                    create_dialog.add_char(c);
                 }
-                KeyCode::Backspace => {
-                    if create_dialog.current_field() == DialogField::Filename {
-                        create_dialog.delete_char();
-                    }
+                KeyCode::Backspace if create_dialog.current_field() == DialogField::Filename => {
+                    create_dialog.delete_char();
                 }
                 _ => {}
             }
@@ -324,9 +438,121 @@ impl EventHandler {
             app.close_create_dialog();
         }
         
-        if should_confirm {
-            if let Err(e) = app.confirm_create() {
-                app.set_error(format!("Failed to create prompt: {}", e));
+        if should_confirm
+            && let Err(e) = app.confirm_create() {
+            app.set_error(format!("Failed to create prompt: {}", e));
+        }
+        
+        Ok(())
+    }
+    
+    fn handle_history_dialog(&self, app: &mut TUIApp, key: &KeyEvent) -> Result<()> {
+        let mut selection_changed = false;
+        
+        if let Some(history_dialog) = app.get_history_dialog_mut() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.close_history();
+                }
+                KeyCode::Up => {
+                    history_dialog.move_up();
+                    selection_changed = true;
+                }
+                KeyCode::Down => {
+                    history_dialog.move_down();
+                    selection_changed = true;
+                }
+                KeyCode::PageUp => {
+                    history_dialog.scroll_diff_up();
+                }
+                KeyCode::PageDown => {
+                    history_dialog.scroll_diff_down();
+                }
+                KeyCode::Char('r') | KeyCode::Enter => {
+                    app.show_restore_confirmation();
+                }
+                _ => {}
+            }
+        }
+        
+        if selection_changed
+            && let Err(e) = app.refresh_history_diff() {
+            app.set_error(format!("Failed to load revision: {}", e));
+        }
+        
+        Ok(())
+    }
+    
+    fn handle_trash_dialog(&self, app: &mut TUIApp, key: &KeyEvent) -> Result<()> {
+        if let Some(trash_dialog) = app.get_trash_dialog_mut() {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    app.close_trash();
+                }
+                KeyCode::Up => {
+                    trash_dialog.move_up();
+                }
+                KeyCode::Down => {
+                    trash_dialog.move_down();
+                }
+                KeyCode::Enter | KeyCode::Char('r') => {
+                    if let Err(e) = app.restore_selected_trash_entry() {
+                        app.set_error(format!("Failed to restore prompt: {}", e));
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    
+    fn handle_variable_dialog(&self, app: &mut TUIApp, key: &KeyEvent) -> Result<()> {
+        let mut should_submit = false;
+        
+        if let Some(dialog) = app.get_variable_dialog_mut() {
+            match key.code {
+                KeyCode::Esc => {
+                    app.close_variable_dialog();
+                }
+                KeyCode::Char('s') if key.modifiers.contains(event::KeyModifiers::CONTROL) => {
+                    should_submit = true;
+                }
+                KeyCode::Tab | KeyCode::Down => {
+                    dialog.next_field();
+                }
+                KeyCode::BackTab | KeyCode::Up => {
+                    dialog.previous_field();
+                }
+                KeyCode::Left => {
+                    dialog.cycle_value(false);
+                }
+                KeyCode::Right => {
+                    dialog.cycle_value(true);
+                }
+                KeyCode::Enter => {
+                    if dialog.selected_kind() == Some(VariableKind::Multiline) {
+                        dialog.insert_newline();
+                    } else if dialog.is_last_field() {
+                        should_submit = true;
+                    } else {
+                        dialog.next_field();
+                    }
+                }
+                KeyCode::Char(c) => {
+                    dialog.add_char(c);
+                }
+                KeyCode::Backspace => {
+                    dialog.delete_char();
+                }
+                _ => {}
+            }
+        }
+        
+        if should_submit {
+            match app.submit_variable_dialog() {
+                Ok(true) => app.quit(),
+                Ok(false) => {}
+                Err(e) => app.set_error(format!("Cannot copy: {}", e)),
             }
         }
         
@@ -359,13 +585,67 @@ impl EventHandler {
                         KeyCode::Right => {
                             panel.move_cursor_right();
                         }
+                        KeyCode::BackTab => {
+                            panel.go_back();
+                        }
+                        _ => {}
+                    }
+                }
+                BuildStep::Complete if panel.is_naming_recipe() => {
+                    // Handle recipe name input; failures are shown on the completion screen
+                    match key.code {
+                        KeyCode::Esc => {
+                            panel.cancel_saving_recipe();
+                        }
+                        KeyCode::Enter => {
+                            let _ = app.save_build_as_recipe();
+                        }
+                        KeyCode::Char(c) => {
+                            panel.add_recipe_name_char(c);
+                        }
+                        KeyCode::Backspace => {
+                            panel.delete_recipe_name_char();
+                        }
                         _ => {}
                     }
                 }
                 BuildStep::Complete => {
-                    // Handle completion
+                    // Handle the review screen
                     match key.code {
-                        KeyCode::Enter | KeyCode::Esc => {
+                        KeyCode::Up if key.modifiers.contains(event::KeyModifiers::SHIFT) => {
+                            panel.move_review_item_up();
+                        }
+                        KeyCode::Down if key.modifiers.contains(event::KeyModifiers::SHIFT) => {
+                            panel.move_review_item_down();
+                        }
+                        KeyCode::Char('K') => {
+                            panel.move_review_item_up();
+                        }
+                        KeyCode::Char('J') => {
+                            panel.move_review_item_down();
+                        }
+                        KeyCode::Up => {
+                            panel.review_previous();
+                        }
+                        KeyCode::Down => {
+                            panel.review_next();
+                        }
+                        KeyCode::Char('d') | KeyCode::Delete => {
+                            panel.remove_review_item();
+                        }
+                        KeyCode::Char('s') => {
+                            panel.start_saving_recipe();
+                        }
+                        KeyCode::Char('f') => {
+                            panel.cycle_format();
+                        }
+                        KeyCode::BackTab => {
+                            panel.go_back();
+                        }
+                        KeyCode::Esc => {
+                            app.exit_build_mode();
+                        }
+                        KeyCode::Enter => {
                             if let Err(e) = app.combine_and_copy_selected_prompts() {
                                 app.set_error(format!("Failed to combine prompts: {}", e));
                             }
@@ -385,9 +665,21 @@ impl EventHandler {
                         KeyCode::Down => {
                             panel.next();
                         }
+                        KeyCode::Char(' ') => {
+                            panel.toggle_current();
+                        }
+                        KeyCode::BackTab | KeyCode::Backspace => {
+                            panel.go_back();
+                        }
                         KeyCode::Enter => {
                             panel.select_current();
                         }
+                        KeyCode::Char(c @ '1'..='9') => {
+                            let index = c as usize - '1' as usize;
+                            if let Err(e) = app.run_recipe(index) {
+                                app.set_error(format!("Failed to run recipe: {}", e));
+                            }
+                        }
                         _ => {}
                     }
                 }
@@ -474,10 +766,18 @@ impl EventHandler {
                     app.set_search_query(&new_query);
                 }
             }
+            KeyCode::Tab => {
+                app.cycle_snippet();
+            }
+            KeyCode::Enter if app.open_selected_snippet() => {
+                // The editor opens at the selected snippet's line
+            }
             KeyCode::Enter => {
                 // Keep search active but allow selection
                 if matches!(app.mode(), AppMode::QuickSelect) {
                     match app.copy_selected_to_clipboard() {
+                        // Prompts with variables wait for the fill-in form
+                        Ok(_) if app.is_variable_dialog_active() => {}
                         Ok(_) => app.quit(),
                         Err(e) => app.set_error(format!("Cannot copy: {}", e)),
                     }
@@ -505,13 +805,43 @@ impl EventHandler {
     }
     
     fn handle_normal_mode(&self, app: &mut TUIApp, key: &KeyEvent) -> Result<()> {
+        use crate::presentation::tui::components::confirmation_dialog::BulkAction;
+        
+        let is_management = matches!(app.mode(), AppMode::Management);
         match key.code {
+            KeyCode::Esc if app.has_marks() => {
+                app.clear_marks();
+            }
             KeyCode::Esc | KeyCode::Char('q') => {
                 app.quit();
             }
             KeyCode::Down => {
                 app.next();
             }
+            KeyCode::Char(' ') if is_management => {
+                app.toggle_mark_selected();
+                app.next();
+            }
+            KeyCode::Char('V') if is_management => {
+                app.mark_range_to_selected();
+            }
+            KeyCode::Char('s') if is_management && app.has_marks() => {
+                app.show_bulk_star_confirmation();
+            }
+            KeyCode::Char('d') if is_management && app.has_marks() => {
+                app.show_bulk_confirmation(BulkAction::Delete);
+            }
+            KeyCode::Char('p') if is_management => {
+                app.show_bulk_type_confirmation();
+            }
+            KeyCode::Char('x') if is_management => {
+                if let Err(e) = app.show_bulk_export_confirmation() {
+                    app.set_error(format!("Error exporting prompts: {}", e));
+                }
+            }
+            KeyCode::Char('y') if is_management => {
+                app.show_bulk_confirmation(BulkAction::Copy);
+            }
             KeyCode::Up => {
                 app.previous();
             }
@@ -520,6 +850,8 @@ impl EventHandler {
                 // In Management mode, we'll handle this differently later
                 if matches!(app.mode(), AppMode::QuickSelect) {
                     match app.copy_selected_to_clipboard() {
+                        // Prompts with variables wait for the fill-in form
+                        Ok(_) if app.is_variable_dialog_active() => {}
                         Ok(_) => app.quit(),
                         Err(e) => app.set_error(format!("Cannot copy: {}", e)),
                     }
@@ -529,10 +861,9 @@ impl EventHandler {
                 app.toggle_mode();
             }
             KeyCode::Char('s') => {
-                if matches!(app.mode(), AppMode::QuickSelect | AppMode::Management) {
-                    if let Err(e) = app.toggle_star_on_selected() {
-                        app.set_error(e.to_string());
-                    }
+                if matches!(app.mode(), AppMode::QuickSelect | AppMode::Management)
+                    && let Err(e) = app.toggle_star_on_selected() {
+                    app.set_error(e.to_string());
                 }
             }
             KeyCode::Char('e') => {
@@ -556,10 +887,9 @@ impl EventHandler {
                 }
             }
             KeyCode::Char('n') => {
-                if matches!(app.mode(), AppMode::Management) {
-                    if let Err(e) = app.create_new_prompt() {
-                        app.set_error(format!("Error creating prompt: {}", e));
-                    }
+                if matches!(app.mode(), AppMode::Management)
+                    && let Err(e) = app.create_new_prompt() {
+                    app.set_error(format!("Error creating prompt: {}", e));
                 }
             }
             KeyCode::Char('t') => {
@@ -567,6 +897,18 @@ impl EventHandler {
                     app.open_tag_management();
                 }
             }
+            KeyCode::Char('h') => {
+                if matches!(app.mode(), AppMode::Management)
+                    && let Err(e) = app.open_history() {
+                    app.set_error(format!("Error loading history: {}", e));
+                }
+            }
+            KeyCode::Char('T') => {
+                if matches!(app.mode(), AppMode::Management)
+                    && let Err(e) = app.open_trash() {
+                    app.set_error(format!("Error loading trash: {}", e));
+                }
+            }
             KeyCode::Char('f') => {
                 // Open tag filter dialog in both modes
                 app.open_tag_filter();
@@ -631,6 +973,21 @@ impl EventHandler {
             if app.is_create_dialog_active() {
                 return self.handle_create_dialog(app, &key);
             }
+            
+            // Handle history dialog if showing
+            if app.is_history_active() {
+                return self.handle_history_dialog(app, &key);
+            }
+            
+            // Handle trash dialog if showing
+            if app.is_trash_active() {
+                return self.handle_trash_dialog(app, &key);
+            }
+            
+            // Handle variable fill-in form if showing
+            if app.is_variable_dialog_active() {
+                return self.handle_variable_dialog(app, &key);
+            }
 
             // Handle build mode
             if app.is_build_mode() {
@@ -675,6 +1032,9 @@ fn run_with_mode(_base_path: PathBuf, config: &Config, manage_mode: bool) -> Res
     let mode = if manage_mode { AppMode::Management } else { AppMode::QuickSelect };
     let mut app = TUIApp::new_with_mode_and_config(config, mode)?;
     let event_handler = EventHandler::new();
+    
+    // Live reload is best effort; without a watcher the TUI still refreshes after its own actions
+    let watcher = StorageWatcher::new(config.storage_path()).ok();
 
     // Main loop
     loop {
@@ -685,35 +1045,46 @@ fn run_with_mode(_base_path: PathBuf, config: &Config, manage_mode: bool) -> Res
             screen.render(f, f.size());
             
             // Handle build mode rendering in the same draw call
-            if app.is_build_mode() {
-                if let Some(panel) = app.get_interactive_build_panel_mut() {
-                    panel.render(f, f.size());
-                }
+            if app.is_build_mode()
+                && let Some(panel) = app.get_interactive_build_panel_mut() {
+                panel.render(f, f.size());
             }
             
             // Handle config mode rendering
-            if app.is_config_mode() {
-                if let Some(config_screen) = app.get_config_screen() {
-                    config_screen.render(f, f.size());
-                }
+            if app.is_config_mode()
+                && let Some(config_screen) = app.get_config_screen() {
+                config_screen.render(f, f.size());
             }
         })?;
 
-        // Handle events
-        if let Ok(event) = event::read() {
+        // Handle events, waking up periodically to pick up library changes
+        if event::poll(WATCH_POLL_INTERVAL)?
+            && let Ok(event) = event::read() {
             event_handler.handle_event(&mut app, event)?;
         }
+        
+        // Reload when prompts change on disk outside the TUI
+        if let Some(watcher) = &watcher
+            && watcher.has_changes()
+            && let Err(e) = app.refresh_from_disk() {
+            app.set_error(format!("Failed to reload prompts: {}", e));
+        }
+        app.show_warnings();
 
         // Handle pending actions that require exiting TUI temporarily
         if let Some(action) = app.take_pending_action() {
             match action {
-                crate::presentation::tui::app::PendingAction::Edit => {
+                crate::presentation::tui::app::PendingAction::Edit
+                | crate::presentation::tui::app::PendingAction::EditAtLine(_) => {
                     // Exit TUI temporarily
                     disable_raw_mode()?;
                     execute!(io::stdout(), terminal::LeaveAlternateScreen)?;
                     
                     // Edit the prompt
-                    let result = app.edit_selected();
+                    let result = match action {
+                        crate::presentation::tui::app::PendingAction::EditAtLine(line) => app.edit_selected_at_line(line),
+                        _ => app.edit_selected(),
+                    };
                     
                     // Restore TUI
                     enable_raw_mode()?;
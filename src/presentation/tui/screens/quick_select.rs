@@ -1,4 +1,4 @@
-use crate::presentation::tui::app::{TUIApp, AppMode};
+use crate::presentation::tui::app::{TUIApp, AppMode, MAX_SNIPPETS};
 use crate::presentation::tui::components::search::HighlightedText;
 use crate::presentation::tui::components::input_field::InputField;
 use ratatui::{
@@ -49,12 +49,9 @@ impl<'a> QuickSelectScreen<'a> {
         };
         
         // Add active filters to header if any
-        let active_filters = self.app.get_active_tag_filters();
-        let header_text = if active_filters.is_empty() {
-            mode_text.to_string()
-        } else {
-            let filter_tags: Vec<String> = active_filters.iter().cloned().collect();
-            format!("{} | Filtering by: {}", mode_text, filter_tags.join(", "))
+        let header_text = match self.app.tag_filter_summary() {
+            Some(filters) => format!("{} | Filtering by: {}", mode_text, filters),
+            None => mode_text.to_string(),
         };
         
         let header = Paragraph::new(header_text)
@@ -123,12 +120,19 @@ impl<'a> QuickSelectScreen<'a> {
         f.render_widget(mode_selector, footer_chunks[0]);
         
         // Commands box
+        let marked_text;
         let commands_text = if self.app.is_search_active() {
-            "Type to search  Enter: Select  Esc: Cancel search"
+            "Type to search, e.g. tag:rust -tag:draft content:todo  Tab: Next match  Enter: Select  Esc: Cancel search"
+        } else if self.app.has_marks() {
+            marked_text = format!(
+                "{} marked  Space: Mark  V: Mark range  d: Delete  t: Tags  s: Star  p: Type  x: Export  y: Copy  Esc: Unmark",
+                self.app.marked_count()
+            );
+            &marked_text
         } else {
             match self.app.mode() {
                 AppMode::QuickSelect => "↑↓: Navigate  Enter: Copy  s: Star  /: Search  f: Filter  F: Clear Filters  Esc: Exit",
-                AppMode::Management => "↑↓: Navigate  e: Edit  d: Delete  n: New  s: Star  t: Tags  f: Filter  F: Clear  /: Search  Esc: Exit",
+                AppMode::Management => "↑↓: Navigate  Space: Mark  e: Edit  d: Delete  n: New  s: Star  t: Tags  p: Type  h: History  T: Trash  f: Filter  F: Clear  /: Search  Esc: Exit",
                 AppMode::Build => "↑↓: Navigate  Space: Select  Enter: Combine  Esc: Back",
                 AppMode::Config => "Tab: Switch fields  Ctrl+S: Save  Esc: Exit",
             }
@@ -149,10 +153,27 @@ impl<'a> QuickSelectScreen<'a> {
         
         // Render type prompts dialog if showing
         if self.app.is_showing_type_prompts_dialog() {
-            let type_prompts_dialog = crate::presentation::tui::components::TypePromptsDialog;
+            let type_prompts_dialog = crate::presentation::tui::components::TypePromptsDialog::new(
+                self.app.get_config().prompt_types(),
+            );
             type_prompts_dialog.render(f, area);
         }
         
+        // Render history dialog if showing (below the restore confirmation)
+        if let Some(history_dialog) = self.app.get_history_dialog() {
+            history_dialog.render(f, area);
+        }
+        
+        // Render recently deleted view if showing
+        if let Some(trash_dialog) = self.app.get_trash_dialog() {
+            trash_dialog.render(f, area);
+        }
+        
+        // Render variable fill-in form if showing
+        if let Some(variable_dialog) = self.app.get_variable_dialog() {
+            variable_dialog.render(f, area);
+        }
+        
         // Render confirmation dialog if showing
         if let Some(dialog) = self.app.get_confirmation_dialog() {
             dialog.render(f, area);
@@ -277,25 +298,39 @@ impl<'a> QuickSelectScreen<'a> {
         let prompts = self.app.get_filtered_prompts();
         
         let search_query = if self.app.is_search_active() && !self.app.get_search_query().is_empty() {
-            Some(self.app.get_search_query())
+            Some(self.app.search_highlight_terms())
         } else {
             None
         };
         
         let highlighter = HighlightedText::new();
+        let has_marks = self.app.has_marks();
+        let mut list_state = self.app.get_list_state();
+        let selected_index = list_state.selected();
         
         let items: Vec<ListItem> = prompts
             .iter()
-            .map(|p| {
+            .enumerate()
+            .map(|(index, p)| {
                 // Check if prompt is starred
                 let is_starred = p.tags.iter().any(|t| t == "starred");
                 let star_prefix = if is_starred { "⭐ " } else { "   " };
                 
-                if let Some(query) = search_query {
-                    let highlighted = highlighter.highlight(&p.name, query);
-                    let mut spans: Vec<Span> = vec![
-                        Span::styled(star_prefix, Style::default().fg(Color::Yellow))
-                    ];
+                // Marked prompts get a check in front while anything is marked
+                let mark_prefix = has_marks.then(|| {
+                    let mark = if self.app.is_marked(p) { "✓ " } else { "  " };
+                    Span::styled(mark, Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD))
+                });
+                
+                // Show the folder of nested prompts as a dimmed prefix
+                let folder_prefix = p.folder()
+                    .map(|folder| Span::styled(format!("{}/", folder), Style::default().fg(Color::DarkGray)));
+                
+                if let Some(query) = &search_query {
+                    let highlighted = highlighter.highlight_fuzzy(&p.name, query);
+                    let mut spans: Vec<Span> = mark_prefix.into_iter().collect();
+                    spans.push(Span::styled(star_prefix, Style::default().fg(Color::Yellow)));
+                    spans.extend(folder_prefix);
                     spans.extend(highlighted.segments
                         .into_iter()
                         .map(|seg| {
@@ -305,24 +340,49 @@ impl<'a> QuickSelectScreen<'a> {
                                 Span::raw(seg.text)
                             }
                         }));
-                    ListItem::new(Line::from(spans))
+                    
+                    // Content matches go under the result as `line:column  snippet`
+                    let mut lines = vec![Line::from(spans)];
+                    let matches = self.app.get_content_matches(p);
+                    for (match_index, found) in matches.iter().enumerate().take(MAX_SNIPPETS) {
+                        let is_selected = selected_index == Some(index) && self.app.selected_snippet() == Some(match_index);
+                        let style = if is_selected {
+                            Style::default().fg(Color::Black).bg(Color::Cyan)
+                        } else {
+                            Style::default().fg(Color::DarkGray)
+                        };
+                        lines.push(Line::from(Span::styled(
+                            format!("      {}:{}  {}", found.line, found.column, found.snippet),
+                            style,
+                        )));
+                    }
+                    if matches.len() > MAX_SNIPPETS {
+                        lines.push(Line::from(Span::styled(
+                            format!("      … {} more", matches.len() - MAX_SNIPPETS),
+                            Style::default().fg(Color::DarkGray),
+                        )));
+                    }
+                    ListItem::new(lines)
                 } else {
-                    ListItem::new(Line::from(vec![
-                        Span::styled(star_prefix, Style::default().fg(Color::Yellow)),
-                        Span::raw(&p.name)
-                    ]))
+                    let mut spans: Vec<Span> = mark_prefix.into_iter().collect();
+                    spans.push(Span::styled(star_prefix, Style::default().fg(Color::Yellow)));
+                    spans.extend(folder_prefix);
+                    spans.push(Span::raw(&p.name));
+                    ListItem::new(Line::from(spans))
                 }
             })
             .collect();
 
-        let title = if !self.app.get_active_tag_filters().is_empty() {
-            let filters: Vec<String> = self.app.get_active_tag_filters().iter().cloned().collect();
-            format!("Prompts (tags: {}) - {} results", filters.join(", "), prompts.len())
+        let mut title = if let Some(filters) = self.app.tag_filter_summary() {
+            format!("Prompts (tags: {}) - {} results", filters, prompts.len())
         } else if self.app.is_search_active() && !self.app.get_search_query().is_empty() {
             format!("Prompts (filtered: {})", prompts.len())
         } else {
             "Prompts".to_string()
         };
+        if has_marks {
+            title.push_str(&format!(" - {} marked", self.app.marked_count()));
+        }
 
         let list = List::new(items)
             .block(Block::default()
@@ -336,7 +396,6 @@ impl<'a> QuickSelectScreen<'a> {
                     .add_modifier(Modifier::BOLD),
             );
 
-        let mut list_state = self.app.get_list_state();
         f.render_stateful_widget(list, area, &mut list_state);
     }
 
@@ -370,6 +429,12 @@ impl<'a> QuickSelectScreen<'a> {
     fn render_search_bar(&self, f: &mut Frame, area: Rect) {
         let search_query = self.app.get_search_query();
         
+        // Parse errors replace the title so they show next to the query being typed
+        let (title, color) = match self.app.search_error() {
+            Some(error) => (format!("Search - {}", error), Color::Red),
+            None => ("Search (Esc to cancel)".to_string(), Color::Yellow),
+        };
+        
         let search_field = InputField::new(search_query)
             .prefix("Search: ")
             .show_cursor(true)
@@ -377,8 +442,8 @@ impl<'a> QuickSelectScreen<'a> {
             .block(Block::default()
                 .borders(Borders::ALL)
                 .border_type(ratatui::widgets::BorderType::Rounded)
-                .title("Search (Esc to cancel)")
-                .border_style(Style::default().fg(Color::Yellow)));
+                .title(title)
+                .border_style(Style::default().fg(color)));
         
         f.render_widget(search_field, area);
     }
@@ -103,6 +103,9 @@ impl UIState {
                             ConfirmationAction::Overwrite(_path) => {
                                 // Handle overwrite in the future
                             }
+                            ConfirmationAction::Restore(_, _) | ConfirmationAction::Bulk(_, _) => {
+                                // Restores and bulk actions are handled directly by TUIApp
+                            }
                         }
                     }
                     self.confirmation_message = None;
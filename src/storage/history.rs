@@ -0,0 +1,89 @@
+use anyhow::Result;
+use chrono::{Local, TimeZone};
+use std::path::{Path, PathBuf};
+use crate::application::models::Revision;
+use crate::utils::constants::HISTORY_DIR;
+
+/// Stores numbered snapshots of prompt files under `.history/<prompt path>/`
+pub struct HistoryStore {
+    root: PathBuf,
+}
+
+impl HistoryStore {
+    pub fn new(base_path: &Path) -> Self {
+        Self { root: base_path.join(HISTORY_DIR) }
+    }
+    
+    /// Saves `content` as the next revision of `file_path`, unless it matches the latest one
+    pub fn snapshot(&self, file_path: &str, content: &str) -> Result<Option<Revision>> {
+        let revisions = self.list(file_path)?;
+        if let Some(latest) = revisions.last()
+            && self.read(file_path, latest.number)?.as_deref() == Some(content) {
+            return Ok(None);
+        }
+        
+        let number = revisions.last().map(|r| r.number + 1).unwrap_or(1);
+        let created_at = Local::now();
+        let dir = self.revision_dir(file_path);
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join(format!("{:06}-{}.md", number, created_at.timestamp())), content)?;
+        
+        Ok(Some(Revision {
+            number,
+            created_at,
+            size: content.len() as u64,
+        }))
+    }
+    
+    /// Lists the revisions of `file_path`, oldest first
+    pub fn list(&self, file_path: &str) -> Result<Vec<Revision>> {
+        Ok(self.revision_files(file_path)?
+            .into_iter()
+            .map(|(revision, _)| revision)
+            .collect())
+    }
+    
+    /// Reads a revision, returning None if it does not exist
+    pub fn read(&self, file_path: &str, number: u32) -> Result<Option<String>> {
+        match self.revision_files(file_path)?.into_iter().find(|(r, _)| r.number == number) {
+            Some((_, path)) => Ok(Some(std::fs::read_to_string(path)?)),
+            None => Ok(None),
+        }
+    }
+    
+    fn revision_dir(&self, file_path: &str) -> PathBuf {
+        self.root.join(file_path.strip_suffix(".md").unwrap_or(file_path))
+    }
+    
+    fn revision_files(&self, file_path: &str) -> Result<Vec<(Revision, PathBuf)>> {
+        let dir = self.revision_dir(file_path);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        
+        let mut revisions = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            // File names look like `000003-1760600000.md`
+            let Some((number, timestamp)) = path.file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.split_once('-')) else {
+                continue;
+            };
+            let (Ok(number), Ok(timestamp)) = (number.parse::<u32>(), timestamp.parse::<i64>()) else {
+                continue;
+            };
+            let Some(created_at) = Local.timestamp_opt(timestamp, 0).single() else {
+                continue;
+            };
+            let size = std::fs::metadata(&path)?.len();
+            revisions.push((Revision { number, created_at, size }, path));
+        }
+        
+        revisions.sort_by_key(|(revision, _)| revision.number);
+        Ok(revisions)
+    }
+}
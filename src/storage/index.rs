@@ -0,0 +1,137 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+use crate::application::models::PromptMetadata;
+use crate::utils::constants::INDEX_FILE;
+
+/// Bump whenever the on-disk layout or the metadata extraction changes
+const INDEX_VERSION: u32 = 3;
+
+/// Cached metadata for a single prompt file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Modification time in nanoseconds since the unix epoch
+    pub modified: u64,
+    pub size: u64,
+    /// Hex encoded sha256 of the file content
+    pub hash: String,
+    pub metadata: PromptMetadata,
+}
+
+/// On-disk index of prompt metadata keyed by path relative to the prompts directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptIndex {
+    version: u32,
+    entries: BTreeMap<String, IndexEntry>,
+    #[serde(skip)]
+    dirty: bool,
+}
+
+impl Default for PromptIndex {
+    fn default() -> Self {
+        Self {
+            version: INDEX_VERSION,
+            entries: BTreeMap::new(),
+            dirty: false,
+        }
+    }
+}
+
+impl PromptIndex {
+    /// Loads the index from the storage root, starting empty if it is missing, corrupt or outdated
+    pub fn load(base_path: &Path) -> Self {
+        std::fs::read_to_string(base_path.join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_default()
+    }
+    
+    /// Writes the index back if anything changed since it was loaded
+    pub fn save(&mut self, base_path: &Path) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let content = serde_json::to_string(self)?;
+        std::fs::write(base_path.join(INDEX_FILE), content)?;
+        self.dirty = false;
+        Ok(())
+    }
+    
+    pub fn get(&self, file_path: &str) -> Option<&IndexEntry> {
+        self.entries.get(file_path)
+    }
+    
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    
+    /// Returns the cached entry if the file's mtime and size still match
+    pub fn fresh_entry(&self, file_path: &str, stamp: FileStamp) -> Option<&IndexEntry> {
+        self.entries.get(file_path)
+            .filter(|entry| entry.modified == stamp.modified && entry.size == stamp.size)
+    }
+    
+    /// Returns the cached entry if its content hash matches, refreshing the stored stamp
+    pub fn entry_with_hash(&mut self, file_path: &str, hash: &str, stamp: FileStamp) -> Option<&IndexEntry> {
+        let entry = self.entries.get_mut(file_path).filter(|entry| entry.hash == hash)?;
+        if entry.modified != stamp.modified || entry.size != stamp.size {
+            entry.modified = stamp.modified;
+            entry.size = stamp.size;
+            self.dirty = true;
+        }
+        Some(entry)
+    }
+    
+    pub fn insert(&mut self, file_path: String, hash: String, stamp: FileStamp, metadata: PromptMetadata) {
+        self.entries.insert(file_path, IndexEntry {
+            modified: stamp.modified,
+            size: stamp.size,
+            hash,
+            metadata,
+        });
+        self.dirty = true;
+    }
+    
+    /// Drops entries for files that no longer exist
+    pub fn retain_paths(&mut self, existing: &HashSet<String>) {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| existing.contains(path));
+        if self.entries.len() != before {
+            self.dirty = true;
+        }
+    }
+}
+
+/// Cheap change detection data taken from file metadata
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: u64,
+    pub size: u64,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Result<Self> {
+        let metadata = std::fs::metadata(path)?;
+        let modified = metadata.modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        Ok(Self { modified, size: metadata.len() })
+    }
+}
+
+/// Hex encoded sha256 of `content`
+pub fn content_hash(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
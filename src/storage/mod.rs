@@ -1,15 +1,39 @@
+pub mod history;
+pub mod index;
+pub mod recipes;
+pub mod trash;
+
 use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use crate::application::models::PromptMetadata;
+use crate::application::models::{PromptMetadata, PromptType};
 use crate::utils::constants::PROMPTS_DIR;
+use crate::utils::frontmatter::{FrontmatterDocument, FrontmatterUpdater};
+use index::{content_hash, FileStamp, PromptIndex};
 
 pub struct FileSystem {
     base_path: PathBuf,
+    /// Problems met while listing prompts, kept until `take_warnings`
+    warnings: RefCell<Vec<String>>,
 }
 
 impl FileSystem {
     pub fn new(base_path: PathBuf) -> Self {
-        Self { base_path }
+        Self { base_path, warnings: RefCell::new(Vec::new()) }
+    }
+
+    /// Returns and clears the warnings gathered so far
+    pub fn take_warnings(&self) -> Vec<String> {
+        self.warnings.take()
+    }
+
+    fn warn(&self, warning: String) {
+        let mut warnings = self.warnings.borrow_mut();
+        if !warnings.contains(&warning) {
+            warnings.push(warning);
+        }
     }
 
     pub fn base_path(&self) -> &PathBuf {
@@ -42,56 +66,122 @@ impl FileSystem {
         self.base_path.join(relative_path)
     }
 
+    /// Lists all prompts, answering from the metadata index and only re-parsing changed files
     pub fn list_prompts(&self) -> Result<Vec<PromptMetadata>> {
         let prompts_dir = self.base_path.join(PROMPTS_DIR);
         let mut prompts = Vec::new();
         
-
+        let mut files = Vec::new();
         if prompts_dir.exists() {
-            for entry in std::fs::read_dir(&prompts_dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.extension().and_then(|s| s.to_str()) == Some("md") {
-                    let content = std::fs::read_to_string(&path)?;
-                    let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-                    
-                    // Extract metadata from content
-                    let name = extract_name_from_content(&content)
-                        .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(|s| s.to_string()))
-                        .unwrap_or_else(|| file_name.to_string());
-                    
-                    let tags = extract_tags_from_content(&content);
-                    let type_option = extract_type_from_content(&content);
-                    
-                    // If no type or invalid type, update the file with default type
-                    let prompt_type = if let Some(prompt_type) = type_option {
-                        prompt_type
-                    } else {
-                        // Update file to add type: "whole"
-                        if let Ok(updated_content) = crate::utils::frontmatter::FrontmatterUpdater::ensure_type(&content, &name, type_option) {
-                            // Write the updated content back to file
-                            // Calculate relative path from base_path
-                            let relative_path = path.strip_prefix(&self.base_path)
-                                .unwrap_or(&path);
-                            if let Err(e) = self.write(relative_path, &updated_content) {
-                                eprintln!("Warning: Failed to update type in {}: {}", file_name, e);
-                            }
-                        }
-                        crate::application::models::PromptType::default()
-                    };
-                    
-                    prompts.push(PromptMetadata {
-                        name,
-                        file_path: file_name.to_string(),
-                        tags,
-                        prompt_type,
-                    });
+            collect_prompt_files(&prompts_dir, &mut files)?;
+        }
+        
+        let mut index = PromptIndex::load(&self.base_path);
+        let mut seen = HashSet::new();
+
+        for path in files {
+            // Store the path relative to the prompts directory so nested prompts stay addressable
+            let relative_file_path = path.strip_prefix(&prompts_dir)
+                .unwrap_or(&path)
+                .components()
+                .filter_map(|c| c.as_os_str().to_str())
+                .collect::<Vec<_>>()
+                .join("/");
+            
+            let stamp = FileStamp::of(&path)?;
+            let (mut metadata, modified) = if let Some(entry) = index.fresh_entry(&relative_file_path, stamp) {
+                (entry.metadata.clone(), entry.modified)
+            } else {
+                let content = std::fs::read_to_string(&path)?;
+                let hash = content_hash(&content);
+                
+                match index.entry_with_hash(&relative_file_path, &hash, stamp) {
+                    Some(entry) => (entry.metadata.clone(), entry.modified),
+                    None => {
+                        let (metadata, updated_content) = self.parse_prompt(&path, &relative_file_path, &content);
+                        
+                        // The type write-back changes the file, so index the content that is now on disk
+                        let (hash, stamp) = match updated_content {
+                            Some(updated) => (content_hash(&updated), FileStamp::of(&path)?),
+                            None => (hash, stamp),
+                        };
+                        index.insert(relative_file_path.clone(), hash, stamp, metadata.clone());
+                        (metadata, stamp.modified)
+                    }
                 }
-            }
+            };
+            // Taken from the file rather than the index, whose entries can outlive a touch
+            metadata.modified_at = Some(Local.timestamp_nanos(modified as i64));
+            
+            prompts.push(metadata);
+            seen.insert(relative_file_path);
         }
+        
+        index.retain_paths(&seen);
+        // The index is only a cache, so failing to persist it (e.g. read-only storage) is not an error
+        let _ = index.save(&self.base_path);
 
         Ok(prompts)
     }
+    
+    /// Extracts metadata from a prompt file, returning the rewritten content if a default type was added
+    fn parse_prompt(&self, path: &Path, relative_file_path: &str, content: &str) -> (PromptMetadata, Option<String>) {
+        let file_name = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        
+        // Extract metadata from content
+        let frontmatter = match FrontmatterDocument::parse(content).and_then(|doc| doc.metadata()) {
+            Ok(frontmatter) => Some(frontmatter),
+            Err(e) => {
+                self.warn(format!("Failed to parse frontmatter in {}: {}", relative_file_path, e));
+                None
+            }
+        };
+        
+        let name = frontmatter.as_ref()
+            .and_then(|fm| fm.name.clone())
+            .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(|s| s.to_string()))
+            .unwrap_or_else(|| file_name.to_string());
+        
+        let tags = frontmatter.as_ref().map(|fm| fm.tags.clone()).unwrap_or_default();
+        let description = frontmatter.as_ref().and_then(|fm| fm.description.clone()).unwrap_or_default();
+        let created_at = frontmatter.as_ref()
+            .and_then(|fm| fm.created_at.as_deref())
+            .and_then(parse_timestamp);
+        let type_option = frontmatter.as_ref()
+            .and_then(|fm| fm.prompt_type.as_deref())
+            .and_then(parse_prompt_type);
+        
+        // If no type or invalid type, update the file with default type.
+        // Files whose frontmatter could not be parsed are left untouched.
+        let mut updated = None;
+        let prompt_type = if let Some(prompt_type) = type_option {
+            prompt_type
+        } else {
+            if frontmatter.is_some()
+                && let Ok(updated_content) = FrontmatterUpdater::ensure_type(content, &name, type_option) {
+                // Write the updated content back to file
+                // Calculate relative path from base_path
+                let relative_path = path.strip_prefix(&self.base_path)
+                    .unwrap_or(path);
+                match self.write(relative_path, &updated_content) {
+                    Ok(()) => updated = Some(updated_content),
+                    Err(e) => self.warn(format!("Failed to update type in {}: {}", relative_file_path, e)),
+                }
+            }
+            PromptType::default()
+        };
+        
+        let metadata = PromptMetadata {
+            name,
+            file_path: relative_file_path.to_string(),
+            tags,
+            prompt_type,
+            description,
+            created_at,
+            modified_at: None,
+        };
+        (metadata, updated)
+    }
 
     pub fn delete(&self, relative_path: &Path) -> Result<()> {
         let full_path = self.base_path.join(relative_path);
@@ -100,71 +190,51 @@ impl FileSystem {
     }
 }
 
-fn extract_name_from_content(content: &str) -> Option<String> {
-    // Very simple front-matter parsing for now
-    if content.starts_with("---\n") {
-        let parts: Vec<&str> = content.splitn(3, "---\n").collect();
-        if parts.len() >= 2 {
-            for line in parts[1].lines() {
-                if line.starts_with("name: ") {
-                    let name = line.trim_start_matches("name: ").trim().trim_matches('"');
-                    return Some(name.to_string());
-                }
-            }
+/// Recursively collects `*.md` files below `dir`, skipping hidden files and folders
+fn collect_prompt_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        
+        let is_hidden = path.file_name()
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+        
+        if path.is_dir() {
+            collect_prompt_files(&path, files)?;
+        } else if path.extension().and_then(|s| s.to_str()) == Some("md") {
+            files.push(path);
         }
     }
-    None
+    Ok(())
 }
 
-fn extract_tags_from_content(content: &str) -> Vec<String> {
-    if let Some(start) = content.find("---") {
-        if let Some(end) = content[start + 3..].find("---") {
-            let frontmatter = &content[start + 3..start + 3 + end];
-            
-            // Look for tags line
-            for line in frontmatter.lines() {
-                if line.trim().starts_with("tags:") {
-                    let tags_part = line.trim_start_matches("tags:").trim();
-                    
-                    // Parse array format [tag1, tag2]
-                    if tags_part.starts_with('[') && tags_part.ends_with(']') {
-                        let tags_str = &tags_part[1..tags_part.len() - 1];
-                        return tags_str
-                            .split(',')
-                            .map(|s| s.trim().trim_matches('"').to_string())
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                    }
-                }
-            }
-        }
+pub fn extract_type_from_content(content: &str) -> Option<PromptType> {
+    let document = FrontmatterDocument::parse(content).ok()?;
+    let frontmatter = document.metadata().ok()?;
+    frontmatter.prompt_type.as_deref().and_then(parse_prompt_type)
+}
+
+/// Maps a frontmatter `type` value to a PromptType, returning None for blank values.
+/// Types the config does not declare are kept as written.
+fn parse_prompt_type(value: &str) -> Option<PromptType> {
+    if value.trim().is_empty() {
+        return None;
     }
-    Vec::new()
+    Some(PromptType::new(value))
 }
 
-pub fn extract_type_from_content(content: &str) -> Option<crate::application::models::PromptType> {
-    if let Some(start) = content.find("---") {
-        if let Some(end) = content[start + 3..].find("---") {
-            let frontmatter = &content[start + 3..start + 3 + end];
-            
-            // Look for type line
-            for line in frontmatter.lines() {
-                if line.trim().starts_with("type:") {
-                    let type_part = line.trim_start_matches("type:").trim().trim_matches('"');
-                    
-                    // Map string to PromptType enum
-                    return match type_part.to_lowercase().as_str() {
-                        "instruction" => Some(crate::application::models::PromptType::Instruction),
-                        "context" => Some(crate::application::models::PromptType::Context),
-                        "input_indicator" => Some(crate::application::models::PromptType::InputIndicator),
-                        "output_indicator" => Some(crate::application::models::PromptType::OutputIndicator),
-                        "etc" => Some(crate::application::models::PromptType::Etc),
-                        "whole" => Some(crate::application::models::PromptType::Whole),
-                        _ => None, // Invalid type value
-                    };
-                }
-            }
-        }
+/// Reads a frontmatter timestamp: RFC 3339 as fink writes it, or a local `YYYY-MM-DD[ HH:MM:SS]`
+fn parse_timestamp(value: &str) -> Option<DateTime<Local>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.with_timezone(&Local));
     }
-    None // No type field found
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d").map(|date| date.and_time(Default::default())))
+        .ok()?;
+    Local.from_local_datetime(&naive).earliest()
 }
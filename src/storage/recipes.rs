@@ -0,0 +1,73 @@
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+use crate::application::models::Recipe;
+use crate::utils::constants::RECIPES_DIR;
+
+/// Keeps saved build recipes as `recipes/<name>.json` under the storage root
+pub struct RecipeStore {
+    root: PathBuf,
+}
+
+impl RecipeStore {
+    pub fn new(base_path: &Path) -> Self {
+        Self {
+            root: base_path.join(RECIPES_DIR),
+        }
+    }
+    
+    /// Lists saved recipes sorted by name, skipping files that cannot be read
+    pub fn list(&self) -> Result<Vec<Recipe>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+        
+        let mut recipes = Vec::new();
+        for dir_entry in std::fs::read_dir(&self.root)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+            if let Ok(content) = std::fs::read_to_string(&path)
+                && let Ok(recipe) = serde_json::from_str::<Recipe>(&content) {
+                recipes.push(recipe);
+            }
+        }
+        
+        recipes.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(recipes)
+    }
+    
+    pub fn load(&self, name: &str) -> Result<Option<Recipe>> {
+        let path = self.recipe_path(name)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?))
+    }
+    
+    /// Writes a recipe, replacing any saved under the same name
+    pub fn save(&self, recipe: &Recipe) -> Result<()> {
+        std::fs::create_dir_all(&self.root)?;
+        std::fs::write(self.recipe_path(&recipe.name)?, serde_json::to_string_pretty(recipe)?)?;
+        Ok(())
+    }
+    
+    /// Removes a recipe, returning whether it existed
+    pub fn delete(&self, name: &str) -> Result<bool> {
+        let path = self.recipe_path(name)?;
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(path)?;
+        Ok(true)
+    }
+    
+    /// Path of a recipe file; names with folders or a leading dot are refused so lookups stay
+    /// inside the recipes directory
+    fn recipe_path(&self, name: &str) -> Result<PathBuf> {
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Err(anyhow!("'{}' is not a valid recipe name", name));
+        }
+        Ok(self.root.join(format!("{}.json", name)))
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{Result, anyhow};
+use chrono::{Duration, Local};
+use std::path::{Path, PathBuf};
+use crate::application::models::TrashEntry;
+use crate::utils::constants::{PROMPTS_DIR, TRASH_DIR};
+
+/// Holds deleted prompt files as `.trash/<id>.md` next to a `.trash/<id>.json` record
+pub struct TrashStore {
+    prompts_dir: PathBuf,
+    root: PathBuf,
+}
+
+impl TrashStore {
+    pub fn new(base_path: &Path) -> Self {
+        Self {
+            prompts_dir: base_path.join(PROMPTS_DIR),
+            root: base_path.join(TRASH_DIR),
+        }
+    }
+    
+    /// Moves a prompt file into the trash
+    pub fn trash(&self, file_path: &str) -> Result<TrashEntry> {
+        std::fs::create_dir_all(&self.root)?;
+        
+        let deleted_at = Local::now();
+        let stamp = deleted_at.format("%Y%m%d%H%M%S%3f").to_string();
+        let mut id = stamp.clone();
+        let mut suffix = 1;
+        while self.record_path(&id).exists() {
+            suffix += 1;
+            id = format!("{}-{}", stamp, suffix);
+        }
+        
+        let entry = TrashEntry {
+            id,
+            original_path: file_path.to_string(),
+            deleted_at,
+        };
+        
+        // Write the record first so a trashed file is never left without one
+        std::fs::write(self.record_path(&entry.id), serde_json::to_string_pretty(&entry)?)?;
+        if let Err(e) = std::fs::rename(self.prompts_dir.join(file_path), self.file_path(&entry.id)) {
+            let _ = std::fs::remove_file(self.record_path(&entry.id));
+            return Err(e.into());
+        }
+        Ok(entry)
+    }
+    
+    /// Lists trashed prompts, most recently deleted first
+    pub fn list(&self) -> Result<Vec<TrashEntry>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+        
+        let mut entries = Vec::new();
+        for dir_entry in std::fs::read_dir(&self.root)? {
+            let path = dir_entry?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+            // Skip records that are unreadable or whose file has gone missing
+            let Ok(entry) = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<TrashEntry>(&content)?)) else {
+                continue;
+            };
+            if self.file_path(&entry.id).exists() {
+                entries.push(entry);
+            }
+        }
+        
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+        Ok(entries)
+    }
+    
+    /// Moves a trashed prompt back to its original path
+    pub fn restore(&self, id: &str) -> Result<TrashEntry> {
+        let entry = self.list()?
+            .into_iter()
+            .find(|entry| entry.id == id)
+            .ok_or_else(|| anyhow!("No trash entry with id {}", id))?;
+        
+        let destination = self.prompts_dir.join(&entry.original_path);
+        if destination.exists() {
+            return Err(anyhow!("A prompt already exists at {}", entry.original_path));
+        }
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        
+        std::fs::rename(self.file_path(&entry.id), destination)?;
+        std::fs::remove_file(self.record_path(&entry.id))?;
+        Ok(entry)
+    }
+    
+    /// Permanently removes every trashed prompt, returning how many were removed
+    pub fn empty(&self) -> Result<usize> {
+        self.purge_where(|_| true)
+    }
+    
+    /// Permanently removes prompts deleted more than `days` days ago
+    pub fn purge_older_than(&self, days: u32) -> Result<usize> {
+        let cutoff = Local::now() - Duration::days(i64::from(days));
+        self.purge_where(|entry| entry.deleted_at < cutoff)
+    }
+    
+    fn purge_where(&self, predicate: impl Fn(&TrashEntry) -> bool) -> Result<usize> {
+        let mut removed = 0;
+        for entry in self.list()?.into_iter().filter(|entry| predicate(entry)) {
+            std::fs::remove_file(self.file_path(&entry.id))?;
+            std::fs::remove_file(self.record_path(&entry.id))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+    
+    fn file_path(&self, id: &str) -> PathBuf {
+        self.root.join(format!("{}.md", id))
+    }
+    
+    fn record_path(&self, id: &str) -> PathBuf {
+        self.root.join(format!("{}.json", id))
+    }
+}
@@ -2,6 +2,8 @@ use std::path::{Path, PathBuf};
 use std::fs;
 use serde::{Deserialize, Serialize};
 use crate::utils::error::{Result, FinkError, StorageError};
+use crate::utils::output_format::OutputFormat;
+use crate::application::models::{PromptType, PromptTypes};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -11,6 +13,19 @@ pub struct Config {
     clipboard_prefix: String,
     #[serde(default)]
     clipboard_postfix: String,
+    /// Days a deleted prompt stays in the trash before it is purged (0 keeps it forever)
+    #[serde(default = "default_trash_retention_days")]
+    trash_retention_days: u32,
+    /// Format used to combine prompts in build mode, updated when another one is picked there
+    #[serde(default)]
+    build_format: OutputFormat,
+    /// Prompt types offered when creating prompts, in build order
+    #[serde(default)]
+    prompt_types: PromptTypes,
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
 }
 
 impl Config {
@@ -46,6 +61,35 @@ impl Config {
         self.clipboard_postfix = postfix;
     }
     
+    pub fn trash_retention_days(&self) -> u32 {
+        self.trash_retention_days
+    }
+    
+    pub fn set_trash_retention_days(&mut self, days: u32) {
+        self.trash_retention_days = days;
+    }
+    
+    pub fn build_format(&self) -> OutputFormat {
+        self.build_format
+    }
+    
+    pub fn set_build_format(&mut self, format: OutputFormat) {
+        self.build_format = format;
+    }
+    
+    pub fn prompt_types(&self) -> &PromptTypes {
+        &self.prompt_types
+    }
+    
+    pub fn set_prompt_types(&mut self, prompt_types: PromptTypes) {
+        self.prompt_types = prompt_types;
+    }
+    
+    /// Types that get a step in build mode, in the order they are combined
+    pub fn build_types(&self) -> Vec<PromptType> {
+        self.prompt_types.build_order()
+    }
+    
     pub fn default_config_path() -> PathBuf {
         // Check for test environment variable first
         if let Ok(test_config_path) = std::env::var("FINK_TEST_CONFIG_PATH") {
@@ -71,13 +115,11 @@ impl Config {
             .map_err(|e| FinkError::Storage(StorageError::ParseError(e.to_string())))?;
         
         // Expand ~ in storage_path
-        if let Some(path_str) = config.storage_path.to_str() {
-            if path_str.starts_with("~/") {
-                if let Some(home) = dirs::home_dir() {
-                    let expanded = path_str.replacen("~/", &format!("{}/", home.display()), 1);
-                    config.storage_path = PathBuf::from(expanded);
-                }
-            }
+        if let Some(path_str) = config.storage_path.to_str()
+            && path_str.starts_with("~/")
+            && let Some(home) = dirs::home_dir() {
+            let expanded = path_str.replacen("~/", &format!("{}/", home.display()), 1);
+            config.storage_path = PathBuf::from(expanded);
         }
         
         // Fix the path if it ends with /prompts (it shouldn't)
@@ -130,6 +172,45 @@ clipboard_prefix = ""
 
 # Text to append to copied prompts
 clipboard_postfix = ""
+
+# Days deleted prompts are kept in the trash (0 keeps them forever)
+trash_retention_days = 30
+
+# Format for combined prompts in build mode: plain, markdown, xml or json
+build_format = "plain"
+
+# Prompt types, in build order. The id is the value of `type:` in a prompt's
+# frontmatter; types with build = false get no step in build mode.
+[[prompt_types]]
+id = "whole"
+name = "Whole"
+description = "Complete prompt (default)"
+build = false
+
+[[prompt_types]]
+id = "instruction"
+name = "Instruction"
+description = "Task or instruction for the model"
+
+[[prompt_types]]
+id = "context"
+name = "Context"
+description = "External information or context"
+
+[[prompt_types]]
+id = "input_indicator"
+name = "Input Indicator"
+description = "Input or question marker"
+
+[[prompt_types]]
+id = "output_indicator"
+name = "Output Indicator"
+description = "Output format indicator"
+
+[[prompt_types]]
+id = "etc"
+name = "Etc"
+description = "Other component type"
 "#, default_storage.display());
             
             fs::write(config_path, default_config)
@@ -147,6 +228,9 @@ impl Default for Config {
             storage_path: home.join(".fink"),
             clipboard_prefix: String::new(),
             clipboard_postfix: String::new(),
+            trash_retention_days: default_trash_retention_days(),
+            build_format: OutputFormat::default(),
+            prompt_types: PromptTypes::default(),
         }
     }
 }
\ No newline at end of file
@@ -5,4 +5,19 @@ pub const PROMPTS_DIR: &str = "prompts";
 pub const INIT_FLAG_FILE: &str = ".initialized";
 
 /// Directory name for storing templates
-pub const TEMPLATES_DIR: &str = "templates";
\ No newline at end of file
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// File name of the prompt metadata index, stored at the storage root
+pub const INDEX_FILE: &str = ".index.json";
+
+/// Hidden directory under the storage root holding prompt revisions
+pub const HISTORY_DIR: &str = ".history";
+
+/// Hidden directory under the storage root holding deleted prompts
+pub const TRASH_DIR: &str = ".trash";
+
+/// Directory under the storage root holding saved build recipes
+pub const RECIPES_DIR: &str = "recipes";
+
+/// Tag that marks a prompt as starred
+pub const STARRED_TAG: &str = "starred";
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the read commands (`list`, `search`, `get`) print prompts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataFormat {
+    /// Human readable lines, as the commands always printed
+    #[default]
+    Text,
+    /// One pretty printed JSON array, or a single object for `get`
+    Json,
+    /// One compact JSON object per line
+    Ndjson,
+    /// One tab separated row per record, without a header
+    Tsv,
+}
+
+/// The formats meant for scripts, which render any list of records the same way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MachineFormat {
+    Json,
+    Ndjson,
+    Tsv,
+}
+
+impl DataFormat {
+    /// Whether errors should be reported as a JSON object
+    pub fn is_json(self) -> bool {
+        matches!(self, DataFormat::Json | DataFormat::Ndjson)
+    }
+
+    /// The machine readable format, or None for text, which each command prints its own way
+    pub fn machine(self) -> Option<MachineFormat> {
+        match self {
+            DataFormat::Text => None,
+            DataFormat::Json => Some(MachineFormat::Json),
+            DataFormat::Ndjson => Some(MachineFormat::Ndjson),
+            DataFormat::Tsv => Some(MachineFormat::Tsv),
+        }
+    }
+}
+
+impl MachineFormat {
+    /// Renders records as a JSON array, JSON lines, or tab separated rows built by `row`
+    pub fn render<T: Serialize>(self, records: &[T], row: impl Fn(&T) -> Vec<String>) -> serde_json::Result<String> {
+        match self {
+            MachineFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(records)?)),
+            MachineFormat::Ndjson => records
+                .iter()
+                .map(|record| serde_json::to_string(record).map(|line| line + "\n"))
+                .collect(),
+            MachineFormat::Tsv => Ok(records
+                .iter()
+                .map(|record| {
+                    let fields: Vec<String> = row(record).iter().map(|field| tsv_field(field)).collect();
+                    fields.join("\t") + "\n"
+                })
+                .collect()),
+        }
+    }
+}
+
+/// Escapes a value for a TSV cell, so tabs and newlines in prompt bodies cannot split a row
+pub fn tsv_field(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl fmt::Display for DataFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataFormat::Text => write!(f, "text"),
+            DataFormat::Json => write!(f, "json"),
+            DataFormat::Ndjson => write!(f, "ndjson"),
+            DataFormat::Tsv => write!(f, "tsv"),
+        }
+    }
+}
+
+impl FromStr for DataFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "text" | "plain" => Ok(DataFormat::Text),
+            "json" => Ok(DataFormat::Json),
+            "ndjson" | "jsonl" => Ok(DataFormat::Ndjson),
+            "tsv" => Ok(DataFormat::Tsv),
+            _ => Err(format!("unknown format '{}' (expected text, json, ndjson or tsv)", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Record {
+        name: &'static str,
+        body: &'static str,
+    }
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record { name: "a", body: "one\ttwo" },
+            Record { name: "b", body: "line\nbreak" },
+        ]
+    }
+
+    fn row(record: &Record) -> Vec<String> {
+        vec![record.name.to_string(), record.body.to_string()]
+    }
+
+    #[test]
+    fn ndjson_writes_one_object_per_line() {
+        let output = MachineFormat::Ndjson.render(&records(), row).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines, vec![r#"{"name":"a","body":"one\ttwo"}"#, r#"{"name":"b","body":"line\nbreak"}"#]);
+    }
+
+    #[test]
+    fn tsv_escapes_tabs_and_newlines() {
+        assert_eq!(
+            MachineFormat::Tsv.render(&records(), row).unwrap(),
+            "a\tone\\ttwo\nb\tline\\nbreak\n"
+        );
+    }
+
+    #[test]
+    fn only_text_has_no_machine_format() {
+        assert_eq!(DataFormat::Text.machine(), None);
+        assert_eq!(DataFormat::Tsv.machine(), Some(MachineFormat::Tsv));
+    }
+
+    #[test]
+    fn parses_format_names() {
+        assert_eq!("NDJSON".parse::<DataFormat>(), Ok(DataFormat::Ndjson));
+        assert!("yaml".parse::<DataFormat>().is_err());
+    }
+}
@@ -330,7 +330,7 @@ pub fn get_type_specific_prompts() -> HashMap<PromptType, Vec<TypeSpecificPrompt
     let mut prompts = HashMap::new();
     
     // Instruction type prompts
-    prompts.insert(PromptType::Instruction, vec![
+    prompts.insert(PromptType::INSTRUCTION, vec![
         TypeSpecificPrompt {
             name: "step-by-step-guide",
             description: "Creates detailed step-by-step instructions",
@@ -351,7 +351,7 @@ pub fn get_type_specific_prompts() -> HashMap<PromptType, Vec<TypeSpecificPrompt
 - Include any necessary warnings or prerequisites
 - Mention expected outcomes
 - Add troubleshooting tips where relevant"#,
-            prompt_type: PromptType::Instruction,
+            prompt_type: PromptType::INSTRUCTION,
         },
         TypeSpecificPrompt {
             name: "how-to-guide",
@@ -367,7 +367,7 @@ pub fn get_type_specific_prompts() -> HashMap<PromptType, Vec<TypeSpecificPrompt
 6. Suggests next steps or related guides
 
 Keep instructions clear, concise, and actionable."#,
-            prompt_type: PromptType::Instruction,
+            prompt_type: PromptType::INSTRUCTION,
         },
         TypeSpecificPrompt {
             name: "command-instruction",
@@ -390,12 +390,12 @@ $ command --with --options
 - Expected output
 - Common errors and solutions
 - Alternative commands if applicable"#,
-            prompt_type: PromptType::Instruction,
+            prompt_type: PromptType::INSTRUCTION,
         },
     ]);
     
     // Context type prompts
-    prompts.insert(PromptType::Context, vec![
+    prompts.insert(PromptType::CONTEXT, vec![
         TypeSpecificPrompt {
             name: "project-context",
             description: "Provides project background and context",
@@ -423,7 +423,7 @@ Please provide comprehensive context about this project:
 - Related documentation
 - Team structure
 - Development workflow"#,
-            prompt_type: PromptType::Context,
+            prompt_type: PromptType::CONTEXT,
         },
         TypeSpecificPrompt {
             name: "code-context",
@@ -440,7 +440,7 @@ Provide context to understand this code:
 5. **Key Concepts**: What domain knowledge is needed?
 6. **History**: Why was it written this way?
 7. **Constraints**: What limitations or requirements influenced the design?"#,
-            prompt_type: PromptType::Context,
+            prompt_type: PromptType::CONTEXT,
         },
         TypeSpecificPrompt {
             name: "business-context",
@@ -470,12 +470,12 @@ Provide the business context for this initiative:
 - Expected benefits
 - Potential risks
 - Dependencies on other initiatives"#,
-            prompt_type: PromptType::Context,
+            prompt_type: PromptType::CONTEXT,
         },
     ]);
     
     // Input Indicator prompts
-    prompts.insert(PromptType::InputIndicator, vec![
+    prompts.insert(PromptType::INPUT_INDICATOR, vec![
         TypeSpecificPrompt {
             name: "input-format",
             description: "Specifies expected input format",
@@ -505,7 +505,7 @@ Password: [hidden]
 - Follow the specified format exactly
 - Use appropriate data types
 - Include units where applicable"#,
-            prompt_type: PromptType::InputIndicator,
+            prompt_type: PromptType::INPUT_INDICATOR,
         },
         TypeSpecificPrompt {
             name: "data-input",
@@ -530,12 +530,12 @@ Password: [hidden]
 - Arrays and objects must follow nested structure
 
 ### Please provide your data below:"#,
-            prompt_type: PromptType::InputIndicator,
+            prompt_type: PromptType::INPUT_INDICATOR,
         },
     ]);
     
     // Output Indicator prompts
-    prompts.insert(PromptType::OutputIndicator, vec![
+    prompts.insert(PromptType::OUTPUT_INDICATOR, vec![
         TypeSpecificPrompt {
             name: "output-format",
             description: "Specifies expected output format",
@@ -564,7 +564,7 @@ Message: [Detailed message]
 - Markdown for documentation
 - Plain text for simple outputs
 - CSV for tabular data"#,
-            prompt_type: PromptType::OutputIndicator,
+            prompt_type: PromptType::OUTPUT_INDICATOR,
         },
         TypeSpecificPrompt {
             name: "result-output",
@@ -593,12 +593,12 @@ Timestamp: [ISO 8601 timestamp]
 ```
 
 All output will follow this consistent structure for easy parsing and understanding."#,
-            prompt_type: PromptType::OutputIndicator,
+            prompt_type: PromptType::OUTPUT_INDICATOR,
         },
     ]);
     
     // Etc type prompts
-    prompts.insert(PromptType::Etc, vec![
+    prompts.insert(PromptType::ETC, vec![
         TypeSpecificPrompt {
             name: "brainstorm-ideas",
             description: "Brainstorming and ideation helper",
@@ -620,7 +620,7 @@ All output will follow this consistent structure for easy parsing and understand
 - What-if scenarios
 
 Share your topic or challenge, and let's explore creative solutions!"#,
-            prompt_type: PromptType::Etc,
+            prompt_type: PromptType::ETC,
         },
         TypeSpecificPrompt {
             name: "checklist-creator",
@@ -650,7 +650,7 @@ I'll create a comprehensive checklist for your needs:
 - Due dates
 
 What checklist would you like me to create?"#,
-            prompt_type: PromptType::Etc,
+            prompt_type: PromptType::ETC,
         },
     ]);
     
@@ -658,9 +658,15 @@ What checklist would you like me to create?"#,
 }
 
 pub fn initialize_type_specific_prompts(prompts_dir: &Path) -> Result<(), FinkError> {
+    let all_types: Vec<PromptType> = get_type_specific_prompts().into_keys().collect();
+    initialize_type_specific_prompts_for(prompts_dir, &all_types)
+}
+
+/// Writes the type-specific defaults, limited to the given (configured) types
+pub fn initialize_type_specific_prompts_for(prompts_dir: &Path, types: &[PromptType]) -> Result<(), FinkError> {
     let type_prompts = get_type_specific_prompts();
     
-    for (_prompt_type, prompts) in type_prompts.iter() {
+    for (_prompt_type, prompts) in type_prompts.iter().filter(|(prompt_type, _)| types.contains(prompt_type)) {
         for prompt in prompts {
             let file_path = prompts_dir.join(format!("{}.md", prompt.name));
             
@@ -686,14 +692,7 @@ modified_at: "{}"
                     .map(|tag| format!("\"{}\"", tag))
                     .collect::<Vec<_>>()
                     .join(", "),
-                match prompt.prompt_type {
-                    PromptType::Instruction => "instruction",
-                    PromptType::Context => "context",
-                    PromptType::InputIndicator => "input_indicator",
-                    PromptType::OutputIndicator => "output_indicator",
-                    PromptType::Etc => "etc",
-                    PromptType::Whole => "whole",
-                },
+                prompt.prompt_type.as_str(),
                 chrono::Utc::now().to_rfc3339(),
                 chrono::Utc::now().to_rfc3339(),
                 prompt.content
@@ -719,18 +718,18 @@ mod tests {
         let prompts = get_type_specific_prompts();
         
         // Verify we have prompts for each type
-        assert!(prompts.contains_key(&PromptType::Instruction));
-        assert!(prompts.contains_key(&PromptType::Context));
-        assert!(prompts.contains_key(&PromptType::InputIndicator));
-        assert!(prompts.contains_key(&PromptType::OutputIndicator));
-        assert!(prompts.contains_key(&PromptType::Etc));
+        assert!(prompts.contains_key(&PromptType::INSTRUCTION));
+        assert!(prompts.contains_key(&PromptType::CONTEXT));
+        assert!(prompts.contains_key(&PromptType::INPUT_INDICATOR));
+        assert!(prompts.contains_key(&PromptType::OUTPUT_INDICATOR));
+        assert!(prompts.contains_key(&PromptType::ETC));
         
         // Verify counts for each type
-        assert_eq!(prompts.get(&PromptType::Instruction).unwrap().len(), 3);
-        assert_eq!(prompts.get(&PromptType::Context).unwrap().len(), 3);
-        assert_eq!(prompts.get(&PromptType::InputIndicator).unwrap().len(), 2);
-        assert_eq!(prompts.get(&PromptType::OutputIndicator).unwrap().len(), 2);
-        assert_eq!(prompts.get(&PromptType::Etc).unwrap().len(), 2);
+        assert_eq!(prompts.get(&PromptType::INSTRUCTION).unwrap().len(), 3);
+        assert_eq!(prompts.get(&PromptType::CONTEXT).unwrap().len(), 3);
+        assert_eq!(prompts.get(&PromptType::INPUT_INDICATOR).unwrap().len(), 2);
+        assert_eq!(prompts.get(&PromptType::OUTPUT_INDICATOR).unwrap().len(), 2);
+        assert_eq!(prompts.get(&PromptType::ETC).unwrap().len(), 2);
         
         // Total should be 12 prompts
         let total_count: usize = prompts.values().map(|v| v.len()).sum();
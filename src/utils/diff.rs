@@ -0,0 +1,10 @@
+use similar::TextDiff;
+
+/// Renders a unified diff between two texts with three lines of context
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(3)
+        .header(old_label, new_label)
+        .to_string()
+}
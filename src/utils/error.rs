@@ -14,6 +14,14 @@ pub enum PromptError {
     NotFound(String),
     AlreadyExists(String),
     InvalidFormat(String),
+    RevisionNotFound(String, u32),
+    NotInTrash(String),
+    IncludeNotFound(String, String),
+    IncludeCycle(Vec<String>),
+    TemplateNotFound(String),
+    RecipeNotFound(String),
+    TagNotFound(String),
+    TagExists(String),
 }
 
 #[derive(Debug)]
@@ -33,6 +41,7 @@ pub enum ExternalError {
 pub enum ValidationError {
     InvalidInput(&'static str, String),
     MissingRequired(String),
+    UnresolvedVariables(Vec<String>),
 }
 
 impl fmt::Display for FinkError {
@@ -52,6 +61,14 @@ impl fmt::Display for PromptError {
             PromptError::NotFound(name) => write!(f, "Prompt not found: {}", name),
             PromptError::AlreadyExists(name) => write!(f, "Prompt already exists: {}", name),
             PromptError::InvalidFormat(msg) => write!(f, "Invalid prompt format: {}", msg),
+            PromptError::RevisionNotFound(name, revision) => write!(f, "Revision {} not found for prompt: {}", revision, name),
+            PromptError::NotInTrash(name) => write!(f, "Not found in trash: {}", name),
+            PromptError::IncludeNotFound(name, from) => write!(f, "Included prompt not found: {} (referenced from {})", name, from),
+            PromptError::IncludeCycle(chain) => write!(f, "Include cycle: {}", chain.join(" -> ")),
+            PromptError::TemplateNotFound(name) => write!(f, "Template not found: {}", name),
+            PromptError::RecipeNotFound(name) => write!(f, "Recipe not found: {}", name),
+            PromptError::TagNotFound(tag) => write!(f, "No prompt is tagged: {}", tag),
+            PromptError::TagExists(tag) => write!(f, "Tag already in use: {}", tag),
         }
     }
 }
@@ -84,6 +101,9 @@ impl fmt::Display for ValidationError {
             ValidationError::MissingRequired(field) => {
                 write!(f, "Missing required field: {}", field)
             }
+            ValidationError::UnresolvedVariables(names) => {
+                write!(f, "Unresolved template variables: {}", names.join(", "))
+            }
         }
     }
 }
@@ -133,6 +153,58 @@ impl FinkError {
                     name, name
                 )
             }
+            FinkError::Prompt(PromptError::RevisionNotFound(name, revision)) => {
+                format!(
+                    "Prompt '{}' has no revision {}. Run 'fink history {}' to see saved revisions.",
+                    name, revision, name
+                )
+            }
+            FinkError::Prompt(PromptError::NotInTrash(name)) => {
+                format!(
+                    "'{}' is not in the trash. Run 'fink trash list' to see deleted prompts.",
+                    name
+                )
+            }
+            FinkError::Validation(ValidationError::UnresolvedVariables(names)) => {
+                let flags: Vec<String> = names.iter().map(|name| format!("--var {}=...", name)).collect();
+                format!("Provide a value for each variable, e.g. {}", flags.join(" "))
+            }
+            FinkError::Prompt(PromptError::IncludeNotFound(name, from)) => {
+                format!(
+                    "'{}' includes '{}', which does not exist. Fix the {{{{> {}}}}} line in '{}' or create the missing prompt.",
+                    from, name, name, from
+                )
+            }
+            FinkError::Prompt(PromptError::IncludeCycle(chain)) => {
+                format!(
+                    "Prompts include each other in a loop ({}). Remove one of the include lines to break it.",
+                    chain.join(" -> ")
+                )
+            }
+            FinkError::Prompt(PromptError::TemplateNotFound(name)) => {
+                format!(
+                    "Could not find template '{}'. Run 'fink template list' to see available templates or create it with 'fink template new {}'.",
+                    name, name
+                )
+            }
+            FinkError::Prompt(PromptError::RecipeNotFound(name)) => {
+                format!(
+                    "Could not find recipe '{}'. Run 'fink build' to list saved recipes, or save one from build mode.",
+                    name
+                )
+            }
+            FinkError::Prompt(PromptError::TagNotFound(tag)) => {
+                format!(
+                    "No prompt is tagged '{}'. Run 'fink tag list --counts' to see the tags in use.",
+                    tag
+                )
+            }
+            FinkError::Prompt(PromptError::TagExists(tag)) => {
+                format!(
+                    "Tag '{}' is already used by other prompts. Combine the two with 'fink tag merge <tag> --into {}'.",
+                    tag, tag
+                )
+            }
             FinkError::Storage(StorageError::Io(e)) if e.kind() == io::ErrorKind::PermissionDenied => {
                 "Permission denied. Check file permissions or run with appropriate privileges.".to_string()
             }
@@ -140,11 +212,52 @@ impl FinkError {
         }
     }
     
+    /// Stable identifier for the error, reported by `--format json`
+    pub fn code(&self) -> &'static str {
+        match self {
+            FinkError::Prompt(e) => match e {
+                PromptError::NotFound(_) => "prompt_not_found",
+                PromptError::AlreadyExists(_) => "prompt_exists",
+                PromptError::InvalidFormat(_) => "invalid_format",
+                PromptError::RevisionNotFound(_, _) => "revision_not_found",
+                PromptError::NotInTrash(_) => "not_in_trash",
+                PromptError::IncludeNotFound(_, _) => "include_not_found",
+                PromptError::IncludeCycle(_) => "include_cycle",
+                PromptError::TemplateNotFound(_) => "template_not_found",
+                PromptError::RecipeNotFound(_) => "recipe_not_found",
+                PromptError::TagNotFound(_) => "tag_not_found",
+                PromptError::TagExists(_) => "tag_exists",
+            },
+            FinkError::Storage(e) => match e {
+                StorageError::Io(_) => "io_error",
+                StorageError::ParseError(_) => "parse_error",
+                StorageError::InvalidPath(_) => "invalid_path",
+            },
+            FinkError::External(e) => match e {
+                ExternalError::ClipboardError(_) => "clipboard_error",
+                ExternalError::EditorError(_) => "editor_error",
+            },
+            FinkError::Validation(e) => match e {
+                ValidationError::InvalidInput(_, _) => "invalid_input",
+                ValidationError::MissingRequired(_) => "missing_required",
+                ValidationError::UnresolvedVariables(_) => "unresolved_variables",
+            },
+        }
+    }
+    
     pub fn is_recoverable(&self) -> bool {
         matches!(
             self,
             FinkError::Prompt(PromptError::NotFound(_)) |
             FinkError::Prompt(PromptError::AlreadyExists(_)) |
+            FinkError::Prompt(PromptError::RevisionNotFound(_, _)) |
+            FinkError::Prompt(PromptError::NotInTrash(_)) |
+            FinkError::Prompt(PromptError::IncludeNotFound(_, _)) |
+            FinkError::Prompt(PromptError::IncludeCycle(_)) |
+            FinkError::Prompt(PromptError::TemplateNotFound(_)) |
+            FinkError::Prompt(PromptError::RecipeNotFound(_)) |
+            FinkError::Prompt(PromptError::TagNotFound(_)) |
+            FinkError::Prompt(PromptError::TagExists(_)) |
             FinkError::Validation(_)
         )
     }
@@ -1,139 +1,336 @@
 use crate::utils::error::{Result, FinkError, StorageError};
 use crate::application::models::PromptType;
+use crate::utils::variables::{self, VariableSpec};
+use gray_matter::engine::{Engine, YAML};
+use gray_matter::Pod;
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
 
 const FRONTMATTER_DELIMITER: &str = "---\n";
 
-pub struct FrontmatterUpdater;
+/// Typed view over the frontmatter keys fink understands
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PromptFrontmatter {
+    #[serde(deserialize_with = "deserialize_scalar")]
+    pub name: Option<String>,
+    #[serde(deserialize_with = "deserialize_scalar")]
+    pub description: Option<String>,
+    #[serde(deserialize_with = "deserialize_tags")]
+    pub tags: Vec<String>,
+    #[serde(rename = "type", deserialize_with = "deserialize_scalar")]
+    pub prompt_type: Option<String>,
+    #[serde(deserialize_with = "deserialize_variables")]
+    pub variables: Vec<VariableSpec>,
+    #[serde(deserialize_with = "deserialize_scalar")]
+    pub created_at: Option<String>,
+}
 
-impl FrontmatterUpdater {
-    /// Updates tags in the content, preserving all other frontmatter fields
-    pub fn update_tags(content: &str, name: &str, tags: &[String]) -> Result<String> {
-        if content.starts_with(FRONTMATTER_DELIMITER) {
-            Self::update_existing_frontmatter(content, tags)
-        } else {
-            Ok(Self::add_new_frontmatter(content, name, tags))
+/// One top-level key of the frontmatter together with its raw source lines
+#[derive(Debug, Clone)]
+struct FrontmatterEntry {
+    key: Option<String>,
+    raw: String,
+}
+
+/// A prompt file split into frontmatter and body.
+///
+/// Entries keep their original text and order, so writing the document back
+/// only changes the keys that were explicitly set or removed.
+#[derive(Debug, Clone)]
+pub struct FrontmatterDocument {
+    entries: Option<Vec<FrontmatterEntry>>,
+    closing: String,
+    body: String,
+    data: Pod,
+}
+
+impl FrontmatterDocument {
+    /// Splits `content` into frontmatter entries and body, parsing the YAML with gray_matter
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut lines = content.split_inclusive('\n');
+        
+        let has_frontmatter = lines.next().is_some_and(|line| line.trim_end() == "---");
+        if !has_frontmatter {
+            return Ok(Self {
+                entries: None,
+                closing: String::new(),
+                body: content.to_string(),
+                data: Pod::Null,
+            });
+        }
+        
+        let mut entries: Vec<FrontmatterEntry> = Vec::new();
+        let mut closing = None;
+        
+        for line in lines.by_ref() {
+            if line.trim_end() == "---" {
+                closing = Some(line.to_string());
+                break;
+            }
+            
+            match Self::entry_key(line) {
+                Some(key) => entries.push(FrontmatterEntry { key: Some(key), raw: line.to_string() }),
+                None => match entries.last_mut() {
+                    Some(entry) => entry.raw.push_str(line),
+                    None => entries.push(FrontmatterEntry { key: None, raw: line.to_string() }),
+                },
+            }
         }
+        
+        let closing = closing.ok_or_else(|| FinkError::Storage(StorageError::ParseError(
+            "Invalid frontmatter format".to_string()
+        )))?;
+        
+        let mut document = Self {
+            entries: Some(entries),
+            closing,
+            body: lines.collect(),
+            data: Pod::Null,
+        };
+        document.data = YAML::parse(&document.frontmatter_text());
+        Ok(document)
     }
     
-    /// Ensures the content has a type field, adding it if missing or invalid
-    pub fn ensure_type(content: &str, name: &str, current_type: Option<PromptType>) -> Result<String> {
-        if content.starts_with(FRONTMATTER_DELIMITER) {
-            Self::ensure_type_in_existing_frontmatter(content, current_type)
-        } else {
-            Ok(Self::add_new_frontmatter_with_type(content, name, &[], PromptType::default()))
+    /// Whether the content started with a frontmatter block
+    pub fn has_frontmatter(&self) -> bool {
+        self.entries.is_some()
+    }
+    
+    /// Content after the closing delimiter, untouched
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+    
+    /// Raw value of a top-level key as parsed by gray_matter
+    pub fn get(&self, key: &str) -> Option<&Pod> {
+        match &self.data {
+            Pod::Hash(map) => map.get(key),
+            _ => None,
         }
     }
     
-    fn update_existing_frontmatter(content: &str, tags: &[String]) -> Result<String> {
-        let parts: Vec<&str> = content.splitn(3, FRONTMATTER_DELIMITER).collect();
-        
-        if parts.len() < 3 {
-            return Err(FinkError::Storage(StorageError::ParseError(
-                "Invalid frontmatter format".to_string()
-            )));
+    /// Whether the frontmatter declares `key`, even with an empty value
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.iter()
+            .flatten()
+            .any(|entry| entry.key.as_deref() == Some(key))
+    }
+    
+    /// Every key of the frontmatter as JSON, an empty object without frontmatter
+    pub fn to_json(&self) -> Result<Value> {
+        match &self.data {
+            Pod::Null => Ok(Value::Object(Default::default())),
+            data => data.deserialize().map_err(|e| FinkError::Storage(
+                StorageError::ParseError(format!("Invalid frontmatter: {}", e))
+            )),
+        }
+    }
+    
+    /// Deserializes the known keys into a typed struct
+    pub fn metadata(&self) -> Result<PromptFrontmatter> {
+        match &self.data {
+            Pod::Hash(_) => self.data.deserialize().map_err(|e| FinkError::Storage(
+                StorageError::ParseError(format!("Invalid frontmatter: {}", e))
+            )),
+            Pod::Null if !self.has_yaml_content() => Ok(PromptFrontmatter::default()),
+            _ => Err(FinkError::Storage(StorageError::ParseError(
+                "Frontmatter is not a valid YAML mapping".to_string()
+            ))),
+        }
+    }
+    
+    /// Sets `key` to an already formatted YAML value, replacing the existing entry in place
+    /// or appending a new one. A frontmatter block is created if the document had none.
+    pub fn set_raw(&mut self, key: &str, value: &str) {
+        let line = format!("{}: {}\n", key, value);
+        let entries = self.entries.get_or_insert_with(Vec::new);
+        if self.closing.is_empty() {
+            self.closing = FRONTMATTER_DELIMITER.to_string();
         }
         
-        let updated_frontmatter = Self::update_tags_in_frontmatter(parts[1], tags);
-        Ok(format!("{}{}{}{}", 
-            FRONTMATTER_DELIMITER, 
-            updated_frontmatter, 
-            FRONTMATTER_DELIMITER, 
-            parts[2]
-        ))
-    }
-    
-    fn update_tags_in_frontmatter(frontmatter: &str, tags: &[String]) -> String {
-        let mut lines: Vec<String> = Vec::new();
-        let mut tags_updated = false;
-        
-        for line in frontmatter.lines() {
-            if line.starts_with("tags:") {
-                lines.push(TagFormatter::format_tags_line(tags));
-                tags_updated = true;
-            } else {
-                lines.push(line.to_string());
+        match entries.iter_mut().find(|entry| entry.key.as_deref() == Some(key)) {
+            Some(entry) => {
+                // Keep blank lines and comments that trail the old value
+                let trivia = Self::trailing_trivia(&entry.raw).to_string();
+                entry.raw = line + &trivia;
             }
+            None => entries.push(FrontmatterEntry { key: Some(key.to_string()), raw: line }),
         }
         
-        // Add tags if they didn't exist
-        if !tags_updated {
-            lines.push(TagFormatter::format_tags_line(tags));
+        self.data = YAML::parse(&self.frontmatter_text());
+    }
+    
+    /// Sets `key` to a double-quoted string
+    pub fn set_string(&mut self, key: &str, value: &str) {
+        self.set_raw(key, &quote(value));
+    }
+    
+    /// Removes every entry for `key`
+    pub fn remove(&mut self, key: &str) {
+        if let Some(entries) = self.entries.as_mut() {
+            entries.retain(|entry| entry.key.as_deref() != Some(key));
         }
-        
-        lines.join("\n") + "\n"
+        self.data = YAML::parse(&self.frontmatter_text());
     }
     
-    fn add_new_frontmatter(content: &str, name: &str, tags: &[String]) -> String {
-        format!(
-            "{}name: \"{}\"\n{}\n{}{}",
-            FRONTMATTER_DELIMITER,
-            name,
-            TagFormatter::format_tags_line(tags),
-            FRONTMATTER_DELIMITER,
-            content
-        )
+    /// Renders the document back into file content
+    pub fn to_content(&self) -> String {
+        match &self.entries {
+            Some(_) => format!("{}{}{}{}", FRONTMATTER_DELIMITER, self.frontmatter_text(), self.closing, self.body),
+            None => self.body.clone(),
+        }
     }
     
-    fn add_new_frontmatter_with_type(content: &str, name: &str, tags: &[String], prompt_type: PromptType) -> String {
-        format!(
-            "{}name: \"{}\"\n{}\ntype: \"{}\"\n{}{}",
-            FRONTMATTER_DELIMITER,
-            name,
-            TagFormatter::format_tags_line(tags),
-            Self::prompt_type_to_string(prompt_type),
-            FRONTMATTER_DELIMITER,
-            content
-        )
+    fn frontmatter_text(&self) -> String {
+        self.entries.iter()
+            .flatten()
+            .map(|entry| entry.raw.as_str())
+            .collect()
     }
     
-    fn ensure_type_in_existing_frontmatter(content: &str, current_type: Option<PromptType>) -> Result<String> {
-        let parts: Vec<&str> = content.splitn(3, FRONTMATTER_DELIMITER).collect();
-        
-        if parts.len() < 3 {
-            return Err(FinkError::Storage(StorageError::ParseError(
-                "Invalid frontmatter format".to_string()
-            )));
+    fn has_yaml_content(&self) -> bool {
+        self.frontmatter_text().lines().any(|line| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        })
+    }
+    
+    /// Returns the key of a line that starts a new top-level entry
+    fn entry_key(line: &str) -> Option<String> {
+        if line.starts_with([' ', '\t', '-', '#']) {
+            return None;
         }
-        
-        let mut lines: Vec<String> = Vec::new();
-        let mut type_found = false;
-        
-        for line in parts[1].lines() {
-            if line.trim().starts_with("type:") {
-                type_found = true;
-                // Only update if current_type is None (invalid) or line has invalid value
-                if current_type.is_none() {
-                    lines.push(format!("type: \"{}\"", Self::prompt_type_to_string(PromptType::default())));
-                } else {
-                    lines.push(line.to_string());
-                }
-            } else {
-                lines.push(line.to_string());
+        let (key, _) = line.split_once(':')?;
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        if key.is_empty() {
+            None
+        } else {
+            Some(key.to_string())
+        }
+    }
+    
+    fn trailing_trivia(raw: &str) -> &str {
+        let mut start = raw.len();
+        for line in raw.split_inclusive('\n').rev() {
+            let trimmed = line.trim();
+            if !trimmed.is_empty() && !trimmed.starts_with('#') {
+                break;
             }
+            start -= line.len();
         }
-        
-        // Add type field if it wasn't found
-        if !type_found {
-            lines.push(format!("type: \"{}\"", Self::prompt_type_to_string(PromptType::default())));
+        // The first line always holds the key itself
+        let first_line_end = raw.find('\n').map(|i| i + 1).unwrap_or(raw.len());
+        &raw[start.max(first_line_end)..]
+    }
+}
+
+/// Formats a string as a double-quoted YAML scalar
+pub fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn deserialize_scalar<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<String>, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    Ok(scalar_to_string(&value))
+}
+
+fn deserialize_tags<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<String>, D::Error> {
+    let tags = match Value::deserialize(deserializer)? {
+        Value::Array(items) => items.iter().filter_map(scalar_to_string).collect(),
+        // Also accept the comma separated form `tags: a, b`
+        Value::String(s) => s.split(',').map(|tag| tag.to_string()).collect(),
+        _ => Vec::new(),
+    };
+    
+    Ok(tags.into_iter()
+        .map(|tag: String| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect())
+}
+
+fn deserialize_variables<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<VariableSpec>, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    Ok(variables::parse_declarations(&value))
+}
+
+pub struct FrontmatterUpdater;
+
+impl FrontmatterUpdater {
+    /// Updates tags in the content, preserving all other frontmatter fields
+    pub fn update_tags(content: &str, name: &str, tags: &[String]) -> Result<String> {
+        let mut document = FrontmatterDocument::parse(content)?;
+        if !document.has_frontmatter() {
+            document.set_string("name", name);
         }
-        
-        Ok(format!("{}{}\n{}{}", 
-            FRONTMATTER_DELIMITER, 
-            lines.join("\n"), 
-            FRONTMATTER_DELIMITER, 
-            parts[2]
-        ))
+        document.set_raw("tags", &TagFormatter::format_tags_value(tags));
+        Ok(document.to_content())
+    }
+    
+    /// Sets the type field, preserving all other frontmatter fields
+    pub fn update_type(content: &str, name: &str, prompt_type: &PromptType) -> Result<String> {
+        let mut document = FrontmatterDocument::parse(content)?;
+        if !document.has_frontmatter() {
+            document.set_string("name", name);
+            document.set_raw("tags", &TagFormatter::format_tags_value(&[]));
+        }
+        document.set_string("type", prompt_type.as_str());
+        Ok(document.to_content())
     }
     
-    fn prompt_type_to_string(prompt_type: PromptType) -> &'static str {
-        match prompt_type {
-            PromptType::Instruction => "instruction",
-            PromptType::Context => "context",
-            PromptType::InputIndicator => "input_indicator",
-            PromptType::OutputIndicator => "output_indicator",
-            PromptType::Etc => "etc",
-            PromptType::Whole => "whole",
+    /// Ensures the content has a type field, adding the default type if it is missing or blank.
+    /// Types that are not declared in the config are kept as written.
+    pub fn ensure_type(content: &str, name: &str, current_type: Option<PromptType>) -> Result<String> {
+        let mut document = FrontmatterDocument::parse(content)?;
+        if !document.has_frontmatter() {
+            document.set_string("name", name);
+            document.set_raw("tags", &TagFormatter::format_tags_value(&[]));
+        }
+        
+        let has_type = current_type.is_some() || document.metadata()?
+            .prompt_type
+            .is_some_and(|prompt_type| !prompt_type.trim().is_empty());
+        if !has_type {
+            document.set_string("type", PromptType::default().as_str());
         }
+        Ok(document.to_content())
+    }
+    
+    /// Returns every variable of a prompt, combining the `variables:` declarations with the
+    /// placeholders used in the body. Frontmatter that does not parse declares nothing.
+    pub fn variables(content: &str) -> Result<Vec<VariableSpec>> {
+        let (body, declared) = Self::body_and_declarations(content, |_| {});
+        Ok(variables::resolve_specs(&body, &declared))
+    }
+    
+    /// Splits content into its body and `variables:` declarations. Frontmatter that does not
+    /// parse declares nothing, as listing treats it, and its error is passed to `on_error`.
+    pub fn body_and_declarations(content: &str, on_error: impl FnOnce(FinkError)) -> (String, Vec<VariableSpec>) {
+        let document = match FrontmatterDocument::parse(content) {
+            Ok(document) => document,
+            Err(e) => {
+                on_error(e);
+                return (content.to_string(), Vec::new());
+            }
+        };
+        
+        let declared = match document.metadata() {
+            Ok(frontmatter) => frontmatter.variables,
+            Err(e) => {
+                on_error(e);
+                PromptFrontmatter::default().variables
+            }
+        };
+        (document.body().to_string(), declared)
     }
 }
 
@@ -142,17 +339,18 @@ pub struct TagFormatter;
 impl TagFormatter {
     /// Formats tags for YAML frontmatter
     pub fn format_tags_line(tags: &[String]) -> String {
-        if tags.is_empty() {
-            "tags: []".to_string()
-        } else {
-            format!(
-                "tags: [{}]",
-                tags.iter()
-                    .map(|tag| format!("\"{}\"", tag))
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            )
-        }
+        format!("tags: {}", Self::format_tags_value(tags))
+    }
+    
+    /// Formats tags as an inline YAML list, e.g. `["a", "b"]`
+    pub fn format_tags_value(tags: &[String]) -> String {
+        format!(
+            "[{}]",
+            tags.iter()
+                .map(|tag| quote(tag))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
     }
 }
 
@@ -206,4 +404,42 @@ Body"#;
         assert!(result.contains(r#"tags: ["tag1"]"#));
         assert!(result.contains("Just body content"));
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_round_trip_without_changes() {
+        let content = "---\nname: \"test\"\n# comment\nauthor: me\ntags:\n  - a\n  - b\n---\nBody\n";
+        let document = FrontmatterDocument::parse(content).unwrap();
+        assert_eq!(document.to_content(), content);
+    }
+    
+    #[test]
+    fn test_parse_block_tags_and_quoted_colon() {
+        let content = "---\nname: \"Review: backend\"\ndescription: |\n  line one\n  line two\ntags:\n  - rust\n  - review\ntype: context\n---\nBody";
+        let metadata = FrontmatterDocument::parse(content).unwrap().metadata().unwrap();
+        
+        assert_eq!(metadata.name.as_deref(), Some("Review: backend"));
+        assert_eq!(metadata.description.as_deref(), Some("line one\nline two\n"));
+        assert_eq!(metadata.tags, vec!["rust", "review"]);
+        assert_eq!(metadata.prompt_type.as_deref(), Some("context"));
+    }
+    
+    #[test]
+    fn test_update_tags_keeps_key_order_and_unknown_keys() {
+        let content = "---\nname: test\ntags:\n  - old\n# keep me\ncustom:\n  nested: value\n---\nBody";
+        let result = FrontmatterUpdater::update_tags(content, "test", &["new".to_string()]).unwrap();
+        
+        assert_eq!(result, "---\nname: test\ntags: [\"new\"]\n# keep me\ncustom:\n  nested: value\n---\nBody");
+    }
+    
+    #[test]
+    fn test_invalid_yaml_is_reported() {
+        let content = "---\nname: [unclosed\n---\nBody";
+        let document = FrontmatterDocument::parse(content).unwrap();
+        assert!(document.metadata().is_err());
+    }
+    
+    #[test]
+    fn test_unclosed_frontmatter_is_an_error() {
+        assert!(FrontmatterDocument::parse("---\nname: test\nBody").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::application::models::PromptMetadata;
+
+// Scoring follows fzf: every matched character scores, matches at word starts and
+// right after the previous match earn bonuses, and gaps between matches cost points.
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CAMEL: i64 = 7;
+const BONUS_CONSECUTIVE: i64 = 5;
+const PENALTY_GAP_START: i64 = -3;
+const PENALTY_GAP_EXTENSION: i64 = -1;
+const UNMATCHED: i64 = i64::MIN / 2;
+
+/// A fuzzy match of a pattern against a piece of text
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Char indices of the matched characters in the text, ascending
+    pub positions: Vec<usize>,
+}
+
+/// Matches the characters of `pattern`, in order and case-insensitively, against `text`.
+/// Returns the best scoring alignment, or None if `pattern` is not a subsequence of `text`.
+pub fn fuzzy_match(text: &str, pattern: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+
+    let original: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = original.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+    if !is_subsequence(&lower, &pattern) {
+        return None;
+    }
+
+    let bonuses: Vec<i64> = (0..original.len()).map(|j| position_bonus(&original, j)).collect();
+    let (rows, cols) = (pattern.len(), lower.len());
+
+    // scores[i][j]: best score with pattern[i] matched at text[j]; from[i][j]: where pattern[i - 1] matched
+    let mut scores = vec![vec![UNMATCHED; cols]; rows];
+    let mut from = vec![vec![0usize; cols]; rows];
+
+    for j in 0..cols {
+        if lower[j] == pattern[0] {
+            scores[0][j] = SCORE_MATCH + bonuses[j] * 2;
+        }
+    }
+
+    for i in 1..rows {
+        // Best earlier match of pattern[i - 1] with a gap before column j, and its column
+        let mut gapped = (UNMATCHED, 0);
+        for j in 1..cols {
+            if j >= 2 && scores[i - 1][j - 2] > UNMATCHED {
+                let opened = scores[i - 1][j - 2] + PENALTY_GAP_START;
+                let extended = gapped.0 + PENALTY_GAP_EXTENSION;
+                gapped = if opened >= extended { (opened, j - 2) } else { (extended, gapped.1) };
+            } else if gapped.0 > UNMATCHED {
+                gapped.0 += PENALTY_GAP_EXTENSION;
+            }
+
+            if lower[j] != pattern[i] {
+                continue;
+            }
+            let adjacent = scores[i - 1][j - 1];
+            let (previous, column) = if adjacent > UNMATCHED && adjacent + BONUS_CONSECUTIVE >= gapped.0 {
+                (adjacent + BONUS_CONSECUTIVE, j - 1)
+            } else {
+                gapped
+            };
+            if previous > UNMATCHED {
+                scores[i][j] = previous + SCORE_MATCH + bonuses[j];
+                from[i][j] = column;
+            }
+        }
+    }
+
+    let (mut column, score) = scores[rows - 1]
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by_key(|(j, score)| (*score, std::cmp::Reverse(*j)))?;
+    if score <= UNMATCHED {
+        return None;
+    }
+
+    let mut positions = vec![0; rows];
+    for i in (0..rows).rev() {
+        positions[i] = column;
+        column = from[i][column];
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+fn is_subsequence(text: &[char], pattern: &[char]) -> bool {
+    let mut remaining = pattern.iter().peekable();
+    for c in text {
+        if remaining.peek() == Some(&c) {
+            remaining.next();
+        }
+    }
+    remaining.peek().is_none()
+}
+
+fn position_bonus(text: &[char], index: usize) -> i64 {
+    let current = text[index];
+    match index.checked_sub(1).map(|previous| text[previous]) {
+        None => BONUS_BOUNDARY,
+        Some(previous) if !previous.is_alphanumeric() && current.is_alphanumeric() => BONUS_BOUNDARY,
+        Some(previous) if previous.is_lowercase() && current.is_uppercase() => BONUS_CAMEL,
+        _ => 0,
+    }
+}
+
+/// Scores a prompt against a query. Every whitespace separated term has to match the
+/// name, a tag or the description; name matches count the most, description matches the least.
+pub fn score_prompt(prompt: &PromptMetadata, query: &str) -> Option<i64> {
+    query
+        .split_whitespace()
+        .map(|term| {
+            let name = fuzzy_match(&prompt.display_name(), term).map(|m| m.score);
+            let tags = prompt.tags
+                .iter()
+                .filter_map(|tag| fuzzy_match(tag, term))
+                .map(|m| m.score * 3 / 4)
+                .max();
+            let description = fuzzy_match(&prompt.description, term).map(|m| m.score / 2);
+            name.max(tags).max(description)
+        })
+        .sum()
+}
+
+/// Char positions in `text` matched by any term of `query`, for highlighting
+pub fn match_positions(text: &str, query: &str) -> Vec<usize> {
+    let mut positions: Vec<usize> = query
+        .split_whitespace()
+        .filter_map(|term| fuzzy_match(text, term))
+        .flat_map(|m| m.positions)
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::models::PromptType;
+
+    fn prompt(name: &str, tags: &[&str], description: &str) -> PromptMetadata {
+        PromptMetadata {
+            name: name.to_string(),
+            file_path: format!("{}.md", name),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            prompt_type: PromptType::WHOLE,
+            description: description.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_subsequences_case_insensitively() {
+        let found = fuzzy_match("code-review", "cdrv").unwrap();
+        assert_eq!(found.positions, vec![0, 2, 5, 7]);
+        assert!(fuzzy_match("Code-Review", "CR").is_some());
+        assert!(fuzzy_match("code-review", "rc").is_none());
+    }
+
+    #[test]
+    fn prefers_word_starts_and_consecutive_runs() {
+        let found = fuzzy_match("a review of reviews", "rev").unwrap();
+        assert_eq!(found.positions, vec![2, 3, 4]);
+
+        let boundary = fuzzy_match("code-review", "cr").unwrap().score;
+        let scattered = fuzzy_match("concurrent", "cr").unwrap().score;
+        assert!(boundary > scattered);
+    }
+
+    #[test]
+    fn scores_every_term_across_fields() {
+        let reviewer = prompt("code-review", &["rust"], "Checks pull requests");
+        assert!(score_prompt(&reviewer, "cdrv").is_some());
+        assert!(score_prompt(&reviewer, "review rust").is_some());
+        assert!(score_prompt(&reviewer, "pull").is_some());
+        assert!(score_prompt(&reviewer, "review python").is_none());
+
+        let by_name = score_prompt(&prompt("rust", &[], ""), "rust").unwrap();
+        let by_tag = score_prompt(&reviewer, "rust").unwrap();
+        assert!(by_name > by_tag);
+    }
+
+    #[test]
+    fn collects_positions_for_all_terms() {
+        assert_eq!(match_positions("code-review", "co rev"), vec![0, 1, 5, 6, 7]);
+        assert!(match_positions("code-review", "xyz").is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::utils::error::{Result, FinkError, PromptError};
+
+const INCLUDE_OPEN: &str = "{{>";
+const INCLUDE_CLOSE: &str = "}}";
+
+/// An `{{> other/prompt}}` directive found in a prompt body
+struct Directive<'a> {
+    start: usize,
+    end: usize,
+    name: &'a str,
+}
+
+fn directives(content: &str) -> Vec<Directive<'_>> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    
+    while let Some(open) = content[offset..].find(INCLUDE_OPEN) {
+        let start = offset + open;
+        let inner_start = start + INCLUDE_OPEN.len();
+        let Some(close) = content[inner_start..].find(INCLUDE_CLOSE) else {
+            break;
+        };
+        let inner_end = inner_start + close;
+        let name = content[inner_start..inner_end].trim();
+        
+        if !name.is_empty() && !name.contains('\n') {
+            let end = inner_end + INCLUDE_CLOSE.len();
+            found.push(Directive { start, end, name });
+            offset = end;
+        } else {
+            offset = inner_start;
+        }
+    }
+    
+    found
+}
+
+/// Names referenced by include directives, in order
+pub fn references(content: &str) -> Vec<String> {
+    directives(content).iter().map(|d| d.name.to_string()).collect()
+}
+
+/// Replaces include directives with the bodies of the prompts they reference, recursively.
+///
+/// `resolve` looks a reference up and returns the prompt's id and body, or `None` if no such
+/// prompt exists. `root` is the id of the prompt being expanded, used to detect cycles.
+pub fn expand<F>(root: &str, content: &str, resolve: &mut F) -> Result<String>
+where
+    F: FnMut(&str) -> Result<Option<(String, String)>>,
+{
+    let mut stack = vec![root.to_string()];
+    expand_inner(content, resolve, &mut stack)
+}
+
+fn expand_inner<F>(content: &str, resolve: &mut F, stack: &mut Vec<String>) -> Result<String>
+where
+    F: FnMut(&str) -> Result<Option<(String, String)>>,
+{
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+    
+    for directive in directives(content) {
+        let current = stack.last().cloned().unwrap_or_default();
+        let (id, body) = resolve(directive.name)?.ok_or_else(|| FinkError::Prompt(
+            PromptError::IncludeNotFound(directive.name.to_string(), current)
+        ))?;
+        
+        if stack.contains(&id) {
+            let mut cycle = stack.clone();
+            cycle.push(id);
+            return Err(FinkError::Prompt(PromptError::IncludeCycle(cycle)));
+        }
+        
+        stack.push(id);
+        let expanded = expand_inner(&body, resolve, stack)?;
+        stack.pop();
+        
+        result.push_str(&content[last..directive.start]);
+        result.push_str(&expanded);
+        last = directive.end;
+    }
+    
+    result.push_str(&content[last..]);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn library(prompts: &[(&str, &str)]) -> HashMap<String, String> {
+        prompts.iter().map(|(id, body)| (id.to_string(), body.to_string())).collect()
+    }
+
+    fn expand_from(prompts: &HashMap<String, String>, root: &str) -> Result<String> {
+        expand(root, &prompts[root], &mut |name| {
+            Ok(prompts.get(name).map(|body| (name.to_string(), body.clone())))
+        })
+    }
+
+    #[test]
+    fn expands_nested_includes() {
+        let prompts = library(&[
+            ("review", "{{> tone}}\nReview the code."),
+            ("tone", "Be kind. {{> shared/safety }}"),
+            ("shared/safety", "Never share secrets."),
+        ]);
+        assert_eq!(expand_from(&prompts, "review").unwrap(), "Be kind. Never share secrets.\nReview the code.");
+    }
+
+    #[test]
+    fn allows_the_same_include_twice() {
+        let prompts = library(&[("a", "{{> b}} and {{> b}}"), ("b", "x")]);
+        assert_eq!(expand_from(&prompts, "a").unwrap(), "x and x");
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let prompts = library(&[("a", "{{> b}}"), ("b", "{{> c}}"), ("c", "{{> a}}")]);
+        let err = expand_from(&prompts, "a").unwrap_err();
+        assert_eq!(err.to_string(), "Include cycle: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn reports_missing_references() {
+        let prompts = library(&[("a", "{{> b}}"), ("b", "{{> missing}}")]);
+        let err = expand_from(&prompts, "a").unwrap_err();
+        assert_eq!(err.to_string(), "Included prompt not found: missing (referenced from b)");
+    }
+
+    #[test]
+    fn leaves_variables_alone() {
+        assert!(references("{{name}} {{ >x }}").is_empty());
+        assert_eq!(references("{{>x}} {{> y/z }}"), vec!["x", "y/z"]);
+    }
+}
@@ -5,4 +5,12 @@ pub mod config;
 pub mod default_prompts;
 pub mod constants;
 pub mod text_scroll;
-pub mod state;
\ No newline at end of file
+pub mod state;
+pub mod diff;
+pub mod variables;
+pub mod includes;
+pub mod output_format;
+pub mod data_format;
+pub mod fuzzy;
+pub mod query;
+pub mod tags;
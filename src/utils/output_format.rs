@@ -0,0 +1,185 @@
+use crate::application::models::{PromptType, PromptTypes};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How the pieces of a build are framed when they are combined
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Pieces separated by blank lines, as the combiner always did
+    #[default]
+    Plain,
+    /// One `##` section per prompt type
+    Markdown,
+    /// Pieces wrapped in tags such as `<instruction>`
+    Xml,
+    /// An OpenAI-style chat messages array
+    Json,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 4] = [
+        OutputFormat::Plain,
+        OutputFormat::Markdown,
+        OutputFormat::Xml,
+        OutputFormat::Json,
+    ];
+
+    /// The format after this one, wrapping around
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|format| *format == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Combines prompt bodies, in order, with an optional trailing comment.
+    /// Markdown headings use the display names from `types`.
+    pub fn combine(self, sections: &[(PromptType, String)], types: &PromptTypes, comment: &str) -> String {
+        match self {
+            OutputFormat::Plain => {
+                let mut parts: Vec<String> = sections.iter().map(|(_, content)| content.clone()).collect();
+                if !comment.is_empty() {
+                    parts.push(format!("# Additional Notes:\n{}", comment));
+                }
+                parts.join("\n\n")
+            }
+            OutputFormat::Markdown => {
+                let mut parts: Vec<String> = sections
+                    .iter()
+                    .map(|(prompt_type, content)| format!("## {}\n\n{}", types.display_name(prompt_type), content))
+                    .collect();
+                if !comment.is_empty() {
+                    parts.push(format!("## Additional Notes\n\n{}", comment));
+                }
+                parts.join("\n\n")
+            }
+            OutputFormat::Xml => {
+                let mut parts: Vec<String> = sections
+                    .iter()
+                    .map(|(prompt_type, content)| {
+                        let tag = xml_tag(prompt_type);
+                        format!("<{}>\n{}\n</{}>", tag, content, tag)
+                    })
+                    .collect();
+                if !comment.is_empty() {
+                    parts.push(format!("<notes>\n{}\n</notes>", comment));
+                }
+                parts.join("\n\n")
+            }
+            OutputFormat::Json => {
+                let mut messages: Vec<serde_json::Value> = sections
+                    .iter()
+                    .map(|(prompt_type, content)| {
+                        let role = if *prompt_type == PromptType::INSTRUCTION { "system" } else { "user" };
+                        serde_json::json!({ "role": role, "content": content })
+                    })
+                    .collect();
+                if !comment.is_empty() {
+                    messages.push(serde_json::json!({ "role": "user", "content": comment }));
+                }
+                serde_json::to_string_pretty(&messages).unwrap_or_default()
+            }
+        }
+    }
+}
+
+// Type ids become tag names, with characters that are not valid in a tag replaced
+fn xml_tag(prompt_type: &PromptType) -> String {
+    if *prompt_type == PromptType::WHOLE {
+        return "prompt".to_string();
+    }
+    prompt_type.as_str()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OutputFormat::Plain => write!(f, "plain"),
+            OutputFormat::Markdown => write!(f, "markdown"),
+            OutputFormat::Xml => write!(f, "xml"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "plain" | "text" => Ok(OutputFormat::Plain),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            "xml" => Ok(OutputFormat::Xml),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("unknown output format '{}' (expected plain, markdown, xml or json)", value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sections() -> Vec<(PromptType, String)> {
+        vec![
+            (PromptType::INSTRUCTION, "Be a reviewer.".to_string()),
+            (PromptType::CONTEXT, "Rust code.".to_string()),
+        ]
+    }
+
+    #[test]
+    fn plain_joins_with_blank_lines() {
+        assert_eq!(
+            OutputFormat::Plain.combine(&sections(), &PromptTypes::default(), "Be brief"),
+            "Be a reviewer.\n\nRust code.\n\n# Additional Notes:\nBe brief"
+        );
+    }
+
+    #[test]
+    fn markdown_titles_sections_by_type() {
+        assert_eq!(
+            OutputFormat::Markdown.combine(&sections(), &PromptTypes::default(), ""),
+            "## Instruction\n\nBe a reviewer.\n\n## Context\n\nRust code."
+        );
+    }
+
+    #[test]
+    fn xml_wraps_sections_in_tags() {
+        assert_eq!(
+            OutputFormat::Xml.combine(&sections(), &PromptTypes::default(), "Be brief"),
+            "<instruction>\nBe a reviewer.\n</instruction>\n\n<context>\nRust code.\n</context>\n\n<notes>\nBe brief\n</notes>"
+        );
+    }
+
+    #[test]
+    fn json_builds_messages_array() {
+        let output = OutputFormat::Json.combine(&sections(), &PromptTypes::default(), "Be brief");
+        let messages: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(messages[0]["role"], "system");
+        assert_eq!(messages[0]["content"], "Be a reviewer.");
+        assert_eq!(messages[1]["role"], "user");
+        assert_eq!(messages[2]["content"], "Be brief");
+    }
+
+    #[test]
+    fn uses_configured_names_and_ids() {
+        let persona = PromptType::new("Persona Notes");
+        let types = PromptTypes::new(vec![
+            crate::application::models::TypeDefinition::new(persona.clone(), "Persona", "", true),
+        ]);
+        let sections = vec![(persona, "Be kind.".to_string())];
+        
+        assert_eq!(OutputFormat::Markdown.combine(&sections, &types, ""), "## Persona\n\nBe kind.");
+        assert_eq!(OutputFormat::Xml.combine(&sections, &types, ""), "<persona_notes>\nBe kind.\n</persona_notes>");
+    }
+
+    #[test]
+    fn parses_and_cycles_formats() {
+        assert_eq!("XML".parse::<OutputFormat>().unwrap(), OutputFormat::Xml);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+        assert_eq!(OutputFormat::Json.next(), OutputFormat::Plain);
+    }
+}
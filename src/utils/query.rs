@@ -0,0 +1,536 @@
+use crate::application::models::{ContentMatch, PromptMetadata, PromptType};
+use crate::utils::constants::STARRED_TAG;
+use crate::utils::frontmatter::FrontmatterDocument;
+use crate::utils::fuzzy;
+use crate::utils::tags;
+use std::fmt;
+
+/// A single condition of a search query
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// Free text, fuzzy matched against name, tags and description.
+    /// Quoted phrases are matched as a case-insensitive substring instead.
+    Text { value: String, phrase: bool },
+    /// `name:` - substring of the name, including its folder
+    Name(String),
+    /// `tag:` - a tag, compared case-insensitively
+    Tag(String),
+    /// `type:` - the prompt type
+    Type(PromptType),
+    /// `content:` - substring of the prompt body
+    Content(String),
+    /// `starred:` - whether the prompt carries the starred tag
+    Starred(bool),
+}
+
+/// Parsed search query, e.g. `tag:rust type:instruction -tag:draft review`
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchQuery {
+    Term(Term),
+    Not(Box<SearchQuery>),
+    /// Every part has to match; an empty list matches everything
+    And(Vec<SearchQuery>),
+    /// At least one part has to match
+    Or(Vec<SearchQuery>),
+}
+
+/// Why a query could not be parsed, with the 1-based column it was noticed at
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    pub column: usize,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at column {}", self.message, self.column)
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Term { negated: bool, term: Term },
+    Open { negated: bool },
+    Close,
+    Or,
+}
+
+fn error(message: impl Into<String>, index: usize) -> QueryError {
+    QueryError { message: message.into(), column: index + 1 }
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')'
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    index: usize,
+}
+
+impl Lexer {
+    fn tokens(input: &str) -> Result<Vec<(Token, usize)>, QueryError> {
+        let mut lexer = Lexer { chars: input.chars().collect(), index: 0 };
+        let mut tokens = Vec::new();
+
+        while let Some(c) = lexer.peek() {
+            let start = lexer.index;
+            if c.is_whitespace() {
+                lexer.index += 1;
+            } else if c == ')' {
+                lexer.index += 1;
+                tokens.push((Token::Close, start));
+            } else {
+                tokens.push((lexer.token()?, start));
+            }
+        }
+        Ok(tokens)
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn token(&mut self) -> Result<Token, QueryError> {
+        let start = self.index;
+        let negated = self.peek() == Some('-');
+        if negated {
+            self.index += 1;
+            if self.peek().is_none_or(|c| c.is_whitespace() || c == ')') {
+                return Err(error("expected a term after '-'", start));
+            }
+        }
+
+        if self.peek() == Some('(') {
+            self.index += 1;
+            return Ok(Token::Open { negated });
+        }
+
+        if self.peek() == Some('"') {
+            let value = self.quoted()?;
+            return Ok(Token::Term { negated, term: Term::Text { value, phrase: true } });
+        }
+
+        let word_start = self.index;
+        let word = self.word();
+        if word == "OR" && !negated {
+            return Ok(Token::Or);
+        }
+
+        // `field:value`, where the value may be quoted
+        if let Some(colon) = word.find(':') {
+            let field = &word[..colon];
+            let mut value = word[colon + 1..].to_string();
+            if value.is_empty() && self.peek() == Some('"') {
+                value = self.quoted()?;
+            }
+            if value.is_empty() {
+                return Err(error(format!("missing value after '{}:'", field), word_start));
+            }
+            let term = field_term(field, value, word_start)?;
+            return Ok(Token::Term { negated, term });
+        }
+
+        Ok(Token::Term { negated, term: Term::Text { value: word, phrase: false } })
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if is_delimiter(c) || (c == '"' && word.ends_with(':')) {
+                break;
+            }
+            word.push(c);
+            self.index += 1;
+        }
+        word
+    }
+
+    fn quoted(&mut self) -> Result<String, QueryError> {
+        let start = self.index;
+        self.index += 1; // opening quote
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.index += 1;
+                    break;
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.index += 1;
+                }
+                None => return Err(error("unterminated quote", start)),
+            }
+        }
+        if value.trim().is_empty() {
+            return Err(error("empty quoted phrase", start));
+        }
+        Ok(value)
+    }
+}
+
+fn field_term(field: &str, value: String, index: usize) -> Result<Term, QueryError> {
+    match field.to_lowercase().as_str() {
+        "name" => Ok(Term::Name(value)),
+        "tag" | "tags" => Ok(Term::Tag(value)),
+        "type" => Ok(Term::Type(PromptType::new(&value))),
+        "content" => Ok(Term::Content(value)),
+        "starred" => match value.to_lowercase().as_str() {
+            "true" | "yes" | "1" => Ok(Term::Starred(true)),
+            "false" | "no" | "0" => Ok(Term::Starred(false)),
+            _ => Err(error(format!("expected true or false for 'starred:', got '{}'", value), index)),
+        },
+        _ => Err(error(
+            format!("unknown field '{}' (expected name, tag, type, content or starred)", field),
+            index,
+        )),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(token, _)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.index).map(|(_, position)| *position).unwrap_or(self.end)
+    }
+
+    // or := and ("OR" and)*
+    fn or(&mut self) -> Result<SearchQuery, QueryError> {
+        let mut parts = vec![self.and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.index += 1;
+            parts.push(self.and()?);
+        }
+        Ok(if parts.len() == 1 { parts.remove(0) } else { SearchQuery::Or(parts) })
+    }
+
+    // and := unary+
+    fn and(&mut self) -> Result<SearchQuery, QueryError> {
+        let mut parts = Vec::new();
+        while let Some(token) = self.peek().cloned() {
+            match token {
+                Token::Or | Token::Close => break,
+                Token::Term { negated, term } => {
+                    self.index += 1;
+                    parts.push(negate(SearchQuery::Term(term), negated));
+                }
+                Token::Open { negated } => {
+                    let open = self.position();
+                    self.index += 1;
+                    let group = self.or()?;
+                    if self.peek() != Some(&Token::Close) {
+                        return Err(error("unclosed '('", open));
+                    }
+                    self.index += 1;
+                    parts.push(negate(group, negated));
+                }
+            }
+        }
+
+        match parts.len() {
+            0 => Err(error("expected a search term", self.position())),
+            1 => Ok(parts.remove(0)),
+            _ => Ok(SearchQuery::And(parts)),
+        }
+    }
+}
+
+fn negate(query: SearchQuery, negated: bool) -> SearchQuery {
+    if negated { SearchQuery::Not(Box::new(query)) } else { query }
+}
+
+impl SearchQuery {
+    /// Parses the search bar syntax: terms are combined with AND, `OR` separates
+    /// alternatives, `-` negates, `"..."` quotes a phrase and parentheses group
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let tokens = Lexer::tokens(input)?;
+        if tokens.is_empty() {
+            return Ok(SearchQuery::And(Vec::new()));
+        }
+
+        let mut parser = Parser { tokens, index: 0, end: input.chars().count() };
+        let query = parser.or()?;
+        if parser.peek() == Some(&Token::Close) {
+            return Err(error("unmatched ')'", parser.position()));
+        }
+        Ok(query)
+    }
+
+    /// Returns the ranking score if the prompt matches, or None if it does not.
+    /// `content` loads the prompt body and is only called for `content:` terms.
+    pub fn evaluate(&self, prompt: &PromptMetadata, content: &mut dyn FnMut() -> Option<String>) -> Option<i64> {
+        match self {
+            SearchQuery::Term(term) => term.evaluate(prompt, content),
+            SearchQuery::Not(inner) => match inner.evaluate(prompt, content) {
+                Some(_) => None,
+                None => Some(0),
+            },
+            SearchQuery::And(parts) => parts.iter().map(|part| part.evaluate(prompt, content)).sum(),
+            SearchQuery::Or(parts) => parts.iter().filter_map(|part| part.evaluate(prompt, content)).max(),
+        }
+    }
+
+    /// Rewrites free text terms so they also match the prompt body, as `fink search --content` does
+    pub fn include_content(self) -> Self {
+        match self {
+            SearchQuery::Term(Term::Text { value, phrase }) => SearchQuery::Or(vec![
+                SearchQuery::Term(Term::Text { value: value.clone(), phrase }),
+                SearchQuery::Term(Term::Content(value)),
+            ]),
+            SearchQuery::Term(term) => SearchQuery::Term(term),
+            SearchQuery::Not(inner) => SearchQuery::Not(Box::new(inner.include_content())),
+            SearchQuery::And(parts) => SearchQuery::And(parts.into_iter().map(Self::include_content).collect()),
+            SearchQuery::Or(parts) => SearchQuery::Or(parts.into_iter().map(Self::include_content).collect()),
+        }
+    }
+
+    /// Values of the `content:` terms that a match can show, for locating snippets
+    pub fn content_terms(&self) -> Vec<&str> {
+        match self {
+            SearchQuery::Term(Term::Content(value)) => vec![value.as_str()],
+            SearchQuery::Term(_) | SearchQuery::Not(_) => Vec::new(),
+            SearchQuery::And(parts) | SearchQuery::Or(parts) => parts.iter().flat_map(|part| part.content_terms()).collect(),
+        }
+    }
+
+    /// Text and name values that a match can show, for highlighting
+    pub fn highlight_terms(&self) -> Vec<&str> {
+        match self {
+            SearchQuery::Term(Term::Text { value, .. }) | SearchQuery::Term(Term::Name(value)) => vec![value.as_str()],
+            SearchQuery::Term(_) | SearchQuery::Not(_) => Vec::new(),
+            SearchQuery::And(parts) | SearchQuery::Or(parts) => parts.iter().flat_map(|part| part.highlight_terms()).collect(),
+        }
+    }
+}
+
+impl Term {
+    fn evaluate(&self, prompt: &PromptMetadata, content: &mut dyn FnMut() -> Option<String>) -> Option<i64> {
+        let matched = match self {
+            Term::Text { value, phrase: false } => return fuzzy::score_prompt(prompt, value),
+            Term::Text { value, phrase: true } => {
+                let phrase = value.to_lowercase();
+                let found = prompt.display_name().to_lowercase().contains(&phrase)
+                    || prompt.tags.iter().any(|tag| tag.to_lowercase().contains(&phrase))
+                    || prompt.description.to_lowercase().contains(&phrase);
+                return found.then(|| fuzzy::score_prompt(prompt, value).unwrap_or(0));
+            }
+            Term::Name(value) => prompt.display_name().to_lowercase().contains(&value.to_lowercase()),
+            Term::Tag(value) => prompt.tags.iter().any(|tag| tags::tag_matches(&tag.to_lowercase(), &value.to_lowercase())),
+            Term::Type(prompt_type) => &prompt.prompt_type == prompt_type,
+            Term::Content(value) => content()
+                .is_some_and(|body| body.to_lowercase().contains(&value.to_lowercase())),
+            Term::Starred(starred) => prompt.tags.iter().any(|tag| tag == STARRED_TAG) == *starred,
+        };
+        matched.then_some(0)
+    }
+}
+
+// Longest snippet shown for a match, in characters
+const SNIPPET_WIDTH: usize = 80;
+
+/// Finds every case-insensitive occurrence of `terms` in the body of a prompt file.
+/// Lines are numbered from the top of the file so they can be passed to an editor.
+pub fn find_content_matches(file_content: &str, terms: &[&str]) -> Vec<ContentMatch> {
+    let body = FrontmatterDocument::parse(file_content)
+        .ok()
+        .filter(|document| document.has_frontmatter() && file_content.ends_with(document.body()))
+        .map(|document| document.body().to_string());
+    let (body, first_line) = match &body {
+        Some(body) => {
+            let header = &file_content[..file_content.len() - body.len()];
+            (body.as_str(), header.matches('\n').count() + 1)
+        }
+        None => (file_content, 1),
+    };
+
+    let terms: Vec<Vec<char>> = terms
+        .iter()
+        .map(|term| term.chars().flat_map(char::to_lowercase).collect::<Vec<char>>())
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    let mut matches = Vec::new();
+    for (offset, line) in body.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let lower: Vec<char> = chars.iter().map(|c| c.to_lowercase().next().unwrap_or(*c)).collect();
+        for term in &terms {
+            let mut start = 0;
+            while start + term.len() <= lower.len() {
+                if lower[start..start + term.len()] == term[..] {
+                    matches.push(ContentMatch {
+                        line: first_line + offset,
+                        column: start + 1,
+                        snippet: snippet(&chars, start),
+                    });
+                    start += term.len();
+                } else {
+                    start += 1;
+                }
+            }
+        }
+    }
+    matches.sort_by_key(|m| (m.line, m.column));
+    matches.dedup_by_key(|m| (m.line, m.column));
+    matches
+}
+
+// The trimmed line, cut down to a window around the match when it is long
+fn snippet(line: &[char], start: usize) -> String {
+    let text: String = line.iter().collect();
+    let trimmed = text.trim();
+    if trimmed.chars().count() <= SNIPPET_WIDTH {
+        return trimmed.to_string();
+    }
+
+    let from = start.saturating_sub(SNIPPET_WIDTH / 4);
+    let to = (from + SNIPPET_WIDTH).min(line.len());
+    let window: String = line[from..to].iter().collect();
+    format!(
+        "{}{}{}",
+        if from > 0 { "…" } else { "" },
+        window.trim(),
+        if to < line.len() { "…" } else { "" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> SearchQuery {
+        SearchQuery::Term(Term::Text { value: value.to_string(), phrase: false })
+    }
+
+    fn prompt(name: &str, tags: &[&str], prompt_type: PromptType) -> PromptMetadata {
+        PromptMetadata {
+            name: name.to_string(),
+            file_path: format!("{}.md", name),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            prompt_type,
+            ..Default::default()
+        }
+    }
+
+    fn matches(query: &str, prompt: &PromptMetadata) -> bool {
+        SearchQuery::parse(query).unwrap().evaluate(prompt, &mut || Some("Review the diff".to_string())).is_some()
+    }
+
+    #[test]
+    fn parses_fields_negation_and_text() {
+        assert_eq!(
+            SearchQuery::parse("tag:rust type:instruction -tag:draft review").unwrap(),
+            SearchQuery::And(vec![
+                SearchQuery::Term(Term::Tag("rust".to_string())),
+                SearchQuery::Term(Term::Type(PromptType::INSTRUCTION)),
+                SearchQuery::Not(Box::new(SearchQuery::Term(Term::Tag("draft".to_string())))),
+                text("review"),
+            ])
+        );
+    }
+
+    #[test]
+    fn parses_or_groups_and_phrases() {
+        assert_eq!(
+            SearchQuery::parse("a OR b c").unwrap(),
+            SearchQuery::Or(vec![text("a"), SearchQuery::And(vec![text("b"), text("c")])])
+        );
+        assert_eq!(
+            SearchQuery::parse("-(a OR b)").unwrap(),
+            SearchQuery::Not(Box::new(SearchQuery::Or(vec![text("a"), text("b")])))
+        );
+        assert_eq!(
+            SearchQuery::parse("name:\"code review\" \"pull request\"").unwrap(),
+            SearchQuery::And(vec![
+                SearchQuery::Term(Term::Name("code review".to_string())),
+                SearchQuery::Term(Term::Text { value: "pull request".to_string(), phrase: true }),
+            ])
+        );
+        assert_eq!(SearchQuery::parse("  ").unwrap(), SearchQuery::And(Vec::new()));
+        assert_eq!(SearchQuery::parse("code-review").unwrap(), text("code-review"));
+    }
+
+    #[test]
+    fn reports_errors_with_columns() {
+        let cases = [
+            ("\"open", "unterminated quote", 1),
+            ("rust OR", "expected a search term", 8),
+            ("OR rust", "expected a search term", 1),
+            ("size:3", "unknown field 'size'", 1),
+            ("review tag:", "missing value after 'tag:'", 8),
+            ("(rust", "unclosed '('", 1),
+            ("rust)", "unmatched ')'", 5),
+            ("- rust", "expected a term after '-'", 1),
+            ("starred:maybe", "expected true or false", 1),
+        ];
+        for (input, message, column) in cases {
+            let error = SearchQuery::parse(input).unwrap_err();
+            assert!(error.message.starts_with(message), "{}: {}", input, error);
+            assert_eq!(error.column, column, "{}", input);
+        }
+    }
+
+    #[test]
+    fn evaluates_against_prompts() {
+        let reviewer = prompt("code-review", &["rust", "starred"], PromptType::INSTRUCTION);
+        let draft = prompt("review-draft", &["rust", "draft"], PromptType::INSTRUCTION);
+
+        assert!(matches("tag:rust type:instruction -tag:draft review", &reviewer));
+        assert!(!matches("tag:rust type:instruction -tag:draft review", &draft));
+        assert!(matches("starred:true", &reviewer));
+        assert!(matches("starred:no", &draft));
+        assert!(matches("tag:python OR name:draft", &draft));
+        assert!(matches("content:diff", &reviewer));
+        assert!(!matches("content:merge", &reviewer));
+        assert!(matches("\"code-rev\"", &reviewer));
+        assert!(!matches("\"cdrv\"", &reviewer));
+    }
+
+    #[test]
+    fn locates_content_matches_by_file_line() {
+        let file = "---\nname: \"review\"\n---\n\nCheck the Diff.\nThen diff again: diff\n";
+        let matches = find_content_matches(file, &["diff"]);
+
+        let locations: Vec<(usize, usize)> = matches.iter().map(|m| (m.line, m.column)).collect();
+        assert_eq!(locations, vec![(5, 11), (6, 6), (6, 18)]);
+        assert_eq!(matches[0].snippet, "Check the Diff.");
+        assert!(find_content_matches("no frontmatter\ndiff", &["DIFF"])[0].line == 2);
+    }
+
+    #[test]
+    fn shortens_long_snippets_around_the_match() {
+        let line = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let found = &find_content_matches(&line, &["needle"])[0];
+        assert_eq!(found.column, 101);
+        assert!(found.snippet.starts_with('…') && found.snippet.ends_with('…'));
+        assert!(found.snippet.contains("needle"));
+    }
+
+    #[test]
+    fn content_option_extends_text_terms() {
+        let query = SearchQuery::parse("diff -draft").unwrap().include_content();
+        assert_eq!(query.content_terms(), vec!["diff"]);
+    }
+
+    #[test]
+    fn loads_content_only_when_needed() {
+        let reviewer = prompt("code-review", &[], PromptType::WHOLE);
+        let mut loads = 0;
+        let query = SearchQuery::parse("review").unwrap();
+        query.evaluate(&reviewer, &mut || {
+            loads += 1;
+            None
+        });
+        assert_eq!(loads, 0);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::application::models::TagOperation;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Separates the levels of a hierarchical tag, as in `lang/rust`
+pub const TAG_SEPARATOR: char = '/';
+
+/// Whether `tag` is `filter` itself or one of its descendants, so `lang` matches `lang/rust`
+pub fn tag_matches(tag: &str, filter: &str) -> bool {
+    let filter = filter.trim_end_matches(TAG_SEPARATOR);
+    match tag.strip_prefix(filter) {
+        Some(rest) => rest.is_empty() || rest.starts_with(TAG_SEPARATOR),
+        None => false,
+    }
+}
+
+/// Moves `tag` from under `from` to under `to`, or None if it is not `from` or a descendant
+pub fn retarget(tag: &str, from: &str, to: &str) -> Option<String> {
+    let from = from.trim_end_matches(TAG_SEPARATOR);
+    let to = to.trim_end_matches(TAG_SEPARATOR);
+    tag_matches(tag, from).then(|| format!("{}{}", to, &tag[from.len()..]))
+}
+
+/// The tags a prompt ends up with after `operation`, in their original order without duplicates
+pub fn apply_operation(operation: &TagOperation, tags: &[String]) -> Vec<String> {
+    let mut result: Vec<String> = Vec::new();
+    for tag in tags {
+        let rewritten = match operation {
+            TagOperation::Rename { from, to } => retarget(tag, from, to),
+            TagOperation::Merge { sources, into } => sources.iter().find_map(|source| retarget(tag, source, into)),
+            TagOperation::Delete(deleted) => {
+                if tag_matches(tag, deleted) {
+                    continue;
+                }
+                None
+            }
+        };
+        let rewritten = rewritten.unwrap_or_else(|| tag.clone());
+        if !result.contains(&rewritten) {
+            result.push(rewritten);
+        }
+    }
+    result
+}
+
+/// The tag and every parent above it, outermost first: `a/b/c` gives `a`, `a/b`, `a/b/c`
+pub fn ancestors(tag: &str) -> Vec<&str> {
+    tag.match_indices(TAG_SEPARATOR)
+        .map(|(index, _)| &tag[..index])
+        .chain(std::iter::once(tag))
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+/// Counts the prompts under every tag and parent tag; a prompt is counted once per level
+pub fn count_tags<'a>(tag_lists: impl IntoIterator<Item = &'a [String]>) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for tags in tag_lists {
+        let paths: BTreeSet<&str> = tags.iter().flat_map(|tag| ancestors(tag)).collect();
+        for path in paths {
+            *counts.entry(path.to_string()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parent_tags_match_their_descendants() {
+        assert!(tag_matches("lang/rust", "lang"));
+        assert!(tag_matches("lang/rust", "lang/"));
+        assert!(tag_matches("lang", "lang"));
+        assert!(!tag_matches("language", "lang"));
+        assert!(!tag_matches("lang", "lang/rust"));
+    }
+
+    #[test]
+    fn retargets_tags_with_their_descendants() {
+        assert_eq!(retarget("lang/rust", "lang", "language"), Some("language/rust".to_string()));
+        assert_eq!(retarget("js", "js", "javascript"), Some("javascript".to_string()));
+        assert_eq!(retarget("json", "js", "javascript"), None);
+    }
+
+    #[test]
+    fn applies_operations_without_duplicating_tags() {
+        let tags = vec!["js".to_string(), "javascript".to_string(), "lang/go".to_string()];
+
+        let merged = apply_operation(&TagOperation::Merge { sources: vec!["js".to_string()], into: "javascript".to_string() }, &tags);
+        assert_eq!(merged, vec!["javascript", "lang/go"]);
+
+        let renamed = apply_operation(&TagOperation::Rename { from: "lang".to_string(), to: "language".to_string() }, &tags);
+        assert_eq!(renamed, vec!["js", "javascript", "language/go"]);
+
+        let deleted = apply_operation(&TagOperation::Delete("lang".to_string()), &tags);
+        assert_eq!(deleted, vec!["js", "javascript"]);
+    }
+
+    #[test]
+    fn lists_ancestors_outermost_first() {
+        assert_eq!(ancestors("a/b/c"), vec!["a", "a/b", "a/b/c"]);
+        assert_eq!(ancestors("rust"), vec!["rust"]);
+    }
+
+    #[test]
+    fn counts_each_prompt_once_per_level() {
+        let first = vec!["lang/rust".to_string(), "lang/go".to_string()];
+        let second = vec!["lang/rust".to_string(), "team/infra".to_string()];
+        let counts = count_tags([first.as_slice(), second.as_slice()]);
+
+        assert_eq!(counts["lang"], 2);
+        assert_eq!(counts["lang/rust"], 2);
+        assert_eq!(counts["lang/go"], 1);
+        assert_eq!(counts["team"], 1);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::utils::error::{Result, FinkError, PromptError};
+use crate::utils::frontmatter::FrontmatterDocument;
+use crate::utils::variables;
 use crate::application::models::PromptType;
+use std::collections::HashMap;
 
 pub struct TemplateGenerator;
 
@@ -42,15 +45,45 @@ impl TemplateGenerator {
         }
     }
     
-    fn prompt_type_to_string(prompt_type: PromptType) -> &'static str {
-        match prompt_type {
-            PromptType::Instruction => "instruction",
-            PromptType::Context => "context",
-            PromptType::InputIndicator => "input_indicator",
-            PromptType::OutputIndicator => "output_indicator",
-            PromptType::Etc => "etc",
-            PromptType::Whole => "whole",
+    /// Fills a user template from the templates directory. `{{name}}`, `{{type}}` and `{{date}}`
+    /// are substituted; other placeholders are left for the prompt's own variables.
+    /// Name, tags and type are added to the frontmatter when the template does not set them.
+    pub fn generate_from_user_template(template: &str, name: &str, prompt_type: PromptType) -> Result<String> {
+        let type_name = prompt_type.as_str();
+        let values: HashMap<String, String> = [
+            ("name", name.to_string()),
+            ("type", type_name.to_string()),
+            ("date", chrono::Local::now().format("%Y-%m-%d").to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+        
+        let content = variables::substitute(template, &values);
+        let mut document = FrontmatterDocument::parse(&content)?;
+        if !document.contains_key("name") {
+            document.set_string("name", name);
         }
+        if !document.contains_key("tags") {
+            document.set_raw("tags", "[]");
+        }
+        if !document.contains_key("type") {
+            document.set_string("type", type_name);
+        }
+        
+        Ok(document.to_content())
+    }
+    
+    /// Starting point written by `fink template new`
+    pub fn starter_user_template() -> &'static str {
+        r#"---
+name: "{{name}}"
+tags: []
+type: "{{type}}"
+---
+# {{name}}
+
+"#
     }
     
     fn generate_basic_template_with_type(name: &str, prompt_type: PromptType) -> String {
@@ -78,7 +111,7 @@ Please input your prompt's input data in here!
 (the type or format of the output)
 Please input your prompt's output indicator here!
 "#,
-            name, Self::prompt_type_to_string(prompt_type), name
+            name, prompt_type.as_str(), name
         )
     }
     
@@ -92,7 +125,7 @@ type: "{}"
 # {}
 
 "#,
-            name, Self::prompt_type_to_string(prompt_type), name
+            name, prompt_type.as_str(), name
         )
     }
     
@@ -108,7 +141,7 @@ type: "{}"
 <!-- Content from clipboard will be inserted below -->
 
 "#,
-            name, Self::prompt_type_to_string(prompt_type), name
+            name, prompt_type.as_str(), name
         )
     }
     
@@ -123,7 +156,7 @@ type: "{}"
 
 {}
 "#,
-            name, Self::prompt_type_to_string(prompt_type), name, content
+            name, prompt_type.as_str(), name, content
         )
     }
 }
@@ -153,11 +186,22 @@ mod tests {
     
     #[test]
     fn test_generate_with_custom_type() {
-        let result = TemplateGenerator::generate_with_type("test-prompt", None, PromptType::Instruction).unwrap();
+        let result = TemplateGenerator::generate_with_type("test-prompt", None, PromptType::INSTRUCTION).unwrap();
         assert!(result.contains(r#"type: "instruction""#));
         assert!(!result.contains(r#"type: "whole""#));
     }
     
+    #[test]
+    fn test_generate_from_user_template() {
+        let template = "---\ntags: [\"review\"]\n---\n# {{name}} ({{type}})\n\nReview {{language}} code.\n";
+        let result = TemplateGenerator::generate_from_user_template(template, "pr-review", PromptType::INSTRUCTION).unwrap();
+        assert!(result.contains(r#"tags: ["review"]"#));
+        assert!(result.contains(r#"name: "pr-review""#));
+        assert!(result.contains(r#"type: "instruction""#));
+        assert!(result.contains("# pr-review (instruction)"));
+        assert!(result.contains("Review {{language}} code."));
+    }
+    
     #[test]
     fn test_unknown_template() {
         let result = TemplateGenerator::generate("test", Some("unknown"));
@@ -0,0 +1,369 @@
+use std::collections::HashMap;
+use serde::Serialize;
+use serde_json::Value;
+use crate::utils::error::{Result, FinkError, ValidationError};
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+/// A `{{name}}` placeholder found in a prompt body
+struct Placeholder<'a> {
+    start: usize,
+    end: usize,
+    name: &'a str,
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Finds placeholders in order; braces around anything that is not a plain name are left alone
+fn placeholders(content: &str) -> Vec<Placeholder<'_>> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    
+    while let Some(open) = content[offset..].find(OPEN) {
+        let start = offset + open;
+        let inner_start = start + OPEN.len();
+        let Some(close) = content[inner_start..].find(CLOSE) else {
+            break;
+        };
+        let inner_end = inner_start + close;
+        let name = content[inner_start..inner_end].trim();
+        
+        if is_valid_name(name) {
+            let end = inner_end + CLOSE.len();
+            found.push(Placeholder { start, end, name });
+            offset = end;
+        } else {
+            offset = inner_start;
+        }
+    }
+    
+    found
+}
+
+/// Returns the distinct variable names in a prompt body, in order of first appearance
+pub fn extract_variables(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for placeholder in placeholders(content) {
+        if !names.iter().any(|n| n == placeholder.name) {
+            names.push(placeholder.name.to_string());
+        }
+    }
+    names
+}
+
+pub fn has_variables(content: &str) -> bool {
+    !placeholders(content).is_empty()
+}
+
+/// Replaces every placeholder that has a value, leaving the others untouched
+pub fn substitute(content: &str, values: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+    
+    for placeholder in placeholders(content) {
+        if let Some(value) = values.get(placeholder.name) {
+            result.push_str(&content[last..placeholder.start]);
+            result.push_str(value);
+            last = placeholder.end;
+        }
+    }
+    
+    result.push_str(&content[last..]);
+    result
+}
+
+/// Substitutes all variables, failing with the names of any left without a value
+pub fn render(content: &str, values: &HashMap<String, String>) -> Result<String> {
+    render_with_specs(content, &resolve_specs(content, &[]), values)
+}
+
+/// Substitutes variables using their declarations: defaults fill in missing values,
+/// values are checked against the declared type, and required variables must be set.
+pub fn render_with_specs(content: &str, specs: &[VariableSpec], values: &HashMap<String, String>) -> Result<String> {
+    let mut resolved = HashMap::new();
+    let mut missing = Vec::new();
+    
+    for spec in specs {
+        let value = values.get(&spec.name)
+            .filter(|value| !value.is_empty())
+            .or(spec.default.as_ref());
+        
+        match value {
+            Some(value) => {
+                spec.validate(value)?;
+                resolved.insert(spec.name.clone(), value.clone());
+            }
+            None if spec.required => missing.push(spec.name.clone()),
+            None => {
+                resolved.insert(spec.name.clone(), String::new());
+            }
+        }
+    }
+    
+    if !missing.is_empty() {
+        return Err(FinkError::Validation(ValidationError::UnresolvedVariables(missing)));
+    }
+    
+    Ok(substitute(content, &resolved))
+}
+
+/// Value type of a declared variable, which also decides the widget used to fill it in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableKind {
+    #[default]
+    String,
+    Multiline,
+    Number,
+    Bool,
+    Choice,
+}
+
+impl VariableKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "string" | "text" => Some(Self::String),
+            "multiline" => Some(Self::Multiline),
+            "number" | "int" | "integer" | "float" => Some(Self::Number),
+            "bool" | "boolean" => Some(Self::Bool),
+            "choice" | "enum" => Some(Self::Choice),
+            _ => None,
+        }
+    }
+}
+
+/// A variable as declared under `variables:` in the frontmatter
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VariableSpec {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: VariableKind,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+    pub default: Option<String>,
+    pub required: bool,
+}
+
+impl VariableSpec {
+    /// A plain string variable used in the body without a declaration; it must be given a value
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: None,
+            kind: VariableKind::String,
+            choices: Vec::new(),
+            default: None,
+            required: true,
+        }
+    }
+    
+    /// Builds a declaration from its YAML value. A bare string is taken as the description.
+    fn from_value(name: &str, value: &Value) -> Self {
+        let mut spec = Self { required: false, ..Self::new(name) };
+        
+        let fields = match value {
+            Value::Object(fields) => fields,
+            other => {
+                spec.description = scalar_to_string(other);
+                return spec;
+            }
+        };
+        
+        spec.description = fields.get("description").and_then(scalar_to_string);
+        spec.default = fields.get("default").and_then(scalar_to_string);
+        spec.required = match fields.get("required") {
+            Some(Value::Bool(required)) => *required,
+            Some(Value::String(required)) => required.eq_ignore_ascii_case("true"),
+            _ => false,
+        };
+        spec.choices = match fields.get("choices").or_else(|| fields.get("options")) {
+            Some(Value::Array(items)) => items.iter().filter_map(scalar_to_string).collect(),
+            _ => Vec::new(),
+        };
+        spec.kind = fields.get("type")
+            .and_then(scalar_to_string)
+            .and_then(|kind| VariableKind::parse(&kind))
+            .unwrap_or(if spec.choices.is_empty() { VariableKind::String } else { VariableKind::Choice });
+        
+        spec
+    }
+    
+    /// Checks that a value fits the declared type
+    pub fn validate(&self, value: &str) -> Result<()> {
+        let problem = match self.kind {
+            VariableKind::Number if value.trim().parse::<f64>().is_err() => Some("must be a number".to_string()),
+            VariableKind::Bool if !matches!(value.trim().to_lowercase().as_str(), "true" | "false") => {
+                Some("must be true or false".to_string())
+            }
+            VariableKind::Choice if !self.choices.iter().any(|choice| choice == value) => {
+                Some(format!("must be one of: {}", self.choices.join(", ")))
+            }
+            _ => None,
+        };
+        
+        match problem {
+            Some(problem) => Err(FinkError::Validation(ValidationError::InvalidInput(
+                "variable",
+                format!("'{}' {}, got '{}'", self.name, problem, value),
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Parses a `variables:` value, either a mapping of name to declaration or a list of
+/// declarations with a `name` field
+pub fn parse_declarations(value: &Value) -> Vec<VariableSpec> {
+    match value {
+        Value::Object(entries) => entries
+            .iter()
+            .filter(|(name, _)| is_valid_name(name))
+            .map(|(name, declaration)| VariableSpec::from_value(name, declaration))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                Value::String(name) if is_valid_name(name) => Some(VariableSpec { required: false, ..VariableSpec::new(name) }),
+                Value::Object(fields) => fields.get("name")
+                    .and_then(scalar_to_string)
+                    .filter(|name| is_valid_name(name))
+                    .map(|name| VariableSpec::from_value(&name, item)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Lists every variable of a prompt in the order it should be asked for: those used in the body
+/// first, in order of appearance, then declared ones the body does not reference yet
+pub fn resolve_specs(content: &str, declared: &[VariableSpec]) -> Vec<VariableSpec> {
+    let mut specs: Vec<VariableSpec> = extract_variables(content)
+        .iter()
+        .map(|name| declared.iter()
+            .find(|spec| &spec.name == name)
+            .cloned()
+            .unwrap_or_else(|| VariableSpec::new(name)))
+        .collect();
+    
+    for spec in declared {
+        if !specs.iter().any(|s| s.name == spec.name) {
+            specs.push(spec.clone());
+        }
+    }
+    
+    specs
+}
+
+/// Parses a `key=value` assignment as given to `--var`
+pub fn parse_assignment(assignment: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", assignment))?;
+    let key = key.trim();
+    
+    if !is_valid_name(key) {
+        return Err(format!("'{}' is not a valid variable name", key));
+    }
+    
+    Ok((key.to_string(), value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn extracts_distinct_names_in_order() {
+        let content = "Review {{language}} for {{ ticket }} in {{language}}";
+        assert_eq!(extract_variables(content), vec!["language", "ticket"]);
+    }
+
+    #[test]
+    fn ignores_braces_that_are_not_placeholders() {
+        let content = "fn main() {{ }} and {{not a name}} and {{ok}}";
+        assert_eq!(extract_variables(content), vec!["ok"]);
+    }
+
+    #[test]
+    fn renders_all_values() {
+        let content = "Review {{language}} for {{ ticket }}";
+        let rendered = render(content, &values(&[("language", "Rust"), ("ticket", "FINK-1")])).unwrap();
+        assert_eq!(rendered, "Review Rust for FINK-1");
+    }
+
+    #[test]
+    fn reports_missing_variables() {
+        let err = render("{{a}} {{b}}", &values(&[("a", "1")])).unwrap_err();
+        assert!(matches!(
+            err,
+            FinkError::Validation(ValidationError::UnresolvedVariables(ref names)) if names == &vec!["b".to_string()]
+        ));
+    }
+
+    fn declarations() -> Vec<VariableSpec> {
+        let yaml = serde_json::json!({
+            "language": {"description": "Language", "type": "choice", "choices": ["rust", "go"], "default": "rust"},
+            "count": {"type": "number", "required": true},
+            "notes": "Anything else",
+        });
+        parse_declarations(&yaml)
+    }
+
+    #[test]
+    fn parses_declarations() {
+        let specs = declarations();
+        let language = specs.iter().find(|s| s.name == "language").unwrap();
+        assert_eq!(language.kind, VariableKind::Choice);
+        assert_eq!(language.choices, vec!["rust", "go"]);
+        assert_eq!(language.default.as_deref(), Some("rust"));
+        let notes = specs.iter().find(|s| s.name == "notes").unwrap();
+        assert_eq!(notes.description.as_deref(), Some("Anything else"));
+        assert!(!notes.required);
+    }
+
+    #[test]
+    fn orders_specs_by_body_then_declarations() {
+        let specs = resolve_specs("{{count}} {{ticket}} {{language}}", &declarations());
+        let names: Vec<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names[..3], ["count", "ticket", "language"]);
+        assert!(names.contains(&"notes"));
+        assert!(specs[1].required);
+    }
+
+    #[test]
+    fn applies_defaults_and_validates_types() {
+        let content = "{{language}} x{{count}}";
+        let specs = resolve_specs(content, &declarations());
+        assert_eq!(render_with_specs(content, &specs, &values(&[("count", "3")])).unwrap(), "rust x3");
+        assert!(render_with_specs(content, &specs, &values(&[("count", "many")])).is_err());
+        assert!(render_with_specs(content, &specs, &values(&[("count", "3"), ("language", "java")])).is_err());
+        assert!(render_with_specs(content, &specs, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn parses_assignments() {
+        assert_eq!(parse_assignment("lang=Rust = 2024").unwrap(), ("lang".to_string(), "Rust = 2024".to_string()));
+        assert!(parse_assignment("lang").is_err());
+        assert!(parse_assignment("1x=y").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use fink::application::models::{PromptMetadata, PromptType};
+    use fink::application::models::{PromptFilter, PromptMetadata, PromptType, TagMatch};
 
     #[test]
     fn should_create_prompt_metadata_with_type() {
@@ -8,11 +8,12 @@ mod tests {
             name: "test-prompt".to_string(),
             file_path: "/path/to/test.md".to_string(),
             tags: vec!["tag1".to_string(), "tag2".to_string()],
-            prompt_type: PromptType::Instruction,
+            prompt_type: PromptType::INSTRUCTION,
+            ..Default::default()
         };
 
         assert_eq!(metadata.name, "test-prompt");
-        assert_eq!(metadata.prompt_type, PromptType::Instruction);
+        assert_eq!(metadata.prompt_type, PromptType::INSTRUCTION);
     }
 
     #[test]
@@ -22,8 +23,44 @@ mod tests {
             file_path: "/path/to/test.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         };
 
-        assert_eq!(metadata.prompt_type, PromptType::Whole);
+        assert_eq!(metadata.prompt_type, PromptType::WHOLE);
     }
-}
\ No newline at end of file
+
+    fn prompt(tags: &[&str], prompt_type: PromptType) -> PromptMetadata {
+        PromptMetadata {
+            name: "test-prompt".to_string(),
+            file_path: "test.md".to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            prompt_type,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_match_filter_tags_by_mode() {
+        let review = prompt(&["rust", "review"], PromptType::INSTRUCTION);
+        let debug = prompt(&["rust", "debug"], PromptType::CONTEXT);
+        let tags = Some(vec!["rust".to_string(), "review".to_string()]);
+
+        let any = PromptFilter { tags: tags.clone(), ..Default::default() };
+        assert!(any.matches(&review) && any.matches(&debug));
+
+        let all = PromptFilter { tags, tag_match: TagMatch::All, ..Default::default() };
+        assert!(all.matches(&review) && !all.matches(&debug));
+    }
+
+    #[test]
+    fn should_exclude_tags_and_filter_types() {
+        let review = prompt(&["rust", "review"], PromptType::INSTRUCTION);
+        let deprecated = prompt(&["rust", "deprecated/2023"], PromptType::CONTEXT);
+
+        let exclude = PromptFilter { exclude_tags: vec!["deprecated".to_string()], ..Default::default() };
+        assert!(exclude.matches(&review) && !exclude.matches(&deprecated));
+
+        let context = PromptFilter { types: vec![PromptType::CONTEXT], ..Default::default() };
+        assert!(!context.matches(&review) && context.matches(&deprecated));
+    }
+}
@@ -2,7 +2,7 @@ use fink::application::traits::PromptApplication;
 use fink::application::app::DefaultPromptApplication;
 use tempfile::TempDir;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 fn setup_test_app() -> (TempDir, DefaultPromptApplication) {
     let temp_dir = TempDir::new().unwrap();
@@ -16,7 +16,7 @@ fn setup_test_app() -> (TempDir, DefaultPromptApplication) {
     (temp_dir, app)
 }
 
-fn create_test_prompt(base_path: &PathBuf, name: &str, content: &str) {
+fn create_test_prompt(base_path: &Path, name: &str, content: &str) {
     let file_path = base_path.join("prompts").join(format!("{}.md", name));
     fs::write(file_path, content).unwrap();
 }
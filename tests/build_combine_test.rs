@@ -162,6 +162,6 @@ Whole prompt content"#;
         let build_prompts = app.get_build_prompts();
         assert_eq!(build_prompts.len(), 1);
         assert_eq!(build_prompts[0].name, "instruction-prompt");
-        assert_ne!(build_prompts[0].prompt_type, PromptType::Whole);
+        assert_ne!(build_prompts[0].prompt_type, PromptType::WHOLE);
     }
 }
\ No newline at end of file
@@ -0,0 +1,117 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+fn setup_prompts(base_path: &std::path::Path) {
+    let prompts_path = base_path.join("prompts");
+    fs::create_dir_all(prompts_path.join("rust")).unwrap();
+    let prompts = [
+        ("reviewer.md", "reviewer", "instruction", "You are a careful reviewer."),
+        ("style.md", "style", "context", "Follow the style guide."),
+        ("rust/idioms.md", "idioms", "context", "Prefer iterators."),
+        ("answer.md", "answer", "output_indicator", "Answer in bullet points."),
+    ];
+    for (file, name, prompt_type, body) in prompts {
+        fs::write(
+            prompts_path.join(file),
+            format!("---\nname: \"{}\"\ntype: \"{}\"\n---\n{}", name, prompt_type, body),
+        ).unwrap();
+    }
+}
+
+#[test]
+fn should_combine_prompts_by_type_in_build_order() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--output", "answer", "--context", "style", "--context", "rust/idioms", "--instruction", "reviewer", "--comment", "Be brief", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "You are a careful reviewer.\n\nFollow the style guide.\n\nPrefer iterators.\n\nAnswer in bullet points.\n\n# Additional Notes:\nBe brief\n",
+        ));
+}
+
+#[test]
+fn should_combine_prompts_given_with_their_type() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--prompt", "context=style", "--prompt", "instruction=reviewer", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("You are a careful reviewer.\n\nFollow the style guide.\n"));
+}
+
+#[test]
+fn should_build_in_requested_format() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--instruction", "reviewer", "--format", "xml", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("<instruction>\nYou are a careful reviewer.\n</instruction>\n"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--instruction", "reviewer", "--format", "yaml", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown output format"));
+}
+
+#[test]
+fn should_fail_for_unknown_or_missing_prompts() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--context", "missing", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--comment", "only a note", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--instruction"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--instruction", "style", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'style' is a context prompt, not instruction"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--prompt", "style", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected TYPE=NAME"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "some-recipe", "--context", "style", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure();
+}
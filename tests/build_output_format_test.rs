@@ -0,0 +1,103 @@
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::{AppMode, TUIApp};
+use fink::presentation::tui::components::BuildStep;
+use fink::utils::config::Config;
+use fink::utils::output_format::OutputFormat;
+use std::fs;
+use tempfile::tempdir;
+
+fn setup_prompts(base_path: &std::path::Path) {
+    let prompts_path = base_path.join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(
+        prompts_path.join("reviewer.md"),
+        "---\nname: \"reviewer\"\ntype: \"instruction\"\n---\nYou are a careful reviewer.",
+    ).unwrap();
+    fs::write(
+        prompts_path.join("style.md"),
+        "---\nname: \"style\"\ntype: \"context\"\n---\nFollow the style guide.",
+    ).unwrap();
+}
+
+#[test]
+fn should_combine_prompts_in_each_format() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    let ids = vec!["reviewer".to_string(), "style".to_string()];
+
+    // Act & Assert
+    assert_eq!(
+        app.combine_prompts(&ids, "", OutputFormat::Markdown).unwrap(),
+        "## Instruction\n\nYou are a careful reviewer.\n\n## Context\n\nFollow the style guide."
+    );
+    assert_eq!(
+        app.combine_prompts(&ids, "Be brief", OutputFormat::Xml).unwrap(),
+        "<instruction>\nYou are a careful reviewer.\n</instruction>\n\n<context>\nFollow the style guide.\n</context>\n\n<notes>\nBe brief\n</notes>"
+    );
+
+    let messages: serde_json::Value = serde_json::from_str(&app.combine_prompts(&ids, "", OutputFormat::Json).unwrap()).unwrap();
+    assert_eq!(messages.as_array().unwrap().len(), 2);
+    assert_eq!(messages[0]["role"], "system");
+    assert_eq!(messages[1]["content"], "Follow the style guide.");
+}
+
+#[test]
+fn should_remember_format_picked_in_build_mode() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let config_path = temp_dir.path().join("config.toml");
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_mode_and_config_path(&config, AppMode::QuickSelect, config_path.clone()).unwrap();
+    app.enter_build_mode();
+
+    let panel = app.get_interactive_build_panel_mut().unwrap();
+    assert_eq!(panel.format(), OutputFormat::Plain);
+    panel.next();
+    panel.select_current();
+    while panel.current_step != BuildStep::AddComment {
+        panel.select_current();
+    }
+    panel.finish_comment();
+
+    // Act
+    panel.cycle_format();
+    panel.cycle_format();
+    assert_eq!(panel.format(), OutputFormat::Xml);
+    app.combine_and_copy_selected_prompts().unwrap();
+
+    // Assert
+    assert_eq!(app.get_config().build_format(), OutputFormat::Xml);
+    let saved = Config::load_from_file(&config_path).unwrap();
+    assert_eq!(saved.build_format(), OutputFormat::Xml);
+
+    app.enter_build_mode();
+    assert_eq!(app.get_interactive_build_panel().unwrap().format(), OutputFormat::Xml);
+}
+
+#[test]
+fn should_run_recipe_in_the_format_picked_in_build_mode() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap()
+        .save_recipe("review", vec!["reviewer".to_string(), "style".to_string()], "").unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_mode_and_config_path(&config, AppMode::QuickSelect, config_path).unwrap();
+    app.enter_build_mode();
+
+    // Act
+    let panel = app.get_interactive_build_panel_mut().unwrap();
+    panel.cycle_format();
+    assert_eq!(panel.format(), OutputFormat::Markdown);
+    app.run_recipe(0).unwrap();
+
+    // Assert
+    assert_eq!(app.get_config().build_format(), OutputFormat::Markdown);
+}
@@ -0,0 +1,145 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::TUIApp;
+use fink::presentation::tui::components::BuildStep;
+use fink::utils::config::Config;
+use fink::utils::output_format::OutputFormat;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+fn setup_prompts(base_path: &std::path::Path) {
+    let prompts_path = base_path.join("prompts");
+    fs::create_dir_all(prompts_path.join("backend")).unwrap();
+    fs::write(
+        prompts_path.join("backend/reviewer.md"),
+        "---\nname: \"reviewer\"\ntype: \"instruction\"\n---\nYou are a careful reviewer.",
+    ).unwrap();
+    fs::write(
+        prompts_path.join("style.md"),
+        "---\nname: \"style\"\ntype: \"context\"\n---\nFollow the style guide.",
+    ).unwrap();
+}
+
+#[test]
+fn should_save_and_build_recipe() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    app.save_recipe("review", vec!["backend/reviewer".to_string(), "style".to_string()], "Be brief").unwrap();
+
+    // Assert
+    assert!(temp_dir.path().join("recipes/review.json").exists());
+    let recipes = app.list_recipes().unwrap();
+    assert_eq!(recipes.len(), 1);
+    assert_eq!(recipes[0].prompts, vec!["backend/reviewer", "style"]);
+    assert_eq!(
+        app.build_recipe("review", OutputFormat::Plain).unwrap(),
+        "You are a careful reviewer.\n\nFollow the style guide.\n\n# Additional Notes:\nBe brief"
+    );
+}
+
+#[test]
+fn should_find_recipes_by_their_normalized_name() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    fs::write(temp_dir.path().join("outside.json"), r#"{"name":"x","prompts":["style"],"comment":"","created_at":"2024-01-01T00:00:00+00:00"}"#).unwrap();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    app.save_recipe("My Recipe", vec!["style".to_string()], "").unwrap();
+
+    // Act & Assert
+    assert_eq!(app.build_recipe("My Recipe", OutputFormat::Plain).unwrap(), "Follow the style guide.");
+    assert!(app.build_recipe("../outside", OutputFormat::Plain).is_err());
+    app.delete_recipe("My Recipe").unwrap();
+    assert!(app.list_recipes().unwrap().is_empty());
+}
+
+#[test]
+fn should_reject_recipe_without_prompts() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let result = app.save_recipe("empty", Vec::new(), "");
+
+    // Assert
+    assert!(result.is_err());
+    assert!(app.list_recipes().unwrap().is_empty());
+}
+
+#[test]
+fn should_list_and_run_recipes_from_cli() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    app.save_recipe("review", vec!["backend/reviewer".to_string(), "style".to_string()], "").unwrap();
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("review  backend/reviewer + style"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "review", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("You are a careful reviewer.\n\nFollow the style guide."));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["build", "missing", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Recipe not found"));
+}
+
+#[test]
+fn should_save_completed_build_as_recipe_from_tui() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    app.enter_build_mode();
+
+    let panel = app.get_interactive_build_panel_mut().unwrap();
+    panel.next();
+    panel.select_current(); // reviewer
+    panel.next();
+    panel.select_current(); // style
+    while panel.current_step != BuildStep::AddComment {
+        panel.select_current();
+    }
+    panel.finish_comment();
+    assert_eq!(panel.current_step, BuildStep::Complete);
+
+    // Act
+    panel.start_saving_recipe();
+    "review".chars().for_each(|c| panel.add_recipe_name_char(c));
+    app.save_build_as_recipe().unwrap();
+
+    // Assert
+    assert!(!app.get_interactive_build_panel().unwrap().is_naming_recipe());
+    let application = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    let recipes = application.list_recipes().unwrap();
+    assert_eq!(recipes.len(), 1);
+    assert_eq!(recipes[0].prompts, vec!["backend/reviewer", "style"]);
+
+    // Re-entering build mode offers the saved recipe
+    app.exit_build_mode();
+    app.enter_build_mode();
+    assert_eq!(app.get_interactive_build_panel().unwrap().recipes().len(), 1);
+}
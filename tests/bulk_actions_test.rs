@@ -0,0 +1,150 @@
+use fink::application::app::DefaultPromptApplication;
+use fink::application::models::PromptType;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::{AppMode, TUIApp};
+use fink::presentation::tui::components::confirmation_dialog::{BulkAction, ConfirmationAction};
+use fink::utils::config::Config;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup_library() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(prompts_dir.join("backend")).unwrap();
+    fs::write(prompts_dir.join("alpha.md"), "---\nname: \"alpha\"\ntags: [\"draft\"]\ntype: \"whole\"\n---\nAlpha").unwrap();
+    fs::write(prompts_dir.join("beta.md"), "---\nname: \"beta\"\ntags: []\ntype: \"whole\"\n---\nBeta").unwrap();
+    fs::write(prompts_dir.join("gamma.md"), "---\nname: \"gamma\"\ntags: [\"draft\"]\ntype: \"whole\"\n---\nGamma").unwrap();
+    fs::write(prompts_dir.join("backend").join("delta.md"), "---\nname: \"delta\"\ntags: []\ntype: \"whole\"\n---\nDelta").unwrap();
+    temp_dir
+}
+
+fn management_app(temp_dir: &TempDir) -> TUIApp {
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_mode_and_config(&config, AppMode::Management).unwrap();
+    // Start from the first listed prompt whatever state was saved
+    while app.get_list_state().selected() != Some(0) {
+        app.next();
+    }
+    app
+}
+
+fn marked_names(app: &TUIApp) -> Vec<String> {
+    app.get_marked_prompts().into_iter().map(|p| p.name).collect()
+}
+
+fn read_prompt(temp_dir: &TempDir, path: &str) -> String {
+    fs::read_to_string(temp_dir.path().join("prompts").join(path)).unwrap()
+}
+
+#[test]
+fn test_mark_with_space_and_by_range() {
+    let temp_dir = setup_library();
+    let mut app = management_app(&temp_dir);
+
+    // Listed as alpha, backend/delta, beta, gamma
+    app.toggle_mark_selected();
+    app.next();
+    app.next();
+    app.mark_range_to_selected();
+    assert_eq!(marked_names(&app), vec!["alpha", "delta", "beta"]);
+
+    // Marking again unmarks
+    app.toggle_mark_selected();
+    assert_eq!(marked_names(&app), vec!["alpha", "delta"]);
+
+    app.clear_marks();
+    assert!(!app.has_marks());
+}
+
+#[test]
+fn test_bulk_tag_is_confirmed_once_for_all_marked_prompts() {
+    let temp_dir = setup_library();
+    let mut app = management_app(&temp_dir);
+
+    app.toggle_mark_selected();
+    app.next();
+    app.toggle_mark_selected();
+    app.show_bulk_confirmation(BulkAction::AddTag("review".to_string()));
+
+    let dialog = app.get_confirmation_dialog().unwrap();
+    assert_eq!(dialog.get_message(), "Add tag 'review' to 2 prompts?");
+    assert_eq!(dialog.get_details(), ["alpha", "backend/delta"]);
+    assert!(!read_prompt(&temp_dir, "alpha.md").contains("review"));
+
+    app.confirm_action().unwrap();
+    assert!(read_prompt(&temp_dir, "alpha.md").contains(r#"tags: ["draft", "review"]"#));
+    assert!(read_prompt(&temp_dir, "backend/delta.md").contains(r#"tags: ["review"]"#));
+    assert!(!read_prompt(&temp_dir, "beta.md").contains("review"));
+    assert!(!app.has_marks());
+    assert_eq!(app.get_success_message(), Some("Added 'review' to 2 prompt(s)"));
+}
+
+#[test]
+fn test_bulk_type_change_and_delete() {
+    let temp_dir = setup_library();
+    let mut app = management_app(&temp_dir);
+
+    app.toggle_mark_selected();
+    app.next();
+    app.next();
+    app.next();
+    app.toggle_mark_selected();
+
+    // The type starts after the current one and can be changed before confirming
+    app.show_bulk_type_confirmation();
+    app.cycle_bulk_type(true);
+    match app.get_confirmation_dialog().unwrap().get_action() {
+        ConfirmationAction::Bulk(BulkAction::SetType(prompt_type), ids) => {
+            assert_eq!(*prompt_type, PromptType::CONTEXT);
+            assert_eq!(ids, &["alpha".to_string(), "gamma".to_string()]);
+        }
+        action => panic!("unexpected action {:?}", action),
+    }
+    app.confirm_action().unwrap();
+    assert!(read_prompt(&temp_dir, "gamma.md").contains("type: \"context\""));
+    assert!(read_prompt(&temp_dir, "beta.md").contains("type: \"whole\""));
+
+    // Cancelling keeps the marks for another action
+    app.toggle_mark_selected();
+    app.show_bulk_confirmation(BulkAction::Delete);
+    app.cancel_confirmation();
+    assert_eq!(app.marked_count(), 1);
+    app.show_bulk_confirmation(BulkAction::Delete);
+    app.confirm_action().unwrap();
+    assert_eq!(app.get_prompts().len(), 3);
+}
+
+#[test]
+fn test_bulk_star_unstars_when_all_are_starred() {
+    let temp_dir = setup_library();
+    let mut app = management_app(&temp_dir);
+
+    app.toggle_mark_selected();
+    app.show_bulk_star_confirmation();
+    app.confirm_action().unwrap();
+    assert!(read_prompt(&temp_dir, "alpha.md").contains("starred"));
+
+    app.mark_range_to_selected();
+    app.show_bulk_star_confirmation();
+    assert_eq!(app.get_confirmation_message().unwrap(), "Remove the star from 1 prompt?");
+}
+
+#[test]
+fn test_export_prompts_keeps_folders() {
+    let temp_dir = setup_library();
+    let export_dir = TempDir::new().unwrap();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    let ids = vec!["alpha".to_string(), "backend/delta".to_string()];
+    let files = app.export_prompts(&ids, export_dir.path()).unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(
+        fs::read_to_string(export_dir.path().join("backend/delta.md")).unwrap(),
+        read_prompt(&temp_dir, "backend/delta.md")
+    );
+
+    // Exporting over existing files is refused
+    let error = app.export_prompts(&ids, export_dir.path()).unwrap_err();
+    assert!(error.to_string().contains("already exists"));
+}
@@ -159,7 +159,7 @@ fn test_create_dialog_with_type_selection() {
     // Verify the state
     assert_eq!(dialog.get_normalized_filename(), "test");
     assert_eq!(dialog.get_template(), CreateTemplate::FromClipboard);
-    assert_eq!(dialog.get_prompt_type(), fink::application::models::PromptType::Context);
+    assert_eq!(dialog.get_prompt_type(), fink::application::models::PromptType::CONTEXT);
     assert!(dialog.is_valid());
 }
 
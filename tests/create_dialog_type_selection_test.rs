@@ -15,7 +15,7 @@ mod tests {
     #[test]
     fn should_default_to_whole_type() {
         let dialog = CreateDialog::new();
-        assert_eq!(dialog.get_prompt_type(), PromptType::Whole);
+        assert_eq!(dialog.get_prompt_type(), PromptType::WHOLE);
     }
 
     #[test]
@@ -27,22 +27,22 @@ mod tests {
         
         // Cycle through types
         dialog.next_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::Instruction);
+        assert_eq!(dialog.get_prompt_type(), PromptType::INSTRUCTION);
         
         dialog.next_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::Context);
+        assert_eq!(dialog.get_prompt_type(), PromptType::CONTEXT);
         
         dialog.next_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::InputIndicator);
+        assert_eq!(dialog.get_prompt_type(), PromptType::INPUT_INDICATOR);
         
         dialog.next_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::OutputIndicator);
+        assert_eq!(dialog.get_prompt_type(), PromptType::OUTPUT_INDICATOR);
         
         dialog.next_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::Etc);
+        assert_eq!(dialog.get_prompt_type(), PromptType::ETC);
         
         dialog.next_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::Whole);
+        assert_eq!(dialog.get_prompt_type(), PromptType::WHOLE);
     }
 
     #[test]
@@ -54,10 +54,10 @@ mod tests {
         
         // Cycle backwards
         dialog.previous_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::Etc);
+        assert_eq!(dialog.get_prompt_type(), PromptType::ETC);
         
         dialog.previous_type();
-        assert_eq!(dialog.get_prompt_type(), PromptType::OutputIndicator);
+        assert_eq!(dialog.get_prompt_type(), PromptType::OUTPUT_INDICATOR);
     }
 
     #[test]
@@ -0,0 +1,182 @@
+use fink::application::app::DefaultPromptApplication;
+use fink::application::models::{PromptType, PromptTypes, TypeDefinition};
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::{AppMode, TUIApp};
+use fink::presentation::tui::components::{BuildStep, DialogField};
+use fink::utils::config::Config;
+use fink::utils::output_format::OutputFormat;
+use std::fs;
+use tempfile::tempdir;
+
+fn team_types() -> PromptTypes {
+    PromptTypes::new(vec![
+        TypeDefinition::new(PromptType::new("persona"), "Persona", "Who the model should be", true),
+        TypeDefinition::new(PromptType::new("constraints"), "Constraints", "", true),
+        TypeDefinition::new(PromptType::new("examples"), "Examples", "", false),
+    ])
+}
+
+fn setup_prompts(base_path: &std::path::Path) {
+    let prompts_path = base_path.join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(
+        prompts_path.join("reviewer.md"),
+        "---\nname: \"reviewer\"\ntype: \"persona\"\n---\nYou are a careful reviewer.",
+    ).unwrap();
+    fs::write(
+        prompts_path.join("short.md"),
+        "---\nname: \"short\"\ntype: \"constraints\"\n---\nKeep it short.",
+    ).unwrap();
+}
+
+#[test]
+fn should_round_trip_prompt_types_through_config_file() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    let mut config = Config::default();
+    config.set_prompt_types(team_types());
+
+    // Act
+    config.save(&config_path).unwrap();
+    let loaded = Config::load_from_file(&config_path).unwrap();
+
+    // Assert
+    assert_eq!(loaded.prompt_types(), &team_types());
+    assert_eq!(loaded.build_types(), vec![PromptType::new("persona"), PromptType::new("constraints")]);
+}
+
+#[test]
+fn should_default_to_built_in_types_when_config_omits_them() {
+    let temp_dir = tempdir().unwrap();
+    let config_path = temp_dir.path().join("config.toml");
+    fs::write(&config_path, "editor = \"vim\"\nstorage_path = \"/tmp/prompts\"\n").unwrap();
+
+    let loaded = Config::load_from_file(&config_path).unwrap();
+
+    assert_eq!(loaded.prompt_types(), &PromptTypes::default());
+    assert_eq!(loaded.build_types()[0], PromptType::INSTRUCTION);
+}
+
+#[test]
+fn should_cycle_configured_types_in_create_dialog() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    config.set_prompt_types(team_types());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+
+    // Act
+    app.open_create_dialog();
+    let dialog = app.get_create_dialog_mut().unwrap();
+    while dialog.current_field() != DialogField::Type {
+        dialog.next_field();
+    }
+
+    // Assert
+    assert_eq!(dialog.get_prompt_type(), PromptType::new("persona"));
+    dialog.next_type();
+    assert_eq!(dialog.get_prompt_type(), PromptType::new("constraints"));
+    dialog.next_type();
+    dialog.next_type();
+    assert_eq!(dialog.get_prompt_type(), PromptType::new("persona"));
+    dialog.previous_type();
+    assert_eq!(dialog.get_prompt_type(), PromptType::new("examples"));
+}
+
+#[test]
+fn should_follow_configured_build_order() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    config.set_prompt_types(team_types());
+    let mut app = TUIApp::new_with_mode_and_config_path(&config, AppMode::QuickSelect, temp_dir.path().join("config.toml")).unwrap();
+
+    // Act
+    app.enter_build_mode();
+    let panel = app.get_interactive_build_panel_mut().unwrap();
+
+    // Assert
+    assert_eq!(panel.current_step, BuildStep::Select(PromptType::new("persona")));
+    panel.next();
+    panel.select_current();
+    assert_eq!(panel.current_step, BuildStep::Select(PromptType::new("constraints")));
+    panel.next();
+    panel.select_current();
+    assert_eq!(panel.current_step, BuildStep::AddComment);
+    panel.go_back();
+    assert_eq!(panel.current_step, BuildStep::Select(PromptType::new("constraints")));
+}
+
+#[test]
+fn should_name_custom_types_in_combined_output() {
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    config.set_prompt_types(team_types());
+    let app = DefaultPromptApplication::with_config(&config).unwrap();
+    let ids = vec!["reviewer".to_string(), "short".to_string()];
+
+    let combined = app.combine_prompts(&ids, "", OutputFormat::Markdown).unwrap();
+
+    assert_eq!(combined, "## Persona\n\nYou are a careful reviewer.\n\n## Constraints\n\nKeep it short.");
+}
+
+#[test]
+fn should_check_types_when_combining_typed_prompts() {
+    let temp_dir = tempdir().unwrap();
+    setup_prompts(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    config.set_prompt_types(team_types());
+    let app = DefaultPromptApplication::with_config(&config).unwrap();
+
+    let combined = app.combine_typed_prompts(&[(PromptType::new("persona"), "reviewer".to_string())], "", OutputFormat::Plain);
+    assert_eq!(combined.unwrap(), "You are a careful reviewer.");
+
+    let wrong_type = app.combine_typed_prompts(&[(PromptType::new("constraints"), "reviewer".to_string())], "", OutputFormat::Plain);
+    assert!(wrong_type.unwrap_err().to_string().contains("'reviewer' is a persona prompt, not constraints"));
+
+    let undeclared = app.combine_typed_prompts(&[(PromptType::INSTRUCTION, "reviewer".to_string())], "", OutputFormat::Plain);
+    assert!(undeclared.unwrap_err().to_string().contains("unknown prompt type 'instruction'"));
+}
+
+#[test]
+fn should_match_custom_types_case_insensitively() {
+    let temp_dir = tempdir().unwrap();
+    let prompts_path = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(prompts_path.join("mentor.md"), "---\nname: \"mentor\"\ntype: \"Persona\"\n---\nYou are a patient mentor.").unwrap();
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    config.set_prompt_types(team_types());
+    let app = DefaultPromptApplication::with_config(&config).unwrap();
+
+    let prompts = app.list_prompts(None).unwrap();
+
+    assert_eq!(prompts[0].prompt_type, PromptType::new("persona"));
+    assert!(config.prompt_types().get(&prompts[0].prompt_type).is_some());
+}
+
+#[test]
+fn should_keep_unknown_types_when_listing() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_path = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    let file_path = prompts_path.join("draft.md");
+    let content = "---\nname: \"draft\"\ntype: \"format\"\n---\nAnswer in a table.";
+    fs::write(&file_path, content).unwrap();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let prompts = app.list_prompts(None).unwrap();
+
+    // Assert
+    assert_eq!(prompts[0].prompt_type, PromptType::new("format"));
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), content);
+}
@@ -49,7 +49,7 @@ fn test_initialization_should_use_prompts_directory() {
     let entries: Vec<_> = fs::read_dir(&prompts_dir)
         .unwrap()
         .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "md"))
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "md"))
         .collect();
     
     assert!(!entries.is_empty(), "Should have created prompt files");
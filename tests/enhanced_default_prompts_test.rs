@@ -48,10 +48,10 @@ fn test_initializing_enhanced_prompts_creates_all_files() {
     let temp_dir = TempDir::new().unwrap();
     let prompts_dir = temp_dir.path();
     
-    initialize_default_prompts(&prompts_dir).unwrap();
+    initialize_default_prompts(prompts_dir).unwrap();
     
     // Check all prompts were created
-    let created_files: Vec<_> = fs::read_dir(&prompts_dir)
+    let created_files: Vec<_> = fs::read_dir(prompts_dir)
         .unwrap()
         .filter_map(|entry| {
             entry.ok().and_then(|e| {
@@ -42,7 +42,7 @@ fn test_accepting_initialization_should_create_default_prompts() {
     
     // Should have created default prompts
     app.reload_prompts().unwrap();
-    assert!(app.get_prompts().len() > 0, "Should have default prompts after accepting");
+    assert!(!app.get_prompts().is_empty(), "Should have default prompts after accepting");
     
     // Check that .initialized flag exists
     let init_flag = temp_dir.path().join("prompts").join(".initialized");
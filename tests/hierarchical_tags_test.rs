@@ -0,0 +1,74 @@
+use fink::application::app::DefaultPromptApplication;
+use fink::application::models::PromptFilter;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::{TUIApp, AppMode};
+use fink::presentation::tui::components::TagFilterDialog;
+use fink::utils::config::Config;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use tempfile::TempDir;
+
+fn create_prompts(temp_dir: &TempDir) {
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("rust-review.md"), "---\nname: \"Rust Review\"\ntags: [\"lang/rust\"]\n---\nReview").unwrap();
+    fs::write(prompts_dir.join("go-review.md"), "---\nname: \"Go Review\"\ntags: [\"lang/go\"]\n---\nReview").unwrap();
+    fs::write(prompts_dir.join("infra.md"), "---\nname: \"Infra\"\ntags: [\"team/infra\"]\n---\nDeploy").unwrap();
+    fs::write(prompts_dir.join("language.md"), "---\nname: \"Language\"\ntags: [\"language\"]\n---\nWords").unwrap();
+}
+
+#[test]
+fn test_tag_filter_dialog_shows_tree() {
+    let tags = vec!["lang/go".to_string(), "lang/rust".to_string(), "team/infra".to_string()];
+    let counts = HashMap::from([("lang".to_string(), 2), ("lang/go".to_string(), 1)]);
+    let mut dialog = TagFilterDialog::new(tags, HashSet::new()).with_counts(counts);
+
+    // Parents are listed before their children even without prompts of their own
+    assert_eq!(dialog.get_filtered_tags(), vec!["lang", "lang/go", "lang/rust", "team", "team/infra"]);
+
+    // Collapsing a parent hides its children
+    dialog.toggle_mode();
+    dialog.collapse_selected();
+    assert_eq!(dialog.get_filtered_tags(), vec!["lang", "team", "team/infra"]);
+
+    dialog.expand_selected();
+    assert_eq!(dialog.get_filtered_tags().len(), 5);
+
+    // On a child, collapsing moves back to its parent
+    dialog.move_down();
+    dialog.move_down();
+    dialog.collapse_selected();
+    assert_eq!(dialog.get_selected_tag(), Some("lang".to_string()));
+}
+
+#[test]
+fn test_parent_tag_filter_matches_descendants() {
+    let temp_dir = TempDir::new().unwrap();
+    create_prompts(&temp_dir);
+
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_mode_and_config(&config, AppMode::QuickSelect).unwrap();
+
+    app.set_tag_filters(HashSet::from(["lang".to_string()]));
+    let mut names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    names.sort();
+    assert_eq!(names, vec!["Go Review", "Rust Review"]);
+
+    let counts = app.get_tag_counts();
+    assert_eq!(counts["lang"], 2);
+    assert_eq!(counts["team/infra"], 1);
+}
+
+#[test]
+fn test_list_prompts_matches_descendants_of_parent_tag() {
+    let temp_dir = TempDir::new().unwrap();
+    create_prompts(&temp_dir);
+
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    let filter = PromptFilter { tags: Some(vec!["team".to_string()]), ..Default::default() };
+    let prompts = app.list_prompts(Some(filter)).unwrap();
+
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "Infra");
+}
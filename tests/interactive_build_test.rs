@@ -44,7 +44,7 @@ Context information here."#;
         
         if let Some(panel) = app.get_interactive_build_panel_mut() {
             // Should start with instruction selection
-            assert_eq!(panel.current_step, BuildStep::SelectInstruction);
+            assert_eq!(panel.current_step, BuildStep::SELECT_INSTRUCTION);
             
             // Get options (should include "None" and available instruction prompts)
             let options = panel.get_current_options();
@@ -56,13 +56,13 @@ Context information here."#;
             panel.select_current();
             
             // Should move to context selection
-            assert_eq!(panel.current_step, BuildStep::SelectContext);
+            assert_eq!(panel.current_step, BuildStep::SELECT_CONTEXT);
             
             // Select "None" for context
             panel.select_current(); // Select "None" (default selection)
             
             // Should continue through remaining steps
-            assert_eq!(panel.current_step, BuildStep::SelectInputIndicator);
+            assert_eq!(panel.current_step, BuildStep::SELECT_INPUT_INDICATOR);
         }
     }
 
@@ -167,8 +167,145 @@ Context content"#;
             // Get selected prompts
             let selected = panel.get_selected_prompt_names();
             assert_eq!(selected.len(), 2);
-            assert_eq!(selected[0], (PromptType::Instruction, "my-instruction".to_string()));
-            assert_eq!(selected[1], (PromptType::Context, "my-context".to_string()));
+            assert_eq!(selected[0], (PromptType::INSTRUCTION, "my-instruction".to_string()));
+            assert_eq!(selected[1], (PromptType::CONTEXT, "my-context".to_string()));
         }
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_multi_select_keeps_selection_order() {
+        let temp_dir = tempdir().unwrap();
+        let jkms_path = temp_dir.path().join("prompts");
+        fs::create_dir_all(&jkms_path).unwrap();
+        
+        for name in ["context-a", "context-b", "context-c"] {
+            fs::write(
+                jkms_path.join(format!("{}.md", name)),
+                format!("---\nname: \"{}\"\ntype: \"context\"\n---\n{} content", name, name),
+            ).unwrap();
+        }
+        
+        let mut config = fink::utils::config::Config::default();
+        config.set_storage_path(temp_dir.path().to_path_buf());
+        let mut app = TUIApp::new_with_config(&config).unwrap();
+        app.enter_build_mode();
+        
+        let panel = app.get_interactive_build_panel_mut().unwrap();
+        panel.select_current(); // Skip instruction
+        assert_eq!(panel.current_step, BuildStep::SELECT_CONTEXT);
+        
+        // Toggle context-c then context-a, and toggle context-b on and off again
+        for name in ["context-c", "context-a", "context-b", "context-b"] {
+            while panel.get_selected_prompt_for_preview().map(|p| p.name.as_str()) != Some(name) {
+                panel.next();
+            }
+            panel.toggle_current();
+        }
+        panel.select_current();
+        
+        while panel.current_step != BuildStep::AddComment {
+            panel.select_current();
+        }
+        
+        let selected = panel.get_selected_prompt_names();
+        assert_eq!(selected, vec![
+            (PromptType::CONTEXT, "context-c".to_string()),
+            (PromptType::CONTEXT, "context-a".to_string()),
+        ]);
+    }
+    
+    #[test]
+    fn test_back_navigation_and_review_reorder() {
+        let temp_dir = tempdir().unwrap();
+        let jkms_path = temp_dir.path().join("prompts");
+        fs::create_dir_all(&jkms_path).unwrap();
+        
+        for (name, prompt_type) in [("role", "instruction"), ("background", "context"), ("rules", "etc")] {
+            fs::write(
+                jkms_path.join(format!("{}.md", name)),
+                format!("---\nname: \"{}\"\ntype: \"{}\"\n---\n{} content", name, prompt_type, name),
+            ).unwrap();
+        }
+        
+        let mut config = fink::utils::config::Config::default();
+        config.set_storage_path(temp_dir.path().to_path_buf());
+        let mut app = TUIApp::new_with_config(&config).unwrap();
+        app.enter_build_mode();
+        
+        let panel = app.get_interactive_build_panel_mut().unwrap();
+        panel.next();
+        panel.select_current(); // role
+        panel.next();
+        panel.select_current(); // background
+        
+        // Stepping back keeps the earlier choices
+        panel.go_back();
+        panel.go_back();
+        assert_eq!(panel.current_step, BuildStep::SELECT_INSTRUCTION);
+        assert_eq!(panel.get_selected_prompt_names().len(), 2);
+        
+        while panel.current_step != BuildStep::SELECT_ETC {
+            panel.select_current();
+        }
+        panel.next();
+        panel.select_current(); // rules
+        panel.finish_comment();
+        assert_eq!(panel.current_step, BuildStep::Complete);
+        
+        // Move "rules" to the top and drop "background"
+        panel.review_previous();
+        panel.move_review_item_up();
+        panel.move_review_item_up();
+        assert_eq!(panel.review_selected(), Some(0));
+        panel.review_next();
+        panel.review_next();
+        panel.remove_review_item();
+        
+        let names: Vec<String> = panel.get_selected_prompt_names().into_iter().map(|(_, name)| name).collect();
+        assert_eq!(names, vec!["rules", "role"]);
+        
+        // Going back and returning keeps the hand-arranged order
+        panel.go_back();
+        panel.finish_comment();
+        let names: Vec<String> = panel.get_selected_prompt_names().into_iter().map(|(_, name)| name).collect();
+        assert_eq!(names, vec!["rules", "role"]);
+    }
+    
+    #[test]
+    fn test_preview_and_assembled_output() {
+        let temp_dir = tempdir().unwrap();
+        let jkms_path = temp_dir.path().join("prompts");
+        fs::create_dir_all(&jkms_path).unwrap();
+        
+        fs::write(jkms_path.join("role.md"), "---\nname: \"role\"\ntype: \"instruction\"\n---\nYou review {{> rules}}").unwrap();
+        fs::write(jkms_path.join("rules.md"), "---\nname: \"rules\"\ntype: \"etc\"\n---\nRust code.").unwrap();
+        
+        let mut config = fink::utils::config::Config::default();
+        config.set_storage_path(temp_dir.path().to_path_buf());
+        let mut app = TUIApp::new_with_config(&config).unwrap();
+        app.enter_build_mode();
+        
+        let panel = app.get_interactive_build_panel_mut().unwrap();
+        assert_eq!(panel.get_selected_prompt_content(), None);
+        assert_eq!(panel.assembled_preview(), "");
+        
+        // The highlighted candidate previews its expanded content
+        panel.next();
+        assert_eq!(panel.get_selected_prompt_content(), Some("You review Rust code."));
+        panel.select_current();
+        assert_eq!(panel.assembled_preview(), "You review Rust code.");
+        
+        while panel.current_step != BuildStep::SELECT_ETC {
+            panel.select_current();
+        }
+        panel.next();
+        panel.select_current();
+        "Be brief".chars().for_each(|c| panel.add_comment_char(c));
+        
+        panel.set_format(fink::utils::output_format::OutputFormat::Xml);
+        assert_eq!(
+            panel.assembled_preview(),
+            "<instruction>\nYou review Rust code.\n</instruction>\n\n<etc>\nRust code.\n</etc>\n\n<notes>\nBe brief\n</notes>"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use fink::external::watcher::StorageWatcher;
+use fink::presentation::tui::app::TUIApp;
+use fink::utils::config::Config;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::tempdir;
+
+fn write_prompt(base_path: &Path, file_path: &str, tags: &str) {
+    let path = base_path.join("prompts").join(file_path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let name = Path::new(file_path).file_stem().unwrap().to_str().unwrap();
+    fs::write(path, format!("---\nname: \"{}\"\ntags: [{}]\n---\nBody", name, tags)).unwrap();
+}
+
+fn create_app(base_path: &Path) -> TUIApp {
+    let mut config = Config::default();
+    config.set_storage_path(base_path.to_path_buf());
+    TUIApp::new_with_config(&config).unwrap()
+}
+
+fn select(app: &mut TUIApp, name: &str) {
+    for _ in 0..app.get_prompts().len() {
+        if app.get_selected_prompt_name().as_deref() == Some(name) {
+            return;
+        }
+        app.next();
+    }
+    panic!("prompt '{}' not found", name);
+}
+
+fn wait_for_changes(watcher: &StorageWatcher) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if watcher.has_changes() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    false
+}
+
+#[test]
+fn should_report_prompt_file_changes() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("prompts")).unwrap();
+    let watcher = StorageWatcher::new(temp_dir.path()).unwrap();
+
+    // Act
+    write_prompt(temp_dir.path(), "backend/review.md", "");
+
+    // Assert
+    assert!(wait_for_changes(&watcher));
+    assert!(!watcher.has_changes());
+}
+
+#[test]
+fn should_ignore_changes_to_hidden_storage_files() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("prompts/.history")).unwrap();
+    let watcher = StorageWatcher::new(temp_dir.path()).unwrap();
+
+    // Act
+    fs::write(temp_dir.path().join("prompts/.history/review.md"), "old").unwrap();
+    fs::write(temp_dir.path().join(".index.json"), "{}").unwrap();
+
+    // Assert
+    assert!(!wait_for_changes(&watcher));
+}
+
+#[test]
+fn should_keep_selection_when_library_reloads() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    write_prompt(temp_dir.path(), "alpha.md", "");
+    write_prompt(temp_dir.path(), "beta.md", "");
+    let mut app = create_app(temp_dir.path());
+    select(&mut app, "beta");
+
+    // Act
+    write_prompt(temp_dir.path(), "aardvark.md", "\"new\"");
+    write_prompt(temp_dir.path(), "beta.md", "\"updated\"");
+    app.refresh_from_disk().unwrap();
+
+    // Assert
+    assert_eq!(app.get_prompts().len(), 3);
+    assert_eq!(app.get_selected_prompt_name().as_deref(), Some("beta"));
+    assert_eq!(app.get_selected_prompt_tags(), vec!["updated".to_string()]);
+}
+
+#[test]
+fn should_follow_renamed_prompt() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    write_prompt(temp_dir.path(), "alpha.md", "");
+    write_prompt(temp_dir.path(), "beta.md", "");
+    let mut app = create_app(temp_dir.path());
+    select(&mut app, "beta");
+
+    // Act
+    fs::create_dir_all(temp_dir.path().join("prompts/archive")).unwrap();
+    fs::rename(
+        temp_dir.path().join("prompts/beta.md"),
+        temp_dir.path().join("prompts/archive/beta.md"),
+    ).unwrap();
+    app.refresh_from_disk().unwrap();
+
+    // Assert
+    let selected = &app.get_prompts()[app.selected_index()];
+    assert_eq!(selected.id(), "archive/beta");
+}
+
+#[test]
+fn should_stay_near_deleted_prompt() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    write_prompt(temp_dir.path(), "alpha.md", "");
+    write_prompt(temp_dir.path(), "beta.md", "");
+    write_prompt(temp_dir.path(), "gamma.md", "");
+    let mut app = create_app(temp_dir.path());
+    select(&mut app, "gamma");
+    let previous_index = app.selected_index();
+
+    // Act
+    fs::remove_file(temp_dir.path().join("prompts/gamma.md")).unwrap();
+    app.refresh_from_disk().unwrap();
+
+    // Assert
+    assert_eq!(app.get_prompts().len(), 2);
+    assert_eq!(app.selected_index(), previous_index.min(1));
+    assert!(app.get_selected_prompt_name().is_some());
+}
@@ -12,15 +12,15 @@ fn test_main_should_initialize_prompts_in_fink_subdirectory() {
     // This would create prompts directly in ~/.fink/ instead of ~/.fink/fink/
     
     // First, let's verify the buggy behavior
-    initialize_default_prompts(&base_path).unwrap();
+    initialize_default_prompts(base_path).unwrap();
     
     // With the bug, prompts would be created directly in base_path
     let wrong_location = base_path.join("code-review.md");
     assert!(wrong_location.exists(), "Bug confirmed: prompts are in wrong location");
     
     // Clean up for the correct test
-    fs::remove_dir_all(&base_path).unwrap();
-    fs::create_dir_all(&base_path).unwrap();
+    fs::remove_dir_all(base_path).unwrap();
+    fs::create_dir_all(base_path).unwrap();
     
     // CORRECT BEHAVIOR: should initialize in base_path/prompts/
     let prompts_dir = base_path.join("prompts");
@@ -1,3 +1,4 @@
+use fink::application::models::TagMatch;
 use fink::presentation::tui::app::{TUIApp, AppMode};
 use fink::utils::config::Config;
 use tempfile::TempDir;
@@ -92,6 +93,58 @@ type: "whole"
     assert_eq!(combined_filter[0].name, "Rust Code Review");
 }
 
+#[test]
+fn test_tag_filter_all_and_exclude_modes() {
+    let temp_dir = TempDir::new().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("rust-review.md"), "---\nname: \"Rust Review\"\ntags: [\"rust\", \"review\"]\n---\nReview").unwrap();
+    fs::write(prompts_dir.join("rust-debug.md"), "---\nname: \"Rust Debug\"\ntags: [\"rust\", \"debug\"]\n---\nDebug").unwrap();
+    fs::write(prompts_dir.join("old-review.md"), "---\nname: \"Old Review\"\ntags: [\"review\", \"deprecated\"]\n---\nOld").unwrap();
+    
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_mode_and_config(&config, AppMode::QuickSelect).unwrap();
+    
+    // rust AND review
+    app.set_tag_filters(HashSet::from(["rust".to_string(), "review".to_string()]));
+    app.set_tag_match(TagMatch::All);
+    let names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["Rust Review"]);
+    
+    // NOT deprecated, with no included tags
+    app.set_tag_filters(HashSet::new());
+    app.set_excluded_tag_filters(HashSet::from(["deprecated".to_string()]));
+    assert!(app.is_tag_filter_active());
+    assert_eq!(app.get_filtered_prompts().len(), 2);
+    assert_eq!(app.tag_filter_summary(), Some("-deprecated".to_string()));
+    
+    app.clear_tag_filters();
+    assert_eq!(app.get_filtered_prompts().len(), 3);
+    assert_eq!(app.get_tag_match(), TagMatch::Any);
+}
+
+#[test]
+fn test_tag_filter_dialog_exclusions() {
+    use fink::presentation::tui::components::TagFilterDialog;
+    
+    let tags = vec!["rust".to_string(), "deprecated".to_string()];
+    let mut dialog = TagFilterDialog::new(tags, HashSet::new());
+    dialog.toggle_mode();
+    
+    // Excluding a tag takes it out of the selection, and including it again clears the exclusion
+    dialog.toggle_selected_tag();
+    dialog.toggle_excluded_tag();
+    assert!(dialog.get_selected_tags().is_empty());
+    assert!(dialog.get_excluded_tags().contains("rust"));
+    dialog.toggle_selected_tag();
+    assert!(dialog.get_excluded_tags().is_empty());
+    
+    assert_eq!(dialog.tag_match(), TagMatch::Any);
+    dialog.toggle_tag_match();
+    assert_eq!(dialog.tag_match(), TagMatch::All);
+}
+
 #[test]
 fn test_tag_filter_dialog_search() {
     use fink::presentation::tui::components::TagFilterDialog;
@@ -0,0 +1,100 @@
+use fink::application::app::DefaultPromptApplication;
+use fink::application::repository::{FileSystemRepository, PromptRepository};
+use fink::application::traits::PromptApplication;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn should_list_prompts_from_nested_folders() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(prompts_dir.join("backend/rust")).unwrap();
+
+    fs::write(prompts_dir.join("top.md"), "---\nname: \"Top\"\n---\nTop level").unwrap();
+    fs::write(prompts_dir.join("backend/review.md"), "---\nname: \"Review\"\n---\nReview").unwrap();
+    fs::write(prompts_dir.join("backend/rust/errors.md"), "---\nname: \"Errors\"\n---\nErrors").unwrap();
+
+    // Act
+    let storage = fink::storage::FileSystem::new(temp_dir.path().to_path_buf());
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert_eq!(prompts.len(), 3);
+    let nested = prompts.iter().find(|p| p.name == "Errors").unwrap();
+    assert_eq!(nested.file_path, "backend/rust/errors.md");
+    assert_eq!(nested.id(), "backend/rust/errors");
+    assert_eq!(nested.folder(), Some("backend/rust"));
+    assert_eq!(nested.display_name(), "backend/rust/Errors");
+
+    let top = prompts.iter().find(|p| p.name == "Top").unwrap();
+    assert_eq!(top.folder(), None);
+    assert_eq!(top.display_name(), "Top");
+}
+
+#[test]
+fn should_skip_hidden_folders_when_listing() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(prompts_dir.join(".hidden")).unwrap();
+    fs::write(prompts_dir.join("visible.md"), "# Visible").unwrap();
+    fs::write(prompts_dir.join(".hidden/secret.md"), "# Secret").unwrap();
+
+    // Act
+    let storage = fink::storage::FileSystem::new(temp_dir.path().to_path_buf());
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].file_path, "visible.md");
+}
+
+#[test]
+fn should_find_nested_prompt_by_path() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(prompts_dir.join("backend")).unwrap();
+    fs::create_dir_all(prompts_dir.join("frontend")).unwrap();
+    fs::write(prompts_dir.join("backend/review.md"), "---\nname: \"review\"\n---\nBackend").unwrap();
+    fs::write(prompts_dir.join("frontend/review.md"), "---\nname: \"review\"\n---\nFrontend").unwrap();
+
+    let repository = FileSystemRepository::new(fink::storage::FileSystem::new(temp_dir.path().to_path_buf()));
+
+    // Act
+    let found = repository.find_by_name("frontend/review").unwrap();
+
+    // Assert
+    let prompt = found.expect("nested prompt should be found by path");
+    assert_eq!(prompt.file_path, "frontend/review.md");
+    assert_eq!(repository.get_content(&prompt.file_path).unwrap(), "Frontend");
+}
+
+#[test]
+fn should_create_prompt_inside_folder() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    app.create_prompt("Backend/Code Review", None).unwrap();
+
+    // Assert
+    let file_path = temp_dir.path().join("prompts/backend/code-review.md");
+    assert!(file_path.exists());
+    let content = fs::read_to_string(file_path).unwrap();
+    assert!(content.contains("name: \"Code Review\""));
+}
+
+#[test]
+fn should_reject_invalid_folder_names() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act & Assert
+    assert!(app.create_prompt("backend//review", None).is_err());
+    assert!(app.create_prompt("../escape", None).is_err());
+    assert!(app.create_prompt("/", None).is_err());
+}
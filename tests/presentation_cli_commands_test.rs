@@ -37,6 +37,46 @@ tags: ["bug", "issue"]
         .stdout(predicate::str::contains("bug, issue"));
 }
 
+#[test]
+fn should_list_prompts_with_tag_and_type_filters() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    std::fs::create_dir(&prompts_dir).unwrap();
+    std::fs::write(prompts_dir.join("rust-review.md"), "---\nname: \"Rust Review\"\ntags: [\"rust\", \"review\"]\ntype: \"instruction\"\n---\nReview").unwrap();
+    std::fs::write(prompts_dir.join("rust-context.md"), "---\nname: \"Rust Context\"\ntags: [\"rust\", \"review\", \"deprecated\"]\ntype: \"context\"\n---\nContext").unwrap();
+    std::fs::write(prompts_dir.join("go-review.md"), "---\nname: \"Go Review\"\ntags: [\"go\", \"review\"]\ntype: \"instruction\"\n---\nReview").unwrap();
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["list", "--tag", "rust", "--tag", "review", "--all", "--exclude-tag", "deprecated"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rust Review"))
+        .stdout(predicate::str::contains("Rust Context").not())
+        .stdout(predicate::str::contains("Go Review").not());
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["list", "--type", "context"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Rust Context"))
+        .stdout(predicate::str::contains("Rust Review").not());
+
+    // A misspelt type is an error, not an empty list
+    Command::cargo_bin("fink").unwrap()
+        .args(["list", "--type", "instrucion"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unknown prompt type 'instrucion'"));
+}
+
 #[test]
 fn should_handle_empty_directory_with_list_command() {
     // Arrange
@@ -83,6 +123,44 @@ This is the code review template content."#;
         ));
 }
 
+#[test]
+fn should_get_prompt_with_broken_frontmatter() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    std::fs::create_dir(&prompts_dir).unwrap();
+    std::fs::write(prompts_dir.join("broken-yaml.md"), "---\nname: \"Broken\ntags: [a\n---\nHello {{name}}").unwrap();
+    std::fs::write(prompts_dir.join("bad-variables.md"), "---\nname: \"Bad Variables\"\nvariables: 5\n---\nStill printed").unwrap();
+
+    // Act & Assert
+    // Warnings go to stderr, except with a machine format where it only carries errors
+    Command::cargo_bin("fink").unwrap()
+        .args(["list", "--format", "json"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("broken-yaml.md"))
+        .stderr(predicate::str::is_empty());
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "broken-yaml", "--var", "name=world"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Hello world"))
+        .stderr(predicate::str::contains("Warning: Failed to parse frontmatter in broken-yaml.md"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "bad-variables"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Still printed"));
+}
+
 #[test]
 fn should_handle_nonexistent_prompt() {
     // Arrange
@@ -461,6 +539,61 @@ tags: ["bug", "code"]
         .stdout(predicate::str::contains("Bug Report"));
 }
 
+#[test]
+fn should_search_prompts_with_field_filters() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    std::fs::create_dir(&prompts_dir).unwrap();
+    std::fs::write(prompts_dir.join("code-review.md"), "---\nname: \"Code Review\"\ntags: [\"rust\"]\n---\nReview").unwrap();
+    std::fs::write(prompts_dir.join("review-draft.md"), "---\nname: \"Review Draft\"\ntags: [\"rust\", \"draft\"]\n---\nDraft").unwrap();
+    
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["search", "tag:rust -tag:draft review"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Code Review"))
+        .stdout(predicate::str::contains("Review Draft").not());
+}
+
+#[test]
+fn should_show_content_matches_with_line_numbers() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    std::fs::create_dir(&prompts_dir).unwrap();
+    std::fs::write(prompts_dir.join("code-review.md"), "---\nname: \"Code Review\"\ntype: \"whole\"\n---\nRead it.\nCheck the diff first.").unwrap();
+    std::fs::write(prompts_dir.join("bug-report.md"), "---\nname: \"Bug Report\"\n---\nSteps").unwrap();
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["search", "diff", "--content"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Code Review"))
+        .stdout(predicate::str::contains("6:11: Check the diff first."))
+        .stdout(predicate::str::contains("Bug Report").not());
+}
+
+#[test]
+fn should_reject_malformed_search_query() {
+    let temp_dir = tempdir().unwrap();
+    std::fs::create_dir(temp_dir.path().join("prompts")).unwrap();
+    
+    Command::cargo_bin("fink").unwrap()
+        .args(["search", "name:\"code"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unterminated quote at column 6"));
+}
+
 #[test]
 fn should_show_message_when_no_search_results() {
     // Arrange
@@ -486,3 +619,127 @@ tags: ["test"]
         .success()
         .stdout(predicate::str::contains("No prompts found matching"));
 }
+
+fn setup_formatted_library() -> tempfile::TempDir {
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    std::fs::create_dir(&prompts_dir).unwrap();
+    std::fs::write(
+        prompts_dir.join("code-review.md"),
+        "---\nname: \"Code Review\"\ntags: [\"code\", \"review\"]\ntype: \"instruction\"\ndescription: \"Reviews a diff\"\ncreated_at: \"2024-03-01\"\nmodel: \"large\"\n---\nCheck the diff.\n\tThen approve.",
+    ).unwrap();
+    std::fs::write(prompts_dir.join("bug-report.md"), "---\nname: \"Bug Report\"\ntags: [\"bug\"]\n---\nSteps").unwrap();
+    temp_dir
+}
+
+#[test]
+fn should_list_prompts_as_json() {
+    let temp_dir = setup_formatted_library();
+
+    let output = Command::cargo_bin("fink").unwrap()
+        .args(["list", "--format", "json", "--tag", "code"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let records: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let record = &records.as_array().unwrap()[0];
+    assert_eq!(records.as_array().unwrap().len(), 1);
+    assert_eq!(record["id"], "code-review");
+    assert_eq!(record["name"], "Code Review");
+    assert_eq!(record["file_path"], "code-review.md");
+    assert_eq!(record["tags"], serde_json::json!(["code", "review"]));
+    assert_eq!(record["type"], "instruction");
+    assert_eq!(record["description"], "Reviews a diff");
+    assert!(record["created_at"].as_str().unwrap().starts_with("2024-03-01T00:00:00"));
+    assert!(record["modified_at"].is_string());
+}
+
+#[test]
+fn should_list_prompts_as_ndjson_and_tsv() {
+    let temp_dir = setup_formatted_library();
+
+    let output = Command::cargo_bin("fink").unwrap()
+        .args(["list", "--format", "ndjson"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let names: Vec<String> = stdout
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["name"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(&"Bug Report".to_string()));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["list", "--format", "tsv", "--tag", "bug"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("bug-report\tBug Report\tbug-report.md\tbug\twhole\t\t\t"));
+}
+
+#[test]
+fn should_search_prompts_as_json_with_matches() {
+    let temp_dir = setup_formatted_library();
+
+    let output = Command::cargo_bin("fink").unwrap()
+        .args(["search", "diff", "--content", "--format", "json"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .output()
+        .unwrap();
+    let records: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(records[0]["name"], "Code Review");
+    assert_eq!(records[0]["matches"][0]["line"], 9);
+    assert_eq!(records[0]["matches"][0]["column"], 11);
+}
+
+#[test]
+fn should_get_prompt_as_json_with_body_and_frontmatter() {
+    let temp_dir = setup_formatted_library();
+
+    let output = Command::cargo_bin("fink").unwrap()
+        .args(["get", "code-review", "--format", "json"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .output()
+        .unwrap();
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(document["name"], "Code Review");
+    assert_eq!(document["body"], "Check the diff.\n\tThen approve.");
+    assert_eq!(document["frontmatter"]["model"], "large");
+    assert_eq!(document["frontmatter"]["tags"], serde_json::json!(["code", "review"]));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "code-review", "--format", "tsv"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with("\tCheck the diff.\\n\\tThen approve.\n"));
+}
+
+#[test]
+fn should_report_errors_as_json_when_requested() {
+    let temp_dir = setup_formatted_library();
+
+    let output = Command::cargo_bin("fink").unwrap()
+        .args(["get", "missing", "--format", "json"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let error: serde_json::Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["code"], "prompt_not_found");
+    assert!(error["error"]["message"].as_str().unwrap().contains("missing"));
+    assert!(error["error"]["hint"].is_string());
+}
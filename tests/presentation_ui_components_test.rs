@@ -10,12 +10,14 @@ fn should_create_prompt_list_component() {
             file_path: "code-review.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
         PromptMetadata {
             name: "Bug Analysis".to_string(),
             file_path: "bug-analysis.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
     ];
 
@@ -36,18 +38,21 @@ fn should_navigate_prompt_list() {
             file_path: "code-review.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
         PromptMetadata {
             name: "Bug Analysis".to_string(),
             file_path: "bug-analysis.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
         PromptMetadata {
             name: "Documentation".to_string(),
             file_path: "documentation.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
     ];
     let mut prompt_list = PromptList::new(prompts);
@@ -75,12 +80,14 @@ fn should_handle_previous_navigation() {
             file_path: "code-review.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
         PromptMetadata {
             name: "Bug Analysis".to_string(),
             file_path: "bug-analysis.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
     ];
     let mut prompt_list = PromptList::new(prompts);
@@ -101,12 +108,14 @@ fn should_get_selected_prompt() {
             file_path: "code-review.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
         PromptMetadata {
             name: "Bug Analysis".to_string(),
             file_path: "bug-analysis.md".to_string(),
             tags: vec![],
             prompt_type: PromptType::default(),
+            ..Default::default()
         },
     ];
     let mut prompt_list = PromptList::new(prompts);
@@ -31,13 +31,13 @@ Another test prompt."#).unwrap();
     
     // Initial state should be QuickSelect mode
     assert_eq!(app.mode(), &AppMode::QuickSelect);
-    assert_eq!(app.is_search_active(), false);
+    assert!(!app.is_search_active());
     
     // Activate search mode
     app.activate_search();
     
     // Should be in search mode
-    assert_eq!(app.is_search_active(), true);
+    assert!(app.is_search_active());
 }
 
 #[test]
@@ -105,11 +105,11 @@ fn test_search_result_highlighting() {
     
     assert_eq!(highlighted.segments.len(), 3);
     assert_eq!(highlighted.segments[0].text, "This is a ");
-    assert_eq!(highlighted.segments[0].is_match, false);
+    assert!(!highlighted.segments[0].is_match);
     assert_eq!(highlighted.segments[1].text, "test");
-    assert_eq!(highlighted.segments[1].is_match, true);
+    assert!(highlighted.segments[1].is_match);
     assert_eq!(highlighted.segments[2].text, " prompt");
-    assert_eq!(highlighted.segments[2].is_match, false);
+    assert!(!highlighted.segments[2].is_match);
     
     // Test case 2: Multiple matches
     let text = "test this test case";
@@ -118,13 +118,13 @@ fn test_search_result_highlighting() {
     
     assert_eq!(highlighted.segments.len(), 4);
     assert_eq!(highlighted.segments[0].text, "test");
-    assert_eq!(highlighted.segments[0].is_match, true);
+    assert!(highlighted.segments[0].is_match);
     assert_eq!(highlighted.segments[1].text, " this ");
-    assert_eq!(highlighted.segments[1].is_match, false);
+    assert!(!highlighted.segments[1].is_match);
     assert_eq!(highlighted.segments[2].text, "test");
-    assert_eq!(highlighted.segments[2].is_match, true);
+    assert!(highlighted.segments[2].is_match);
     assert_eq!(highlighted.segments[3].text, " case");
-    assert_eq!(highlighted.segments[3].is_match, false);
+    assert!(!highlighted.segments[3].is_match);
     
     // Test case 3: Case insensitive matching
     let text = "Test THIS test";
@@ -132,9 +132,9 @@ fn test_search_result_highlighting() {
     let highlighted = highlighter.highlight(text, query);
     
     assert_eq!(highlighted.segments[0].text, "Test");
-    assert_eq!(highlighted.segments[0].is_match, true);
+    assert!(highlighted.segments[0].is_match);
     assert_eq!(highlighted.segments[2].text, "test");
-    assert_eq!(highlighted.segments[2].is_match, true);
+    assert!(highlighted.segments[2].is_match);
     
     // Test case 4: No matches
     let text = "No matches here";
@@ -143,5 +143,151 @@ fn test_search_result_highlighting() {
     
     assert_eq!(highlighted.segments.len(), 1);
     assert_eq!(highlighted.segments[0].text, "No matches here");
-    assert_eq!(highlighted.segments[0].is_match, false);
-}
\ No newline at end of file
+    assert!(!highlighted.segments[0].is_match);
+}
+
+#[test]
+fn test_fuzzy_search_ranks_by_score() {
+    use fink::utils::config::Config;
+    
+    let temp_dir = tempdir().unwrap();
+    let prompts_path = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(prompts_path.join("code-review.md"), "---\nname: \"code-review\"\ntags: [\"code\"]\n---\nReview").unwrap();
+    fs::write(prompts_path.join("card-driver.md"), "---\nname: \"card-driver\"\n---\nDrive").unwrap();
+    fs::write(prompts_path.join("commit.md"), "---\nname: \"commit\"\ndescription: \"Conventional release notes\"\n---\nCommit").unwrap();
+    fs::write(prompts_path.join("notes.md"), "---\nname: \"notes\"\n---\nNotes").unwrap();
+    
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    app.activate_search();
+    
+    // Characters in order, not necessarily adjacent
+    app.set_search_query("cdrv");
+    let mut names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    names.sort();
+    assert_eq!(names, vec!["card-driver", "code-review"]);
+    
+    // Word starts outrank scattered matches
+    app.set_search_query("cr");
+    let names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    assert_eq!(names[0], "code-review");
+    
+    // Descriptions are searched too
+    app.set_search_query("release");
+    let names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["commit"]);
+}
+
+#[test]
+fn test_search_ranks_exact_matches_above_starred_ones() {
+    use fink::utils::config::Config;
+    
+    let temp_dir = tempdir().unwrap();
+    let prompts_path = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(prompts_path.join("review.md"), "---\nname: \"review\"\n---\nReview").unwrap();
+    fs::write(prompts_path.join("real-estate-view.md"), "---\nname: \"real-estate-view\"\ntags: [\"starred\"]\n---\nHouses").unwrap();
+    fs::write(prompts_path.join("notes.md"), "---\nname: \"notes\"\n---\nNotes").unwrap();
+    
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    
+    // Without a search, starred prompts come first
+    let names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["real-estate-view", "notes", "review"]);
+    
+    // While searching, the better match wins over the star
+    app.activate_search();
+    app.set_search_query("review");
+    let names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+    assert_eq!(names, vec!["review", "real-estate-view"]);
+}
+
+#[test]
+fn test_fuzzy_highlighting_marks_matched_characters() {
+    let highlighter = HighlightedText::new();
+    
+    let highlighted = highlighter.highlight_fuzzy("code-review", "cdrv");
+    let marked: Vec<(&str, bool)> = highlighted.segments
+        .iter()
+        .map(|segment| (segment.text.as_str(), segment.is_match))
+        .collect();
+    
+    assert_eq!(marked, vec![
+        ("c", true), ("o", false), ("d", true), ("e-", false),
+        ("r", true), ("e", false), ("v", true), ("iew", false),
+    ]);
+}
+
+#[test]
+fn test_query_language_filters_and_reports_errors() {
+    use fink::utils::config::Config;
+    
+    let temp_dir = tempdir().unwrap();
+    let prompts_path = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(prompts_path.join("code-review.md"), "---\nname: \"code-review\"\ntags: [\"rust\"]\ntype: \"instruction\"\n---\nCheck the diff").unwrap();
+    fs::write(prompts_path.join("review-draft.md"), "---\nname: \"review-draft\"\ntags: [\"rust\", \"draft\"]\ntype: \"instruction\"\n---\nWIP").unwrap();
+    fs::write(prompts_path.join("review-notes.md"), "---\nname: \"review-notes\"\ntags: [\"python\"]\n---\nNotes").unwrap();
+    
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    app.activate_search();
+    let names = |app: &TUIApp| -> Vec<String> {
+        let mut names: Vec<String> = app.get_filtered_prompts().into_iter().map(|p| p.name).collect();
+        names.sort();
+        names
+    };
+    
+    app.set_search_query("tag:rust type:instruction -tag:draft review");
+    assert_eq!(names(&app), vec!["code-review"]);
+    assert!(app.search_error().is_none());
+    
+    app.set_search_query("tag:python OR content:diff");
+    assert_eq!(names(&app), vec!["code-review", "review-notes"]);
+    
+    // An unfinished query reports where it broke and keeps the previous results
+    app.set_search_query("tag:python OR content:diff tag:");
+    let error = app.search_error().unwrap();
+    assert_eq!(error.column, 28);
+    assert!(error.to_string().contains("missing value after 'tag:'"));
+    assert_eq!(names(&app), vec!["code-review", "review-notes"]);
+    
+    // Bodies are read again when the library is reloaded
+    app.set_search_query("content:diff");
+    fs::write(prompts_path.join("review-notes.md"), "---\nname: \"review-notes\"\ntags: [\"python\"]\n---\nNotes on the diff").unwrap();
+    assert_eq!(names(&app), vec!["code-review"]);
+    app.reload_prompts().unwrap();
+    assert_eq!(names(&app), vec!["code-review", "review-notes"]);
+}
+
+#[test]
+fn test_content_matches_are_found_once_per_query() {
+    use fink::utils::config::Config;
+    
+    let temp_dir = tempdir().unwrap();
+    let prompts_path = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_path).unwrap();
+    fs::write(prompts_path.join("code-review.md"), "---\nname: \"code-review\"\ntype: \"instruction\"\n---\nRead it.\nCheck the diff").unwrap();
+    
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    app.activate_search();
+    app.set_search_query("content:diff");
+    let prompt = app.get_filtered_prompts().remove(0);
+    
+    let lines: Vec<usize> = app.get_content_matches(&prompt).iter().map(|found| found.line).collect();
+    assert_eq!(lines, vec![6]);
+    
+    // Rendering again does not read the file
+    fs::remove_file(prompts_path.join("code-review.md")).unwrap();
+    assert_eq!(app.get_content_matches(&prompt).len(), 1);
+    
+    app.deactivate_search();
+    assert!(app.get_content_matches(&prompt).is_empty());
+}
@@ -0,0 +1,121 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+const ORIGINAL: &str = "---\nname: \"review\"\ntags: [\"old\"]\ntype: \"whole\"\n---\nReview the code.\n";
+
+fn setup_prompt(base_path: &std::path::Path) {
+    let prompts_dir = base_path.join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("review.md"), ORIGINAL).unwrap();
+}
+
+#[test]
+fn should_record_revision_when_prompt_is_overwritten() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    app.update_prompt_tags("review", vec!["new".to_string()]).unwrap();
+
+    // Assert
+    let revisions = app.list_revisions("review").unwrap();
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].number, 1);
+    assert_eq!(revisions[0].size, ORIGINAL.len() as u64);
+}
+
+#[test]
+fn should_not_record_revision_for_identical_content() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    app.update_prompt_tags("review", vec!["old".to_string()]).unwrap();
+
+    // Assert
+    assert!(app.list_revisions("review").unwrap().is_empty());
+}
+
+#[test]
+fn should_diff_and_restore_revision() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    app.update_prompt_tags("review", vec!["new".to_string()]).unwrap();
+
+    // Act
+    let diff = app.diff_prompt("review", None).unwrap();
+    app.restore_prompt("review", 1).unwrap();
+
+    // Assert
+    assert!(diff.contains("-tags: [\"old\"]"));
+    assert!(diff.contains("+tags: [\"new\"]"));
+    let restored = fs::read_to_string(temp_dir.path().join("prompts/review.md")).unwrap();
+    assert_eq!(restored, ORIGINAL);
+    
+    // The restore itself is undoable
+    assert_eq!(app.list_revisions("review").unwrap().len(), 2);
+}
+
+#[test]
+fn should_fail_for_unknown_revision() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let result = app.restore_prompt("review", 7);
+
+    // Assert
+    assert!(result.unwrap_err().to_string().contains("Revision 7 not found"));
+    assert!(app.diff_prompt("review", None).is_err());
+}
+
+#[test]
+fn should_show_history_and_diff_from_cli() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    app.update_prompt_tags("review", vec!["new".to_string()]).unwrap();
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .arg("history")
+        .arg("review")
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("   1  "));
+
+    Command::cargo_bin("fink").unwrap()
+        .arg("diff")
+        .arg("review")
+        .arg("1")
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+tags: [\"new\"]"));
+
+    Command::cargo_bin("fink").unwrap()
+        .arg("restore")
+        .arg("review")
+        .arg("1")
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored 'review' to revision 1"));
+}
@@ -0,0 +1,114 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::TUIApp;
+use fink::utils::config::Config;
+use predicates::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use tempfile::tempdir;
+
+fn write_prompt(base_path: &std::path::Path, file_path: &str, content: &str) {
+    let path = base_path.join("prompts").join(file_path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn setup_library(base_path: &std::path::Path) {
+    write_prompt(base_path, "review.md", "---\nname: \"review\"\n---\n{{> shared/tone}}\n\nReview this {{language}} code.");
+    write_prompt(base_path, "shared/tone.md", "---\nname: \"tone\"\n---\nBe concise and kind.\n{{> shared/safety}}");
+    write_prompt(
+        base_path,
+        "shared/safety.md",
+        "---\nname: \"safety\"\nvariables:\n  language:\n    default: rust\n---\nNever include secrets.",
+    );
+}
+
+#[test]
+fn should_expand_includes_recursively() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_library(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let (_, expanded) = app.get_expanded_prompt("review").unwrap();
+    let (_, raw) = app.get_prompt("review").unwrap();
+
+    // Assert
+    assert_eq!(expanded, "Be concise and kind.\nNever include secrets.\n\nReview this {{language}} code.");
+    assert!(raw.starts_with("{{> shared/tone}}"));
+}
+
+#[test]
+fn should_use_variable_declarations_from_included_prompts() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_library(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let rendered = app.render_prompt("review", &HashMap::new()).unwrap();
+
+    // Assert
+    assert!(rendered.ends_with("Review this rust code."));
+}
+
+#[test]
+fn should_report_include_cycles_and_missing_references() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    write_prompt(temp_dir.path(), "a.md", "---\nname: \"a\"\n---\n{{> b}}");
+    write_prompt(temp_dir.path(), "b.md", "---\nname: \"b\"\n---\n{{> a}}");
+    write_prompt(temp_dir.path(), "c.md", "---\nname: \"c\"\n---\n{{> nowhere}}");
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let cycle = app.get_expanded_prompt("a").unwrap_err();
+    let missing = app.get_expanded_prompt("c").unwrap_err();
+
+    // Assert
+    assert_eq!(cycle.to_string(), "Include cycle: a -> b -> a");
+    assert_eq!(missing.to_string(), "Included prompt not found: nowhere (referenced from c)");
+}
+
+#[test]
+fn should_print_expanded_prompt_from_cli() {
+    let temp_dir = tempdir().unwrap();
+    setup_library(temp_dir.path());
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "review", "--var", "language=go", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Never include secrets."))
+        .stdout(predicate::str::contains("Review this go code."));
+
+    write_prompt(temp_dir.path(), "loop.md", "---\nname: \"loop\"\n---\n{{> loop}}");
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "loop", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Include cycle: loop -> loop"));
+}
+
+#[test]
+fn should_preview_expanded_content_in_tui() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    write_prompt(temp_dir.path(), "review.md", "---\nname: \"review\"\n---\n{{> tone}} Review it.");
+    write_prompt(temp_dir.path(), "tone.md", "---\nname: \"tone\"\n---\nBe kind.");
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+
+    // Act
+    while app.get_selected_prompt_name().as_deref() != Some("review") {
+        app.next();
+    }
+
+    // Assert
+    assert_eq!(app.get_selected_content().unwrap(), "Be kind. Review it.");
+}
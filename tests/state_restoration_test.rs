@@ -145,6 +145,33 @@ clipboard_postfix = ""
         cleanup_test_env();
     }
 
+    #[test]
+    fn test_state_restores_prompts_sharing_a_name() {
+        let (temp_dir, config) = create_test_app_with_prompts();
+        let prompts_dir = temp_dir.path().join(".fink").join("prompts");
+        for folder in ["a", "b"] {
+            fs::create_dir_all(prompts_dir.join(folder)).unwrap();
+            fs::write(prompts_dir.join(folder).join("review.md"), format!("---\nname: review\n---\nIn {}", folder)).unwrap();
+        }
+        
+        // Select b/review and quit
+        let mut app = TUIApp::new_with_mode_and_config(&config, AppMode::QuickSelect).unwrap();
+        for _ in 0..app.get_prompts().len() {
+            if app.get_selected_content().as_deref() == Some("In b") {
+                break;
+            }
+            app.next();
+        }
+        app.quit();
+        assert_eq!(AppState::load().unwrap().last_selected_prompt(), Some("b/review"));
+        
+        // The same prompt is selected again, not the first one named review
+        let app = TUIApp::new_with_mode_and_config(&config, AppMode::QuickSelect).unwrap();
+        assert_eq!(app.get_selected_content().as_deref(), Some("In b"));
+        
+        cleanup_test_env();
+    }
+
     #[test]
     fn test_state_handles_missing_prompt() {
         let (_temp_dir, config) = create_test_app_with_prompts();
@@ -13,7 +13,7 @@ type: "instruction"
 # Test Content"#;
 
         let prompt_type = fink::storage::extract_type_from_content(content);
-        assert_eq!(prompt_type, Some(PromptType::Instruction));
+        assert_eq!(prompt_type, Some(PromptType::INSTRUCTION));
     }
 
     #[test]
@@ -32,11 +32,11 @@ tags: ["test"]
     #[test]
     fn should_handle_different_type_values() {
         let test_cases = vec![
-            ("context", PromptType::Context),
-            ("input_indicator", PromptType::InputIndicator),
-            ("output_indicator", PromptType::OutputIndicator),
-            ("etc", PromptType::Etc),
-            ("whole", PromptType::Whole),
+            ("context", PromptType::CONTEXT),
+            ("input_indicator", PromptType::INPUT_INDICATOR),
+            ("output_indicator", PromptType::OUTPUT_INDICATOR),
+            ("etc", PromptType::ETC),
+            ("whole", PromptType::WHOLE),
         ];
 
         for (type_str, expected) in test_cases {
@@ -52,14 +52,14 @@ Content"#, type_str);
     }
 
     #[test]
-    fn should_handle_invalid_type_value() {
+    fn should_keep_unknown_type_value() {
         let content = r#"---
 name: "Test"
 type: "invalid_type"
 ---
 Content"#;
 
-        let prompt_type = fink::storage::extract_type_from_content(&content);
-        assert_eq!(prompt_type, None);
+        let prompt_type = fink::storage::extract_type_from_content(content);
+        assert_eq!(prompt_type, Some(PromptType::new("invalid_type")));
     }
 }
\ No newline at end of file
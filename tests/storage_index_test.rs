@@ -0,0 +1,88 @@
+use fink::storage::index::{content_hash, PromptIndex};
+use fink::storage::FileSystem;
+use fink::utils::constants::INDEX_FILE;
+use std::fs;
+use tempfile::tempdir;
+
+fn setup_prompts() -> (tempfile::TempDir, std::path::PathBuf) {
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("one.md"), "---\nname: \"One\"\ntags: [\"a\"]\ntype: \"whole\"\n---\nFirst").unwrap();
+    fs::write(prompts_dir.join("two.md"), "---\nname: \"Two\"\ntype: \"context\"\n---\nSecond").unwrap();
+    (temp_dir, prompts_dir)
+}
+
+#[test]
+fn should_write_index_with_content_hashes() {
+    // Arrange
+    let (temp_dir, prompts_dir) = setup_prompts();
+    let storage = FileSystem::new(temp_dir.path().to_path_buf());
+
+    // Act
+    storage.list_prompts().unwrap();
+
+    // Assert
+    assert!(temp_dir.path().join(INDEX_FILE).exists());
+    let index = PromptIndex::load(temp_dir.path());
+    assert_eq!(index.len(), 2);
+    
+    let content = fs::read_to_string(prompts_dir.join("one.md")).unwrap();
+    let entry = index.get("one.md").unwrap();
+    assert_eq!(entry.hash, content_hash(&content));
+    assert_eq!(entry.metadata.name, "One");
+}
+
+#[test]
+fn should_answer_unchanged_files_from_index() {
+    // Arrange
+    let (temp_dir, _prompts_dir) = setup_prompts();
+    let storage = FileSystem::new(temp_dir.path().to_path_buf());
+    storage.list_prompts().unwrap();
+    
+    // Tamper with the cached name; an unchanged file must not be re-parsed
+    let index_path = temp_dir.path().join(INDEX_FILE);
+    let raw = fs::read_to_string(&index_path).unwrap();
+    fs::write(&index_path, raw.replace("\"One\"", "\"Cached\"")).unwrap();
+
+    // Act
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert!(prompts.iter().any(|p| p.name == "Cached"));
+}
+
+#[test]
+fn should_reparse_changed_and_drop_deleted_files() {
+    // Arrange
+    let (temp_dir, prompts_dir) = setup_prompts();
+    let storage = FileSystem::new(temp_dir.path().to_path_buf());
+    storage.list_prompts().unwrap();
+
+    // Act
+    fs::write(prompts_dir.join("one.md"), "---\nname: \"One Renamed\"\ntype: \"whole\"\n---\nChanged body").unwrap();
+    fs::remove_file(prompts_dir.join("two.md")).unwrap();
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "One Renamed");
+    let index = PromptIndex::load(temp_dir.path());
+    assert_eq!(index.len(), 1);
+    assert!(index.get("two.md").is_none());
+}
+
+#[test]
+fn should_rebuild_corrupt_index() {
+    // Arrange
+    let (temp_dir, _prompts_dir) = setup_prompts();
+    fs::write(temp_dir.path().join(INDEX_FILE), "not json").unwrap();
+    let storage = FileSystem::new(temp_dir.path().to_path_buf());
+
+    // Act
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert_eq!(prompts.len(), 2);
+    assert_eq!(PromptIndex::load(temp_dir.path()).len(), 2);
+}
@@ -23,7 +23,7 @@ tags: ["test"]
     }
 
     #[test]
-    fn should_update_invalid_type_to_whole() {
+    fn should_preserve_unknown_type() {
         let content = r#"---
 name: "Test Prompt"
 type: "invalid"
@@ -33,8 +33,8 @@ type: "invalid"
 
         let result = FrontmatterUpdater::ensure_type(content, "Test Prompt", None).unwrap();
         
-        assert!(result.contains("type: \"whole\""));
-        assert!(!result.contains("type: \"invalid\""));
+        assert!(result.contains("type: \"invalid\""));
+        assert!(!result.contains("type: \"whole\""));
     }
 
     #[test]
@@ -46,7 +46,7 @@ type: "instruction"
 
 # Test Content"#;
 
-        let result = FrontmatterUpdater::ensure_type(content, "Test Prompt", Some(PromptType::Instruction)).unwrap();
+        let result = FrontmatterUpdater::ensure_type(content, "Test Prompt", Some(PromptType::INSTRUCTION)).unwrap();
         
         assert!(result.contains("type: \"instruction\""));
         assert!(!result.contains("type: \"whole\""));
@@ -77,7 +77,7 @@ tags: ["test"]
 
         // Verify prompt has default type
         assert_eq!(prompts.len(), 1);
-        assert_eq!(prompts[0].prompt_type, PromptType::Whole);
+        assert_eq!(prompts[0].prompt_type, PromptType::WHOLE);
 
         // Verify file was updated
         let updated_content = fs::read_to_string(&prompt_path).unwrap();
@@ -0,0 +1,75 @@
+use fink::application::models::PromptType;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn should_list_prompts_with_block_style_yaml() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir(&prompts_dir).unwrap();
+
+    let content = r#"---
+name: "Review: backend"
+description: >
+  Reviews backend code
+  for common issues
+tags:
+  - rust
+  - review
+type: instruction
+---
+Review the code."#;
+    fs::write(prompts_dir.join("review.md"), content).unwrap();
+
+    // Act
+    let storage = fink::storage::FileSystem::new(temp_dir.path().to_path_buf());
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0].name, "Review: backend");
+    assert_eq!(prompts[0].tags, vec!["rust", "review"]);
+    assert_eq!(prompts[0].prompt_type, PromptType::INSTRUCTION);
+    
+    // A valid type means the file is never rewritten
+    assert_eq!(fs::read_to_string(prompts_dir.join("review.md")).unwrap(), content);
+}
+
+#[test]
+fn should_add_type_without_touching_other_fields() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir(&prompts_dir).unwrap();
+
+    let content = "---\nname: draft\nauthor: someone\ntags:\n  - a\n---\nBody\n";
+    fs::write(prompts_dir.join("draft.md"), content).unwrap();
+
+    // Act
+    let storage = fink::storage::FileSystem::new(temp_dir.path().to_path_buf());
+    storage.list_prompts().unwrap();
+
+    // Assert
+    let updated = fs::read_to_string(prompts_dir.join("draft.md")).unwrap();
+    assert_eq!(updated, "---\nname: draft\nauthor: someone\ntags:\n  - a\ntype: \"whole\"\n---\nBody\n");
+}
+
+#[test]
+fn should_leave_unparseable_frontmatter_untouched() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir(&prompts_dir).unwrap();
+
+    let content = "---\nname: [broken\n---\nBody\n";
+    fs::write(prompts_dir.join("broken.md"), content).unwrap();
+
+    // Act
+    let storage = fink::storage::FileSystem::new(temp_dir.path().to_path_buf());
+    let prompts = storage.list_prompts().unwrap();
+
+    // Assert
+    assert_eq!(prompts[0].name, "broken");
+    assert_eq!(fs::read_to_string(prompts_dir.join("broken.md")).unwrap(), content);
+}
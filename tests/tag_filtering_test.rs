@@ -39,14 +39,14 @@ tags: ["docs", "writing", "markdown"]
     let mut app = TUIApp::new(temp_path).unwrap();
     
     // Initial state should not have tag filtering active
-    assert_eq!(app.is_tag_filter_active(), false);
+    assert!(!app.is_tag_filter_active());
     assert!(app.get_active_tag_filters().is_empty());
     
     // Activate tag filtering mode
     app.activate_tag_filter();
     
     // Should be in tag filter mode
-    assert_eq!(app.is_tag_filter_active(), true);
+    assert!(app.is_tag_filter_active());
 }
 
 #[test]
@@ -0,0 +1,157 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::models::TagOperation;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::components::{LibraryTagAction, TagInputMode, TagManagementDialog};
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn setup_library() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("react.md"), "---\nname: \"react\"\ntags: [\"js\", \"ui\"]\n---\nReact").unwrap();
+    fs::write(prompts_dir.join("node.md"), "---\nname: \"node\"\ntags: [\"javascript\", \"js\"]\n---\nNode").unwrap();
+    fs::write(prompts_dir.join("rust.md"), "---\nname: \"rust\"\ntags: [\"lang/rust\"]\n---\nRust").unwrap();
+    temp_dir
+}
+
+fn read_prompt(temp_dir: &TempDir, name: &str) -> String {
+    fs::read_to_string(temp_dir.path().join("prompts").join(format!("{}.md", name))).unwrap()
+}
+
+#[test]
+fn test_preview_does_not_change_files() {
+    let temp_dir = setup_library();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    let operation = TagOperation::Merge { sources: vec!["js".to_string()], into: "javascript".to_string() };
+    let mut changes = app.preview_tag_operation(&operation).unwrap();
+    changes.sort_by(|a, b| a.prompt.file_path.cmp(&b.prompt.file_path));
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].prompt.file_path, "node.md");
+    assert_eq!(changes[0].after, vec!["javascript"]);
+    assert_eq!(changes[1].after, vec!["javascript", "ui"]);
+    assert!(read_prompt(&temp_dir, "react").contains(r#"tags: ["js", "ui"]"#));
+}
+
+#[test]
+fn test_merge_rename_and_delete_across_prompts() {
+    let temp_dir = setup_library();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    app.apply_tag_operation(&TagOperation::Merge { sources: vec!["js".to_string()], into: "javascript".to_string() }).unwrap();
+    assert!(read_prompt(&temp_dir, "react").contains(r#"tags: ["javascript", "ui"]"#));
+    assert!(read_prompt(&temp_dir, "node").contains(r#"tags: ["javascript"]"#));
+
+    // Renaming a parent tag moves its children along
+    app.apply_tag_operation(&TagOperation::Rename { from: "lang".to_string(), to: "language".to_string() }).unwrap();
+    assert!(read_prompt(&temp_dir, "rust").contains(r#"tags: ["language/rust"]"#));
+
+    let changes = app.apply_tag_operation(&TagOperation::Delete("ui".to_string())).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert!(read_prompt(&temp_dir, "react").contains(r#"tags: ["javascript"]"#));
+
+    // The previous tags stay in the prompt's history
+    assert_eq!(app.list_revisions("react").unwrap().len(), 2);
+}
+
+#[test]
+fn test_rejects_unknown_or_clashing_tags() {
+    let temp_dir = setup_library();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    let missing = app.apply_tag_operation(&TagOperation::Delete("python".to_string()));
+    assert!(missing.unwrap_err().to_string().contains("No prompt is tagged: python"));
+
+    let clash = app.apply_tag_operation(&TagOperation::Rename { from: "js".to_string(), to: "javascript".to_string() });
+    assert!(clash.unwrap_err().to_string().contains("Tag already in use: javascript"));
+    assert!(read_prompt(&temp_dir, "react").contains(r#"tags: ["js", "ui"]"#));
+}
+
+#[test]
+fn test_tag_cli_previews_and_applies() {
+    let temp_dir = setup_library();
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["tag", "rename", "ui", "frontend", "--dry-run"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("react.md: [js, ui] -> [js, frontend]"))
+        .stdout(predicate::str::contains("1 prompt(s) would change"));
+    assert!(read_prompt(&temp_dir, "react").contains("\"ui\""));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["tag", "merge", "js", "--into", "javascript"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Updated 2 prompt(s)"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["tag", "list", "--counts"])
+        .arg("--path")
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("javascript  2"))
+        .stdout(predicate::str::contains("lang  1"))
+        .stdout(predicate::str::contains("js  ").not());
+}
+
+#[test]
+fn test_tag_dialog_builds_library_operations() {
+    let mut dialog = TagManagementDialog::new(vec!["js".to_string(), "ui".to_string()]);
+
+    // Renaming starts from the chosen tag
+    dialog.start_library_action(LibraryTagAction::Rename);
+    assert_eq!(dialog.choose_library_tag(), None);
+    assert_eq!(*dialog.input_mode(), TagInputMode::EnteringTarget(LibraryTagAction::Rename));
+    dialog.add_char('x');
+    assert_eq!(
+        dialog.get_library_operation(),
+        Some(TagOperation::Rename { from: "js".to_string(), to: "jsx".to_string() })
+    );
+
+    // Deleting needs no target
+    dialog.cancel_input();
+    dialog.start_library_action(LibraryTagAction::Delete);
+    dialog.move_selection_down();
+    let operation = dialog.choose_library_tag().unwrap();
+    assert_eq!(operation, TagOperation::Delete("ui".to_string()));
+
+    dialog.show_preview(operation.clone(), Vec::new());
+    assert_eq!(*dialog.input_mode(), TagInputMode::ConfirmingLibraryChange);
+    assert_eq!(dialog.pending_operation(), Some(&operation));
+}
+
+#[test]
+fn test_failed_operation_restores_files_without_recording_history() {
+    let temp_dir = setup_library();
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    let operation = TagOperation::Merge { sources: vec!["js".to_string()], into: "javascript".to_string() };
+    app.preview_tag_operation(&operation).unwrap();
+    let react = read_prompt(&temp_dir, "react");
+    let node = read_prompt(&temp_dir, "node");
+
+    // A file in place of one prompt's history directory makes writing that prompt fail
+    let history_dir = temp_dir.path().join(".history");
+    fs::create_dir_all(&history_dir).unwrap();
+    fs::write(history_dir.join("react"), "").unwrap();
+
+    assert!(app.apply_tag_operation(&operation).is_err());
+    assert_eq!(read_prompt(&temp_dir, "react"), react);
+    assert_eq!(read_prompt(&temp_dir, "node"), node);
+
+    // Undoing the merge does not leave the merged tags behind as a revision
+    if let Ok(revisions) = fs::read_dir(history_dir.join("node")) {
+        for revision in revisions {
+            assert_eq!(fs::read_to_string(revision.unwrap().path()).unwrap(), node);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::TUIApp;
+use fink::utils::config::Config;
+use predicates::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use tempfile::tempdir;
+
+fn setup_prompt(base_path: &std::path::Path) {
+    let prompts_dir = base_path.join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(
+        prompts_dir.join("review.md"),
+        "---\nname: \"review\"\n---\nReview this {{language}} code for {{ ticket }}.",
+    ).unwrap();
+}
+
+#[test]
+fn should_render_prompt_with_variables() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    let values: HashMap<String, String> = [
+        ("language".to_string(), "Rust".to_string()),
+        ("ticket".to_string(), "FINK-42".to_string()),
+    ].into_iter().collect();
+
+    // Act
+    let rendered = app.render_prompt("review", &values).unwrap();
+
+    // Assert
+    assert_eq!(rendered.trim(), "Review this Rust code for FINK-42.");
+}
+
+#[test]
+fn should_fail_on_unresolved_variables() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    let values: HashMap<String, String> = [("language".to_string(), "Rust".to_string())].into_iter().collect();
+
+    // Act
+    let result = app.render_prompt("review", &values);
+
+    // Assert
+    assert_eq!(result.unwrap_err().to_string(), "Unresolved template variables: ticket");
+}
+
+#[test]
+fn should_fill_variables_from_cli() {
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "review", "--var", "language=Rust", "--var", "ticket=FINK-42", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Review this Rust code for FINK-42."));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "review", "--var", "language=Rust", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unresolved template variables: ticket"))
+        .stderr(predicate::str::contains("--var ticket=..."));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["get", "review", "--var", "language", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected KEY=VALUE"));
+}
+
+#[test]
+fn should_open_variable_form_before_copying() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+
+    // Act
+    app.copy_selected_to_clipboard().unwrap();
+
+    // Assert
+    assert!(app.is_variable_dialog_active());
+    let dialog = app.get_variable_dialog_mut().unwrap();
+    assert_eq!(dialog.variables(), ["language".to_string(), "ticket".to_string()]);
+    for c in "Rust".chars() {
+        dialog.add_char(c);
+    }
+    dialog.next_field();
+    dialog.set_value("ticket", "FINK-42");
+    assert!(dialog.is_last_field());
+    assert_eq!(dialog.rendered().trim(), "Review this Rust code for FINK-42.");
+
+    app.close_variable_dialog();
+    assert!(!app.is_variable_dialog_active());
+}
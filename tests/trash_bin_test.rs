@@ -0,0 +1,153 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use fink::storage::trash::TrashStore;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+fn setup_prompt(base_path: &std::path::Path, file_path: &str) {
+    let path = base_path.join("prompts").join(file_path);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, "---\nname: \"review\"\ntype: \"whole\"\n---\nReview the code.").unwrap();
+}
+
+#[test]
+fn should_move_deleted_prompt_to_trash() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path(), "backend/review.md");
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    app.delete_prompt("backend/review", true).unwrap();
+
+    // Assert
+    assert!(!temp_dir.path().join("prompts/backend/review.md").exists());
+    assert!(app.list_prompts(None).unwrap().is_empty());
+    let entries = app.list_trash().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].original_path, "backend/review.md");
+}
+
+#[test]
+fn should_restore_trashed_prompt_by_name() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path(), "backend/review.md");
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    app.delete_prompt("backend/review", true).unwrap();
+
+    // Act
+    let entry = app.restore_from_trash("backend/review").unwrap();
+
+    // Assert
+    assert_eq!(entry.prompt_id(), "backend/review");
+    assert!(temp_dir.path().join("prompts/backend/review.md").exists());
+    assert!(app.list_trash().unwrap().is_empty());
+}
+
+#[test]
+fn should_not_restore_over_existing_prompt() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path(), "review.md");
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+    app.delete_prompt("review", true).unwrap();
+    setup_prompt(temp_dir.path(), "review.md");
+
+    // Act
+    let result = app.restore_from_trash("review");
+
+    // Assert
+    assert!(result.unwrap_err().to_string().contains("already exists"));
+    assert_eq!(app.list_trash().unwrap().len(), 1);
+}
+
+#[test]
+fn should_not_leave_a_record_when_the_move_fails() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    let store = TrashStore::new(temp_dir.path());
+
+    // Act
+    let result = store.trash("missing.md");
+
+    // Assert
+    assert!(result.is_err());
+    assert_eq!(fs::read_dir(temp_dir.path().join(".trash")).unwrap().count(), 0);
+}
+
+#[test]
+fn should_purge_only_old_entries() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path(), "old.md");
+    setup_prompt(temp_dir.path(), "new.md");
+    let store = TrashStore::new(temp_dir.path());
+    let old = store.trash("old.md").unwrap();
+    store.trash("new.md").unwrap();
+    
+    // Backdate the first deletion by 40 days
+    let record_path = temp_dir.path().join(".trash").join(format!("{}.json", old.id));
+    let mut record: serde_json::Value = serde_json::from_str(&fs::read_to_string(&record_path).unwrap()).unwrap();
+    record["deleted_at"] = serde_json::json!((old.deleted_at - chrono::Duration::days(40)).to_rfc3339());
+    fs::write(&record_path, record.to_string()).unwrap();
+
+    // Act
+    let removed = store.purge_older_than(30).unwrap();
+
+    // Assert
+    assert_eq!(removed, 1);
+    let remaining = store.list().unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].original_path, "new.md");
+}
+
+#[test]
+fn should_manage_trash_from_cli() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path(), "review.md");
+
+    // Act & Assert
+    Command::cargo_bin("fink").unwrap()
+        .args(["delete", "review", "--force", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["trash", "list", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("review"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["trash", "restore", "review", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored 'review'"));
+    assert!(temp_dir.path().join("prompts/review.md").exists());
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["delete", "review", "--force", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["trash", "empty", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure();
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["trash", "empty", "--force", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Permanently removed 1 prompt(s)"));
+}
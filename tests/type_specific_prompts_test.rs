@@ -6,11 +6,11 @@ fn test_type_specific_prompts_exist_for_all_types() {
     let type_prompts = get_type_specific_prompts();
     
     // Check that we have prompts for each type
-    assert!(type_prompts.contains_key(&PromptType::Instruction));
-    assert!(type_prompts.contains_key(&PromptType::Context));
-    assert!(type_prompts.contains_key(&PromptType::InputIndicator));
-    assert!(type_prompts.contains_key(&PromptType::OutputIndicator));
-    assert!(type_prompts.contains_key(&PromptType::Etc));
+    assert!(type_prompts.contains_key(&PromptType::INSTRUCTION));
+    assert!(type_prompts.contains_key(&PromptType::CONTEXT));
+    assert!(type_prompts.contains_key(&PromptType::INPUT_INDICATOR));
+    assert!(type_prompts.contains_key(&PromptType::OUTPUT_INDICATOR));
+    assert!(type_prompts.contains_key(&PromptType::ETC));
     
     // Each type should have at least one prompt
     for (prompt_type, prompts) in type_prompts.iter() {
@@ -21,7 +21,7 @@ fn test_type_specific_prompts_exist_for_all_types() {
 #[test]
 fn test_instruction_type_prompts() {
     let type_prompts = get_type_specific_prompts();
-    let instruction_prompts = type_prompts.get(&PromptType::Instruction).unwrap();
+    let instruction_prompts = type_prompts.get(&PromptType::INSTRUCTION).unwrap();
     
     // Should have specific instruction prompts
     assert!(instruction_prompts.iter().any(|p| p.name.contains("step")));
@@ -31,7 +31,7 @@ fn test_instruction_type_prompts() {
 #[test]
 fn test_context_type_prompts() {
     let type_prompts = get_type_specific_prompts();
-    let context_prompts = type_prompts.get(&PromptType::Context).unwrap();
+    let context_prompts = type_prompts.get(&PromptType::CONTEXT).unwrap();
     
     // Should have context-providing prompts
     assert!(context_prompts.iter().any(|p| p.name.contains("context")));
@@ -41,7 +41,7 @@ fn test_context_type_prompts() {
 #[test]
 fn test_input_indicator_prompts() {
     let type_prompts = get_type_specific_prompts();
-    let input_prompts = type_prompts.get(&PromptType::InputIndicator).unwrap();
+    let input_prompts = type_prompts.get(&PromptType::INPUT_INDICATOR).unwrap();
     
     // Should have prompts for marking inputs
     assert!(input_prompts.iter().any(|p| p.name.contains("input")));
@@ -50,7 +50,7 @@ fn test_input_indicator_prompts() {
 #[test]
 fn test_output_indicator_prompts() {
     let type_prompts = get_type_specific_prompts();
-    let output_prompts = type_prompts.get(&PromptType::OutputIndicator).unwrap();
+    let output_prompts = type_prompts.get(&PromptType::OUTPUT_INDICATOR).unwrap();
     
     // Should have prompts for marking outputs
     assert!(output_prompts.iter().any(|p| p.name.contains("output")));
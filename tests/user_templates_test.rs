@@ -0,0 +1,148 @@
+use assert_cmd::Command;
+use fink::presentation::tui::app::TUIApp;
+use fink::presentation::tui::components::{CreateDialog, CreateTemplate};
+use fink::utils::config::Config;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::tempdir;
+
+fn write_template(base_path: &std::path::Path, name: &str, content: &str) {
+    let templates_dir = base_path.join("templates");
+    fs::create_dir_all(&templates_dir).unwrap();
+    fs::write(templates_dir.join(format!("{}.md", name)), content).unwrap();
+}
+
+#[test]
+fn should_create_prompt_from_user_template() {
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("prompts")).unwrap();
+    write_template(
+        temp_dir.path(),
+        "review",
+        "---\ntags: [\"review\"]\n---\n# {{name}}\n\nType: {{type}}, created {{date}}.\nReview the {{language}} diff.\n",
+    );
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["create", "backend/pr-review", "--template", "review", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(temp_dir.path().join("prompts/backend/pr-review.md")).unwrap();
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    assert!(content.contains("name: \"pr-review\""));
+    assert!(content.contains("tags: [\"review\"]"));
+    assert!(content.contains("type: \"whole\""));
+    assert!(content.contains("# pr-review"));
+    assert!(content.contains(&format!("Type: whole, created {}.", today)));
+    assert!(content.contains("Review the {{language}} diff."));
+}
+
+#[test]
+fn should_report_unknown_template() {
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("prompts")).unwrap();
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["create", "x", "--template", "missing", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown template: missing"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["template", "edit", "missing", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Template not found: missing"));
+}
+
+#[test]
+fn should_not_read_templates_outside_the_templates_directory() {
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("prompts")).unwrap();
+    fs::write(temp_dir.path().join("prompts/secret.md"), "---\nname: \"secret\"\n---\nSecret").unwrap();
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["create", "x", "--template", "../prompts/secret", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a valid prompt path"));
+    assert!(!temp_dir.path().join("prompts/x.md").exists());
+}
+
+#[test]
+fn should_create_and_list_templates() {
+    let temp_dir = tempdir().unwrap();
+    write_template(temp_dir.path(), "basic", "# {{name}}\n");
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["template", "new", "Bug Report", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created template 'Bug Report'"));
+    assert!(temp_dir.path().join("templates/bug-report.md").exists());
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["template", "new", "bug-report", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    Command::cargo_bin("fink").unwrap()
+        .args(["template", "list", "--path"])
+        .arg(temp_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("clipboard (built-in)"))
+        .stdout(predicate::str::contains("bug-report"))
+        .stdout(predicate::str::contains("basic (built-in)").not());
+}
+
+#[test]
+fn should_offer_user_templates_in_create_dialog() {
+    // Arrange
+    let mut dialog = CreateDialog::with_templates(vec!["bug-report".to_string(), "review".to_string()]);
+    dialog.next_field();
+    dialog.next_field();
+
+    // Act & Assert
+    dialog.previous_template();
+    assert_eq!(dialog.get_template(), CreateTemplate::User("review".to_string()));
+    dialog.previous_template();
+    assert_eq!(dialog.get_template(), CreateTemplate::User("bug-report".to_string()));
+    dialog.next_template();
+    dialog.next_template();
+    assert_eq!(dialog.get_template(), CreateTemplate::FromClipboard);
+}
+
+#[test]
+fn should_create_prompt_from_user_template_in_tui() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    fs::create_dir_all(temp_dir.path().join("prompts")).unwrap();
+    write_template(temp_dir.path(), "review", "# {{name}} checklist\n");
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+
+    // Act
+    app.open_create_dialog();
+    let dialog = app.get_create_dialog_mut().unwrap();
+    for c in "security".chars() {
+        dialog.add_char(c);
+    }
+    dialog.next_field();
+    dialog.next_field();
+    dialog.previous_template();
+    app.confirm_create().unwrap();
+
+    // Assert
+    let content = fs::read_to_string(temp_dir.path().join("prompts/security.md")).unwrap();
+    assert!(content.starts_with("---\nname: \"security\"\ntags: []\ntype: \"whole\"\n---\n"));
+    assert!(content.contains("# security checklist"));
+}
@@ -82,4 +82,11 @@ fn error_should_indicate_if_recoverable() {
     let io_error = io::Error::new(io::ErrorKind::PermissionDenied, "no access");
     let storage_error = FinkError::Storage(StorageError::Io(io_error));
     assert!(!storage_error.is_recoverable()); // System error, harder to fix
-}
\ No newline at end of file
+}
+
+#[test]
+fn error_should_have_stable_code() {
+    assert_eq!(FinkError::Prompt(PromptError::NotFound("test".to_string())).code(), "prompt_not_found");
+    assert_eq!(FinkError::Storage(StorageError::Io(io::Error::other("disk"))).code(), "io_error");
+    assert_eq!(FinkError::Validation(ValidationError::MissingRequired("name".to_string())).code(), "missing_required");
+}
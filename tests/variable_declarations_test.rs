@@ -0,0 +1,169 @@
+use assert_cmd::Command;
+use fink::application::app::DefaultPromptApplication;
+use fink::application::traits::PromptApplication;
+use fink::presentation::tui::app::TUIApp;
+use fink::utils::config::Config;
+use fink::utils::frontmatter::FrontmatterUpdater;
+use fink::utils::variables::VariableKind;
+use std::collections::HashMap;
+use std::fs;
+use tempfile::tempdir;
+
+const PROMPT: &str = r#"---
+name: "review"
+variables:
+  language:
+    description: "Language of the code"
+    type: choice
+    choices: [rust, go, python]
+    default: rust
+  strict:
+    type: bool
+  max_issues:
+    type: number
+    required: true
+  notes:
+    type: multiline
+---
+Review this {{language}} code (strict: {{strict}}), listing at most {{max_issues}} issues.
+{{notes}}
+"#;
+
+fn setup_prompt(base_path: &std::path::Path) {
+    let prompts_dir = base_path.join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("review.md"), PROMPT).unwrap();
+}
+
+fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn should_read_variable_declarations_from_frontmatter() {
+    // Act
+    let specs = FrontmatterUpdater::variables(PROMPT).unwrap();
+
+    // Assert
+    let names: Vec<&str> = specs.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["language", "strict", "max_issues", "notes"]);
+    assert_eq!(specs[0].kind, VariableKind::Choice);
+    assert_eq!(specs[0].choices, ["rust", "go", "python"]);
+    assert_eq!(specs[0].default.as_deref(), Some("rust"));
+    assert_eq!(specs[0].description.as_deref(), Some("Language of the code"));
+    assert_eq!(specs[1].kind, VariableKind::Bool);
+    assert!(specs[2].required);
+    assert_eq!(specs[3].kind, VariableKind::Multiline);
+}
+
+#[test]
+fn should_apply_defaults_and_check_types_when_rendering() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let app = DefaultPromptApplication::new(temp_dir.path().to_path_buf()).unwrap();
+
+    // Act
+    let rendered = app.render_prompt("review", &values(&[("max_issues", "5"), ("strict", "true")])).unwrap();
+    let missing = app.render_prompt("review", &HashMap::new());
+    let invalid = app.render_prompt("review", &values(&[("max_issues", "five")]));
+
+    // Assert
+    assert!(rendered.starts_with("Review this rust code (strict: true), listing at most 5 issues."));
+    assert_eq!(missing.unwrap_err().to_string(), "Unresolved template variables: max_issues");
+    assert!(invalid.unwrap_err().to_string().contains("'max_issues' must be a number"));
+}
+
+#[test]
+fn should_print_variable_schema_as_json() {
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+
+    let output = Command::cargo_bin("fink").unwrap()
+        .args(["get", "review", "--vars", "--path"])
+        .arg(temp_dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema[0]["name"], "language");
+    assert_eq!(schema[0]["type"], "choice");
+    assert_eq!(schema[0]["choices"], serde_json::json!(["rust", "go", "python"]));
+    assert_eq!(schema[0]["default"], "rust");
+    assert_eq!(schema[2]["required"], true);
+    assert_eq!(schema[3]["type"], "multiline");
+}
+
+#[test]
+fn should_fill_typed_fields_in_variable_form() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    app.copy_selected_to_clipboard().unwrap();
+    let dialog = app.get_variable_dialog_mut().unwrap();
+
+    // Act: choice and bool fields cycle, number fields drop non-numeric input
+    dialog.cycle_value(true);
+    dialog.next_field();
+    dialog.add_char(' ');
+    dialog.next_field();
+    for c in "1x2".chars() {
+        dialog.add_char(c);
+    }
+    dialog.next_field();
+    for c in "first".chars() {
+        dialog.add_char(c);
+    }
+    dialog.insert_newline();
+    dialog.add_char('!');
+
+    // Assert
+    let rendered = dialog.submit().unwrap();
+    assert_eq!(
+        rendered,
+        "Review this go code (strict: true), listing at most 12 issues.\nfirst\n!"
+    );
+}
+
+#[test]
+fn should_keep_form_open_when_required_value_is_missing() {
+    // Arrange
+    let temp_dir = tempdir().unwrap();
+    setup_prompt(temp_dir.path());
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+    app.copy_selected_to_clipboard().unwrap();
+
+    // Act
+    let copied = app.submit_variable_dialog().unwrap();
+
+    // Assert
+    assert!(!copied);
+    let dialog = app.get_variable_dialog().unwrap();
+    assert_eq!(dialog.error(), Some("Unresolved template variables: max_issues"));
+}
+
+#[test]
+fn should_show_unparsable_frontmatter_in_the_status_line() {
+    let temp_dir = tempdir().unwrap();
+    let prompts_dir = temp_dir.path().join("prompts");
+    fs::create_dir_all(&prompts_dir).unwrap();
+    fs::write(prompts_dir.join("broken.md"), "---\nname: \"Broken\ntags: [a\n---\nHello").unwrap();
+    let mut config = Config::default();
+    config.set_storage_path(temp_dir.path().to_path_buf());
+    let mut app = TUIApp::new_with_config(&config).unwrap();
+
+    app.show_warnings();
+    assert!(app.get_error_message().unwrap().contains("Failed to parse frontmatter in broken.md"));
+
+    // Reading the prompt again does not repeat a warning already shown
+    app.clear_error();
+    assert_eq!(app.get_selected_content().as_deref(), Some("Hello"));
+    app.show_warnings();
+    assert_eq!(app.get_error_message(), None);
+}